@@ -10,7 +10,10 @@ import {
     getAssociatedTokenAddressSync
 } from "@solana/spl-token"
 import { PROGRAM_ID, CURVE_CONFIG_SEED, POOL_SEED_PREFIX, GLOBAL_SEED, LAMPORTS_PER_SOL, TOKEN_METADATA_PROGRAM_ID } from "@/lib/constants"
+import { getCreatorProfilePDA } from "@/lib/pdas"
+import { fetchCreatorLaunchCount } from "@/lib/solana"
 import { TooltipProvider } from "@/components/ui/tooltip"
+import { BN } from "bn.js"
 import Link from "next/link"
 
 
@@ -26,6 +29,7 @@ export default function LaunchPage() {
     const [decimals, setDecimals] = useState(6)
     const [initialSupply, setInitialSupply] = useState("1000000000")
     const [initialSol, setInitialSol] = useState("0.1")
+    const [initialBuy, setInitialBuy] = useState("")
 
     const [isLoading, setIsLoading] = useState(false)
     const [error, setError] = useState<string | null>(null)
@@ -33,10 +37,10 @@ export default function LaunchPage() {
 
     // Removed handleImageChange as we now use direct URI input
 
-    const derivePDAs = useCallback((symbolStr: string) => {
-        // Derive mint PDA
+    const derivePDAs = useCallback((launchIndex: number) => {
+        // Derive mint PDA from the creator's launch counter
         const [mint] = PublicKey.findProgramAddressSync(
-            [Buffer.from("mint"), Buffer.from(symbolStr)],
+            [Buffer.from("mint"), publicKey!.toBuffer(), new BN(launchIndex).toArrayLike(Buffer, "le", 8)],
             PROGRAM_ID
         )
 
@@ -96,6 +100,11 @@ export default function LaunchPage() {
             return
         }
 
+        if (!/^[A-Z0-9]+$/.test(symbol)) {
+            setError("Symbol may only contain letters A-Z and digits 0-9")
+            return
+        }
+
         setIsLoading(true)
         setError(null)
         setSuccess(null)
@@ -104,9 +113,12 @@ export default function LaunchPage() {
             // Use provided image URI or leave empty (fallback will show initials)
             let uri = imageUri || ""
 
-            const pdas = derivePDAs(symbol)
+            const [creatorProfile] = getCreatorProfilePDA(publicKey)
+            const launchIndex = await fetchCreatorLaunchCount(connection, creatorProfile)
+            const pdas = derivePDAs(launchIndex)
             const supplyLamports = BigInt(initialSupply) * BigInt(Math.pow(10, decimals))
             const solLamports = BigInt(Math.floor(parseFloat(initialSol) * LAMPORTS_PER_SOL))
+            const buyLamports = BigInt(Math.floor((parseFloat(initialBuy) || 0) * LAMPORTS_PER_SOL))
 
             console.log("Launching token:", {
                 name,
@@ -135,8 +147,10 @@ export default function LaunchPage() {
                     decimals,
                     initialSupply: supplyLamports,
                     initialSolReserve: solLamports,
+                    initialBuySol: buyLamports,
                 },
-                publicKey
+                publicKey,
+                launchIndex
             )
 
             // Add compute budget
@@ -335,6 +349,26 @@ export default function LaunchPage() {
                                 </p>
                             </div>
 
+                            {/* Initial Buy */}
+                            <div>
+                                <label className="block text-sm font-medium text-[#E9E1D8] mb-2">
+                                    Initial Buy (optional)
+                                </label>
+                                <div className="relative">
+                                    <input
+                                        type="text"
+                                        value={initialBuy}
+                                        onChange={(e) => setInitialBuy(e.target.value)}
+                                        placeholder="0"
+                                        className="w-full px-4 py-3 pr-16 rounded-xl bg-[#0E1518] border border-[#2A3338] text-[#E9E1D8] placeholder-[#5F6A6E] focus:outline-none focus:border-[#8C3A32] transition-colors"
+                                    />
+                                    <span className="absolute right-4 top-1/2 -translate-y-1/2 text-[#9FA6A3]">SOL</span>
+                                </div>
+                                <p className="text-xs text-[#5F6A6E] mt-1">
+                                    Buy your own token in the launch transaction, before anyone else can trade.
+                                </p>
+                            </div>
+
                             {/* Paper Hand Tax Notice */}
                             <div className="p-4 rounded-xl bg-[#8C3A32]/10 border border-[#8C3A32]/30">
                                 <div className="flex items-start gap-3">
@@ -185,12 +185,24 @@ export function TradePanel({ mint, onTradeComplete, className }: TradePanelProps
         style = 1 // SELL
       }
 
+      // Referral links carry the referrer's wallet as ?ref=
+      let referrerWallet: PublicKey | null = null
+      const ref = new URLSearchParams(window.location.search).get("ref")
+      if (ref) {
+        try {
+          referrerWallet = new PublicKey(ref)
+        } catch {
+          // Ignore malformed referral links
+        }
+      }
+
       // Create the swap transaction
       const transaction = await createSwapTransaction(
         connection,
         { amount: amountInSmallestUnit, style },
         mint,
-        publicKey
+        publicKey,
+        referrerWallet
       )
 
       // Send transaction via wallet adapter
@@ -33,10 +33,11 @@ export const PROGRAM_ID = new PublicKey(
     process.env.NEXT_PUBLIC_PROGRAM_ID || "8XQAVjtT1QSYgVp8WzhVdwuSvGfDX9UifZupiLvBe2Lh"
 );
 
-// Treasury wallet - where Paper Hand Tax goes
-export const TREASURY_WALLET = new PublicKey(
-    process.env.NEXT_PUBLIC_TREASURY_WALLET || "Gi2GLxRgXgtd6pyb378AhA4hcBEjbP6aNFWCfFgaAGoS"
-);
+// Treasury vault - program-owned PDA where Paper Hand Tax goes
+export const TREASURY_WALLET = PublicKey.findProgramAddressSync(
+    [Buffer.from("treasury_vault")],
+    PROGRAM_ID
+)[0];
 
 // Default token mint (optional, used for initial display)
 export const TOKEN_MINT = new PublicKey(
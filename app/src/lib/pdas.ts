@@ -1,4 +1,5 @@
 import { PublicKey } from "@solana/web3.js";
+import { BN } from "bn.js";
 import {
   PROGRAM_ID,
   CURVE_CONFIG_SEED,
@@ -62,11 +63,63 @@ export function getGlobalPDA(): [PublicKey, number] {
 }
 
 /**
- * Derive the Mint PDA for a new token launch (based on symbol)
+ * Derive the creator's launch counter PDA
  */
-export function getMintPDA(symbol: string): [PublicKey, number] {
+export function getCreatorProfilePDA(creator: PublicKey): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
-    [Buffer.from("mint"), Buffer.from(symbol)],
+    [Buffer.from("creator"), creator.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Derive the Mint PDA for a new token launch (based on the creator's launch counter)
+ */
+export function getMintPDA(creator: PublicKey, launchIndex: number): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("mint"), creator.toBuffer(), new BN(launchIndex).toArrayLike(Buffer, "le", 8)],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Derive the symbol registry PDA
+ */
+export function getSymbolRegistryPDA(): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("symbol_registry")],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Derive the registry entry PDA of a symbol (looked up with ASCII letters in
+ * upper case, as the program does)
+ */
+export function getSymbolEntryPDA(symbol: string): [PublicKey, number] {
+  const normalized = symbol.replace(/[a-z]/g, (c) => c.toUpperCase());
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("symbol"), Buffer.from(normalized)],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Derive the launch record PDA mapping a mint back to its launch
+ */
+export function getLaunchRecordPDA(mint: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("launch"), mint.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Derive the vesting escrow PDA holding a mint's creator allocation
+ */
+export function getVestingEscrowPDA(mint: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("vesting"), mint.toBuffer()],
     PROGRAM_ID
   );
 }
@@ -110,10 +163,12 @@ export function getAllPDAs(mint: PublicKey) {
 }
 
 /**
- * Get all PDAs for launching a new token (uses symbol to derive mint)
+ * Get all PDAs for launching a new token (uses the creator's launch counter to derive mint)
  */
-export function getLaunchPDAs(symbol: string, creator: PublicKey) {
-  const [mint, mintBump] = getMintPDA(symbol);
+export function getLaunchPDAs(creator: PublicKey, launchIndex: number) {
+  const [mint, mintBump] = getMintPDA(creator, launchIndex);
+  const [creatorProfile] = getCreatorProfilePDA(creator);
+  const [launchRecord] = getLaunchRecordPDA(mint);
   const [curveConfig, curveConfigBump] = getCurveConfigPDA();
   const [pool, poolBump] = getPoolPDA(mint);
   const [treasuryVault, treasuryBump] = getTreasuryVaultPDA();
@@ -129,6 +184,8 @@ export function getLaunchPDAs(symbol: string, creator: PublicKey) {
   return {
     mint,
     mintBump,
+    creatorProfile,
+    launchRecord,
     curveConfig,
     curveConfigBump,
     pool,
@@ -2,7 +2,14 @@ import { Connection, PublicKey, Transaction, TransactionInstruction, SystemProgr
 import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID, getAssociatedTokenAddressSync } from "@solana/spl-token";
 import { BN } from "bn.js";
 import { PROGRAM_ID, CURVE_CONFIG_SEED, POOL_SEED_PREFIX, GLOBAL_SEED, TREASURY_WALLET, TOKEN_METADATA_PROGRAM_ID } from "./constants";
-import { getCurveConfigPDA } from "./pdas";
+import {
+    getCurveConfigPDA,
+    getSymbolEntryPDA,
+    getSymbolRegistryPDA,
+    getUserPositionPDA,
+    getVestingEscrowPDA,
+} from "./pdas";
+import { fetchCreatorLaunchCount, fetchPool, fetchReferralReferrer } from "./solana";
 
 // Metaplex Token Metadata Program ID - imported from constants
 
@@ -16,15 +23,40 @@ export interface LaunchParams {
     decimals: number;
     initialSupply: bigint;
     initialSolReserve: bigint;
+    /** Pool-specific paperhand tax in bps; omit to use the global rate */
+    paperhandTaxBps?: number;
+    /** Track cost basis as FIFO lots instead of the average cost */
+    fifoLots?: boolean;
+    /** Lamports the creator spends buying its own token in the launch transaction */
+    initialBuySol?: bigint;
+    /** Part of the initial supply vesting to the creator instead of going to the pool */
+    creatorVesting?: {
+        /** Allocation in base units */
+        amount: bigint;
+        cliffSeconds: number;
+        durationSeconds: number;
+    };
 }
 
 /**
  * Derive all PDAs needed for the launch instruction
  */
-export function getLaunchPDAs(symbol: string, creator: PublicKey) {
-    // Mint PDA
+export function getLaunchPDAs(creator: PublicKey, launchIndex: number) {
+    // Launch counter PDA of the creator
+    const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("creator"), creator.toBuffer()],
+        PROGRAM_ID
+    );
+
+    // Mint PDA, derived from the creator's launch counter
     const [mint, mintBump] = PublicKey.findProgramAddressSync(
-        [Buffer.from("mint"), Buffer.from(symbol)],
+        [Buffer.from("mint"), creator.toBuffer(), new BN(launchIndex).toArrayLike(Buffer, "le", 8)],
+        PROGRAM_ID
+    );
+
+    // Launch record PDA (reverse index from the mint)
+    const [launchRecord] = PublicKey.findProgramAddressSync(
+        [Buffer.from("launch"), mint.toBuffer()],
         PROGRAM_ID
     );
 
@@ -56,12 +88,20 @@ export function getLaunchPDAs(symbol: string, creator: PublicKey) {
         PROGRAM_ID
     );
 
+    // Global Stats PDA
+    const [globalStats] = PublicKey.findProgramAddressSync(
+        [Buffer.from("global_stats")],
+        PROGRAM_ID
+    );
+
     // Pool token account (ATA for global)
     const poolTokenAccount = getAssociatedTokenAddressSync(mint, global, true);
 
     return {
+        creatorProfile,
         mint,
         mintBump,
+        launchRecord,
         metadata,
         metadataBump,
         pool,
@@ -70,6 +110,7 @@ export function getLaunchPDAs(symbol: string, creator: PublicKey) {
         globalBump,
         curveConfig,
         curveConfigBump,
+        globalStats,
         poolTokenAccount,
     };
 }
@@ -80,11 +121,14 @@ import { Program, AnchorProvider, Idl } from "@coral-xyz/anchor";
 
 /**
  * Create a launch transaction
+ * `launchIndex` is the creator's launch counter the mint is derived from;
+ * it's fetched when omitted
  */
 export async function createLaunchTransaction(
     connection: Connection,
     params: LaunchParams,
-    creator: PublicKey
+    creator: PublicKey,
+    launchIndex?: number
 ): Promise<Transaction> {
     // 1. Setup minimal Anchor Provider (read-only is fine for building instruction)
     const provider = new AnchorProvider(
@@ -101,10 +145,34 @@ export async function createLaunchTransaction(
     const program = new Program(IDL as Idl, provider);
 
     // 3. Derive PDAs (still needed for accounts)
-    const pdas = getLaunchPDAs(params.symbol, creator);
+    const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("creator"), creator.toBuffer()],
+        PROGRAM_ID
+    );
+    const pdas = getLaunchPDAs(
+        creator,
+        launchIndex ?? await fetchCreatorLaunchCount(connection, creatorProfile)
+    );
+
+    // The initial buy needs the creator's position, its lots in FIFO pools,
+    // the pool stats and the creator's token account
+    const initialBuy = params.initialBuySol !== undefined && params.initialBuySol > BigInt(0);
+    const [creatorPosition] = getUserPositionPDA(pdas.pool, creator);
+    const [creatorPositionLots] = PublicKey.findProgramAddressSync(
+        [Buffer.from("position_lots"), creatorPosition.toBuffer()],
+        PROGRAM_ID
+    );
+    const [poolStats] = PublicKey.findProgramAddressSync(
+        [Buffer.from("pool_stats"), pdas.pool.toBuffer()],
+        PROGRAM_ID
+    );
+
+    // The creator allocation is held by the vesting escrow's token account
+    const vesting = params.creatorVesting;
+    const [vestingEscrow] = getVestingEscrowPDA(pdas.mint);
 
     // 4. Build Instruction using Anchor
-    // program.methods.launch(name, symbol, uri, decimals, initialSupply, initialSolReserve)
+    // program.methods.launch(name, symbol, uri, decimals, initialSupply, initialSolReserve, paperhandTaxBps, costBasisMode, initialBuySol, creatorVesting)
     const instruction = await program.methods
         .launch(
             params.name,
@@ -112,15 +180,39 @@ export async function createLaunchTransaction(
             params.uri,
             params.decimals,
             new BN(params.initialSupply.toString()),
-            new BN(params.initialSolReserve.toString())
+            new BN(params.initialSolReserve.toString()),
+            params.paperhandTaxBps ?? null,
+            params.fifoLots ? { fifo: {} } : { averageCost: {} },
+            initialBuy ? new BN(params.initialBuySol!.toString()) : null,
+            vesting
+                ? {
+                    amount: new BN(vesting.amount.toString()),
+                    cliffSeconds: vesting.cliffSeconds,
+                    durationSeconds: vesting.durationSeconds,
+                }
+                : null
         )
         .accounts({
             dexConfigurationAccount: pdas.curveConfig,
+            creatorProfile: pdas.creatorProfile,
             mint: pdas.mint,
             metadata: pdas.metadata,
             pool: pdas.pool,
+            launchRecord: pdas.launchRecord,
+            globalStats: pdas.globalStats,
+            symbolRegistry: getSymbolRegistryPDA()[0],
+            symbolEntry: getSymbolEntryPDA(params.symbol)[0],
             globalAccount: pdas.global,
             poolTokenAccount: pdas.poolTokenAccount,
+            creatorPosition: initialBuy ? creatorPosition : null,
+            creatorPositionLots: initialBuy && params.fifoLots ? creatorPositionLots : null,
+            poolStats: initialBuy ? poolStats : null,
+            creatorTokenAccount: initialBuy ? getAssociatedTokenAddressSync(pdas.mint, creator) : null,
+            vestingEscrow: vesting ? vestingEscrow : null,
+            vestingTokenAccount: vesting ? getAssociatedTokenAddressSync(pdas.mint, vestingEscrow, true) : null,
+            // SPL Token launches; Token-2022 ones also pass the hook's extra account list
+            extraAccountMetaList: null,
+            transferHookProgram: null,
             creator: creator,
             systemProgram: SystemProgram.programId,
             tokenProgram: TOKEN_PROGRAM_ID,
@@ -184,6 +276,24 @@ export function getSwapPDAs(mint: PublicKey, user: PublicKey) {
         PROGRAM_ID
     );
 
+    // Referral PDA, holding the referrer binding
+    const [referral] = PublicKey.findProgramAddressSync(
+        [Buffer.from("referral"), pool.toBuffer(), user.toBuffer()],
+        PROGRAM_ID
+    );
+
+    // Pool Stats PDA
+    const [poolStats] = PublicKey.findProgramAddressSync(
+        [Buffer.from("pool_stats"), pool.toBuffer()],
+        PROGRAM_ID
+    );
+
+    // Global Stats PDA
+    const [globalStats] = PublicKey.findProgramAddressSync(
+        [Buffer.from("global_stats")],
+        PROGRAM_ID
+    );
+
     // Pool token account (ATA for global)
     const poolTokenAccount = getAssociatedTokenAddressSync(mint, global, true);
 
@@ -196,20 +306,42 @@ export function getSwapPDAs(mint: PublicKey, user: PublicKey) {
         global,
         treasuryVault,
         userPosition,
+        referral,
+        poolStats,
+        globalStats,
         poolTokenAccount,
         userTokenAccount,
     };
 }
 
+/**
+ * Referrer account registered by a wallet
+ */
+export function getReferrerPDA(wallet: PublicKey) {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("referrer"), wallet.toBuffer()],
+        PROGRAM_ID
+    )[0];
+}
+
 /**
  * Build the swap instruction
  */
 export function buildSwapInstruction(
     params: SwapParams,
     mint: PublicKey,
-    user: PublicKey
+    user: PublicKey,
+    creator: PublicKey | null = null,
+    fifoLots: boolean = false,
+    referrer: PublicKey | null = null
 ): TransactionInstruction {
     const pdas = getSwapPDAs(mint, user);
+    const positionLots = fifoLots
+        ? PublicKey.findProgramAddressSync(
+            [Buffer.from("position_lots"), pdas.userPosition.toBuffer()],
+            PROGRAM_ID
+        )[0]
+        : null;
 
     // Encode instruction data
     // Format: discriminator (8) + amount (8) + style (8)
@@ -236,7 +368,13 @@ export function buildSwapInstruction(
         { pubkey: pdas.pool, isSigner: false, isWritable: true },           // pool
         { pubkey: pdas.global, isSigner: false, isWritable: true },         // global_account
         { pubkey: pdas.treasuryVault, isSigner: false, isWritable: true },  // treasury_vault
+        { pubkey: creator ?? PROGRAM_ID, isSigner: false, isWritable: !!creator }, // creator (optional, program id = none)
+        { pubkey: referrer ?? PROGRAM_ID, isSigner: false, isWritable: !!referrer }, // referrer (optional)
+        { pubkey: pdas.referral, isSigner: false, isWritable: true },       // referral
         { pubkey: pdas.userPosition, isSigner: false, isWritable: true },   // user_position
+        { pubkey: positionLots ?? PROGRAM_ID, isSigner: false, isWritable: !!positionLots }, // position_lots (FIFO pools only)
+        { pubkey: pdas.poolStats, isSigner: false, isWritable: true },      // pool_stats
+        { pubkey: pdas.globalStats, isSigner: false, isWritable: true },    // global_stats
         { pubkey: mint, isSigner: false, isWritable: true },                // mint_token_one
         { pubkey: pdas.poolTokenAccount, isSigner: false, isWritable: true }, // pool_token_account_one
         { pubkey: pdas.userTokenAccount, isSigner: false, isWritable: true }, // user_token_account_one
@@ -257,12 +395,15 @@ export function buildSwapInstruction(
 /**
  * Create a swap transaction
  * Automatically creates user token account if it doesn't exist
+ * A wallet's referrer is bound on its first trade in the pool; `referrerWallet`
+ * only applies to that first trade
  */
 export async function createSwapTransaction(
     connection: Connection,
     params: SwapParams,
     mint: PublicKey,
-    user: PublicKey
+    user: PublicKey,
+    referrerWallet: PublicKey | null = null
 ): Promise<Transaction> {
     const { ComputeBudgetProgram } = await import("@solana/web3.js");
     const { createAssociatedTokenAccountInstruction } = await import("@solana/spl-token");
@@ -291,8 +432,20 @@ export async function createSwapTransaction(
         }
     }
 
-    // Add swap instruction
-    const swapInstruction = buildSwapInstruction(params, mint, user);
+    // Add swap instruction; the pool creator receives its share of any paperhand tax
+    const pool = await fetchPool(connection, pdas.pool);
+    const bound = await fetchReferralReferrer(connection, pdas.referral);
+    let referrer = bound ?? null;
+    if (bound === undefined && referrerWallet && !referrerWallet.equals(user)) {
+        const candidate = getReferrerPDA(referrerWallet);
+        // Only registered referrers can be bound
+        if (await connection.getAccountInfo(candidate)) {
+            referrer = candidate;
+        }
+    }
+    const swapInstruction = buildSwapInstruction(
+        params, mint, user, pool?.creator ?? null, pool?.fifoLots ?? false, referrer
+    );
     transaction.add(swapInstruction);
 
     const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
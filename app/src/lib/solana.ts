@@ -16,6 +16,10 @@ export interface LiquidityPool {
   reserveOne: BN;
   reserveTwo: BN;
   bump: number;
+  /** Pool launcher, paid the creator share of paperhand taxes (null if none) */
+  creator: PublicKey | null;
+  /** Whether the pool tracks cost basis as FIFO lots */
+  fifoLots: boolean;
 }
 
 export interface UserPosition {
@@ -24,6 +28,31 @@ export interface UserPosition {
   totalTokens: BN;
   totalSol: BN;
   bump: number;
+  /** Lifetime results, before tax (lamports) */
+  realizedProfit: BN;
+  realizedLoss: BN;
+  totalTaxPaid: BN;
+  buyCount: number;
+  sellCount: number;
+  /** Unix timestamps, 0 until the first trade */
+  firstTradeTimestamp: number;
+  lastTradeTimestamp: number;
+  totalSolIn: BN;
+  totalSolOut: BN;
+}
+
+export interface RankedPool {
+  mint: PublicKey;
+  marketCap: BN;
+}
+
+export interface GlobalStats {
+  totalLaunches: BN;
+  totalVolume: BN;
+  totalTax: BN;
+  graduatedPools: BN;
+  /** King of the hill, highest market cap first */
+  rankedPools: RankedPool[];
 }
 
 // Connection helper - uses QuickNode by default
@@ -45,7 +74,8 @@ export const IDL = {
       type: {
         kind: "struct",
         fields: [
-          { name: "fees", type: "f64" },
+          { name: "version", type: "u8" },
+          { name: "feeBps", type: "u16" },
           { name: "treasury", type: "publicKey" },
           { name: "paperhandTaxBps", type: "u16" }
         ]
@@ -100,11 +130,11 @@ export async function fetchCurveConfig(
     const accountInfo = await connection.getAccountInfo(configPDA);
     if (!accountInfo) return null;
 
-    // Parse account data (skip 8-byte discriminator)
+    // Parse account data (skip 8-byte discriminator and 1-byte version)
     const data = accountInfo.data.slice(8);
-    const fees = data.readDoubleLE(0);
-    const treasury = new PublicKey(data.slice(8, 40));
-    const paperhandTaxBps = data.readUInt16LE(40);
+    const fees = data.readUInt16LE(1) / 100; // fee_bps -> percent
+    const treasury = new PublicKey(data.slice(3, 35));
+    const paperhandTaxBps = data.readUInt16LE(35);
 
     return { fees, treasury, paperhandTaxBps };
   } catch (e) {
@@ -113,6 +143,20 @@ export async function fetchCurveConfig(
   }
 }
 
+/**
+ * Fetch how many tokens a creator has launched, i.e. the index of its next launch
+ */
+export async function fetchCreatorLaunchCount(
+  connection: Connection,
+  creatorProfilePDA: PublicKey
+): Promise<number> {
+  const accountInfo = await connection.getAccountInfo(creatorProfilePDA);
+  if (!accountInfo) return 0;
+
+  // Skip the discriminator and the creator
+  return Number(accountInfo.data.readBigUInt64LE(8 + 32));
+}
+
 /**
  * Fetch liquidity pool
  */
@@ -131,8 +175,13 @@ export async function fetchPool(
     const reserveOne = new BN(data.slice(72, 80), 'le');
     const reserveTwo = new BN(data.slice(80, 88), 'le');
     const bump = data[88];
+    // bump (88) is followed by version, pause flags and the tax override (89..94)
+    const creatorKey = data.length >= 126 ? new PublicKey(data.slice(94, 126)) : PublicKey.default;
+    const creator = creatorKey.equals(PublicKey.default) ? null : creatorKey;
+    // creator is followed by the reward accounting (126..158) and the cost basis mode
+    const fifoLots = data.length > 158 && data[158] === 1;
 
-    return { tokenOne, tokenTwo, totalSupply, reserveOne, reserveTwo, bump };
+    return { tokenOne, tokenTwo, totalSupply, reserveOne, reserveTwo, bump, creator, fifoLots };
   } catch (e) {
     // Silent fail - account may not exist yet
     return null;
@@ -156,14 +205,85 @@ export async function fetchUserPosition(
     const totalTokens = new BN(data.slice(64, 72), 'le');
     const totalSol = new BN(data.slice(72, 80), 'le');
     const bump = data[80];
+    // bump is followed by the version, entry time and reward accounting (81..115)
+    const realizedProfit = new BN(data.slice(115, 123), 'le');
+    const realizedLoss = new BN(data.slice(123, 131), 'le');
+    const totalTaxPaid = new BN(data.slice(131, 139), 'le');
+    const buyCount = data.readUInt32LE(139);
+    const sellCount = data.readUInt32LE(143);
+    const firstTradeTimestamp = Number(data.readBigInt64LE(147));
+    const lastTradeTimestamp = Number(data.readBigInt64LE(155));
+    const totalSolIn = new BN(data.slice(163, 171), 'le');
+    const totalSolOut = new BN(data.slice(171, 179), 'le');
 
-    return { pool, owner, totalTokens, totalSol, bump };
+    return {
+      pool, owner, totalTokens, totalSol, bump,
+      realizedProfit, realizedLoss, totalTaxPaid, buyCount, sellCount,
+      firstTradeTimestamp, lastTradeTimestamp, totalSolIn, totalSolOut,
+    };
   } catch (e) {
     // Silent fail - account may not exist yet
     return null;
   }
 }
 
+/**
+ * Fetch the referrer bound to a wallet's trades in a pool
+ * Returns undefined before the wallet's first trade, null if it traded without one
+ */
+export async function fetchReferralReferrer(
+  connection: Connection,
+  referralPDA: PublicKey
+): Promise<PublicKey | null | undefined> {
+  try {
+    const accountInfo = await connection.getAccountInfo(referralPDA);
+    if (!accountInfo) return undefined;
+
+    // Discriminator (8) + pool (32) + user (32), then the referrer
+    const referrerKey = new PublicKey(accountInfo.data.slice(72, 104));
+    return referrerKey.equals(PublicKey.default) ? null : referrerKey;
+  } catch (e) {
+    // Silent fail - account may not exist yet
+    return undefined;
+  }
+}
+
+/**
+ * Fetch protocol-wide statistics and the king-of-the-hill ranking
+ */
+export async function fetchGlobalStats(
+  connection: Connection
+): Promise<GlobalStats | null> {
+  try {
+    const [statsPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("global_stats")],
+      PROGRAM_ID
+    );
+    const accountInfo = await connection.getAccountInfo(statsPDA);
+    if (!accountInfo) return null;
+
+    const data = accountInfo.data.slice(8);
+    const totalLaunches = new BN(data.slice(0, 8), 'le');
+    const totalVolume = new BN(data.slice(8, 16), 'le');
+    const totalTax = new BN(data.slice(16, 24), 'le');
+    const graduatedPools = new BN(data.slice(24, 32), 'le');
+    // Ranking entries are mint (32) + market cap (8), followed by their count
+    const rankedCount = data[32 + 40 * 10];
+    const rankedPools: RankedPool[] = [];
+    for (let i = 0; i < rankedCount; i++) {
+      const offset = 32 + 40 * i;
+      rankedPools.push({
+        mint: new PublicKey(data.slice(offset, offset + 32)),
+        marketCap: new BN(data.slice(offset + 32, offset + 40), 'le'),
+      });
+    }
+
+    return { totalLaunches, totalVolume, totalTax, graduatedPools, rankedPools };
+  } catch (e) {
+    return null;
+  }
+}
+
 /**
  * Calculate swap output using bonding curve formula
  * dy = ydx / (x + dx)
@@ -1,27 +1,150 @@
 import { PublicKey, Connection } from "@solana/web3.js"
 import BN from "bn.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
 import * as borsh from "@coral-xyz/borsh" // eslint-disable-line @typescript-eslint/no-unused-vars
 import { PROGRAM_ID } from "../programId"
 
 export interface CurveConfigurationFields {
-  fees: number
+  version: number
+  feeBps: number
+  treasury: PublicKey
+  paperhandTaxBps: number
+  adminCouncil: PublicKey
+  pauseFlags: number
+  accruedFees: BN
+  taxBracketCount: number
+  taxBrackets: Array<types.TaxBracket>
+  minPaperhandTaxBps: number
+  maxPaperhandTaxBps: number
+  taxMode: types.TaxModeKind
+  lossBracketCount: number
+  lossBrackets: Array<types.LossBracket>
+  taxTreasuryBps: number
+  taxReserveBps: number
+  taxCreatorBps: number
+  taxRewardBps: number
+  untrackedTokenPolicy: types.UntrackedTokenPolicyKind
+  autoClosePositions: boolean
+  buybackBps: number
+  buybackMaxSolPerCall: BN
+  buybackCooldownSeconds: number
+  referralFeeBps: number
+  reserved: Array<number>
 }
 
 export interface CurveConfigurationJSON {
-  fees: number
+  version: number
+  feeBps: number
+  treasury: string
+  paperhandTaxBps: number
+  adminCouncil: string
+  pauseFlags: number
+  accruedFees: string
+  taxBracketCount: number
+  taxBrackets: Array<types.TaxBracketJSON>
+  minPaperhandTaxBps: number
+  maxPaperhandTaxBps: number
+  taxMode: types.TaxModeJSON
+  lossBracketCount: number
+  lossBrackets: Array<types.LossBracketJSON>
+  taxTreasuryBps: number
+  taxReserveBps: number
+  taxCreatorBps: number
+  taxRewardBps: number
+  untrackedTokenPolicy: types.UntrackedTokenPolicyJSON
+  autoClosePositions: boolean
+  buybackBps: number
+  buybackMaxSolPerCall: string
+  buybackCooldownSeconds: number
+  referralFeeBps: number
+  reserved: Array<number>
 }
 
 export class CurveConfiguration {
-  readonly fees: number
+  readonly version: number
+  readonly feeBps: number
+  readonly treasury: PublicKey
+  readonly paperhandTaxBps: number
+  readonly adminCouncil: PublicKey
+  readonly pauseFlags: number
+  readonly accruedFees: BN
+  readonly taxBracketCount: number
+  readonly taxBrackets: Array<types.TaxBracket>
+  readonly minPaperhandTaxBps: number
+  readonly maxPaperhandTaxBps: number
+  readonly taxMode: types.TaxModeKind
+  readonly lossBracketCount: number
+  readonly lossBrackets: Array<types.LossBracket>
+  readonly taxTreasuryBps: number
+  readonly taxReserveBps: number
+  readonly taxCreatorBps: number
+  readonly taxRewardBps: number
+  readonly untrackedTokenPolicy: types.UntrackedTokenPolicyKind
+  readonly autoClosePositions: boolean
+  readonly buybackBps: number
+  readonly buybackMaxSolPerCall: BN
+  readonly buybackCooldownSeconds: number
+  readonly referralFeeBps: number
+  readonly reserved: Array<number>
 
   static readonly discriminator = Buffer.from([
     225, 242, 252, 198, 63, 77, 56, 255,
   ])
 
-  static readonly layout = borsh.struct([borsh.f64("fees")])
+  static readonly layout = borsh.struct([
+    borsh.u8("version"),
+    borsh.u16("feeBps"),
+    borsh.publicKey("treasury"),
+    borsh.u16("paperhandTaxBps"),
+    borsh.publicKey("adminCouncil"),
+    borsh.u8("pauseFlags"),
+    borsh.u64("accruedFees"),
+    borsh.u8("taxBracketCount"),
+    borsh.array(types.TaxBracket.layout(), 8, "taxBrackets"),
+    borsh.u16("minPaperhandTaxBps"),
+    borsh.u16("maxPaperhandTaxBps"),
+    types.TaxMode.layout("taxMode"),
+    borsh.u8("lossBracketCount"),
+    borsh.array(types.LossBracket.layout(), 8, "lossBrackets"),
+    borsh.u16("taxTreasuryBps"),
+    borsh.u16("taxReserveBps"),
+    borsh.u16("taxCreatorBps"),
+    borsh.u16("taxRewardBps"),
+    types.UntrackedTokenPolicy.layout("untrackedTokenPolicy"),
+    borsh.bool("autoClosePositions"),
+    borsh.u16("buybackBps"),
+    borsh.u64("buybackMaxSolPerCall"),
+    borsh.u32("buybackCooldownSeconds"),
+    borsh.u16("referralFeeBps"),
+    borsh.array(borsh.u8(), 143, "reserved"),
+  ])
 
   constructor(fields: CurveConfigurationFields) {
-    this.fees = fields.fees
+    this.version = fields.version
+    this.feeBps = fields.feeBps
+    this.treasury = fields.treasury
+    this.paperhandTaxBps = fields.paperhandTaxBps
+    this.adminCouncil = fields.adminCouncil
+    this.pauseFlags = fields.pauseFlags
+    this.accruedFees = fields.accruedFees
+    this.taxBracketCount = fields.taxBracketCount
+    this.taxBrackets = fields.taxBrackets
+    this.minPaperhandTaxBps = fields.minPaperhandTaxBps
+    this.maxPaperhandTaxBps = fields.maxPaperhandTaxBps
+    this.taxMode = fields.taxMode
+    this.lossBracketCount = fields.lossBracketCount
+    this.lossBrackets = fields.lossBrackets
+    this.taxTreasuryBps = fields.taxTreasuryBps
+    this.taxReserveBps = fields.taxReserveBps
+    this.taxCreatorBps = fields.taxCreatorBps
+    this.taxRewardBps = fields.taxRewardBps
+    this.untrackedTokenPolicy = fields.untrackedTokenPolicy
+    this.autoClosePositions = fields.autoClosePositions
+    this.buybackBps = fields.buybackBps
+    this.buybackMaxSolPerCall = fields.buybackMaxSolPerCall
+    this.buybackCooldownSeconds = fields.buybackCooldownSeconds
+    this.referralFeeBps = fields.referralFeeBps
+    this.reserved = fields.reserved
   }
 
   static async fetch(
@@ -68,19 +191,103 @@ export class CurveConfiguration {
     const dec = CurveConfiguration.layout.decode(data.slice(8))
 
     return new CurveConfiguration({
-      fees: dec.fees,
+      version: dec.version,
+      feeBps: dec.feeBps,
+      treasury: dec.treasury,
+      paperhandTaxBps: dec.paperhandTaxBps,
+      adminCouncil: dec.adminCouncil,
+      pauseFlags: dec.pauseFlags,
+      accruedFees: dec.accruedFees,
+      taxBracketCount: dec.taxBracketCount,
+      taxBrackets: dec.taxBrackets.map(
+        (
+          item: any /* eslint-disable-line @typescript-eslint/no-explicit-any */
+        ) => types.TaxBracket.fromDecoded(item)
+      ),
+      minPaperhandTaxBps: dec.minPaperhandTaxBps,
+      maxPaperhandTaxBps: dec.maxPaperhandTaxBps,
+      taxMode: types.TaxMode.fromDecoded(dec.taxMode),
+      lossBracketCount: dec.lossBracketCount,
+      lossBrackets: dec.lossBrackets.map(
+        (
+          item: any /* eslint-disable-line @typescript-eslint/no-explicit-any */
+        ) => types.LossBracket.fromDecoded(item)
+      ),
+      taxTreasuryBps: dec.taxTreasuryBps,
+      taxReserveBps: dec.taxReserveBps,
+      taxCreatorBps: dec.taxCreatorBps,
+      taxRewardBps: dec.taxRewardBps,
+      untrackedTokenPolicy: types.UntrackedTokenPolicy.fromDecoded(dec.untrackedTokenPolicy),
+      autoClosePositions: dec.autoClosePositions,
+      buybackBps: dec.buybackBps,
+      buybackMaxSolPerCall: dec.buybackMaxSolPerCall,
+      buybackCooldownSeconds: dec.buybackCooldownSeconds,
+      referralFeeBps: dec.referralFeeBps,
+      reserved: dec.reserved,
     })
   }
 
   toJSON(): CurveConfigurationJSON {
     return {
-      fees: this.fees,
+      version: this.version,
+      feeBps: this.feeBps,
+      treasury: this.treasury.toString(),
+      paperhandTaxBps: this.paperhandTaxBps,
+      adminCouncil: this.adminCouncil.toString(),
+      pauseFlags: this.pauseFlags,
+      accruedFees: this.accruedFees.toString(),
+      taxBracketCount: this.taxBracketCount,
+      taxBrackets: this.taxBrackets.map((item) => item.toJSON()),
+      minPaperhandTaxBps: this.minPaperhandTaxBps,
+      maxPaperhandTaxBps: this.maxPaperhandTaxBps,
+      taxMode: this.taxMode.toJSON(),
+      lossBracketCount: this.lossBracketCount,
+      lossBrackets: this.lossBrackets.map((item) => item.toJSON()),
+      taxTreasuryBps: this.taxTreasuryBps,
+      taxReserveBps: this.taxReserveBps,
+      taxCreatorBps: this.taxCreatorBps,
+      taxRewardBps: this.taxRewardBps,
+      untrackedTokenPolicy: this.untrackedTokenPolicy.toJSON(),
+      autoClosePositions: this.autoClosePositions,
+      buybackBps: this.buybackBps,
+      buybackMaxSolPerCall: this.buybackMaxSolPerCall.toString(),
+      buybackCooldownSeconds: this.buybackCooldownSeconds,
+      referralFeeBps: this.referralFeeBps,
+      reserved: this.reserved,
     }
   }
 
   static fromJSON(obj: CurveConfigurationJSON): CurveConfiguration {
     return new CurveConfiguration({
-      fees: obj.fees,
+      version: obj.version,
+      feeBps: obj.feeBps,
+      treasury: new PublicKey(obj.treasury),
+      paperhandTaxBps: obj.paperhandTaxBps,
+      adminCouncil: new PublicKey(obj.adminCouncil),
+      pauseFlags: obj.pauseFlags,
+      accruedFees: new BN(obj.accruedFees),
+      taxBracketCount: obj.taxBracketCount,
+      taxBrackets: obj.taxBrackets.map((item) =>
+        types.TaxBracket.fromJSON(item)
+      ),
+      minPaperhandTaxBps: obj.minPaperhandTaxBps,
+      maxPaperhandTaxBps: obj.maxPaperhandTaxBps,
+      taxMode: types.TaxMode.fromJSON(obj.taxMode),
+      lossBracketCount: obj.lossBracketCount,
+      lossBrackets: obj.lossBrackets.map((item) =>
+        types.LossBracket.fromJSON(item)
+      ),
+      taxTreasuryBps: obj.taxTreasuryBps,
+      taxReserveBps: obj.taxReserveBps,
+      taxCreatorBps: obj.taxCreatorBps,
+      taxRewardBps: obj.taxRewardBps,
+      untrackedTokenPolicy: types.UntrackedTokenPolicy.fromJSON(obj.untrackedTokenPolicy),
+      autoClosePositions: obj.autoClosePositions,
+      buybackBps: obj.buybackBps,
+      buybackMaxSolPerCall: new BN(obj.buybackMaxSolPerCall),
+      buybackCooldownSeconds: obj.buybackCooldownSeconds,
+      referralFeeBps: obj.referralFeeBps,
+      reserved: obj.reserved,
     })
   }
 }
@@ -1,5 +1,6 @@
 import { PublicKey, Connection } from "@solana/web3.js"
 import BN from "bn.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
 import * as borsh from "@coral-xyz/borsh" // eslint-disable-line @typescript-eslint/no-unused-vars
 import { PROGRAM_ID } from "../programId"
 
@@ -10,6 +11,18 @@ export interface LiquidityPoolFields {
   reserveOne: BN
   reserveTwo: BN
   bump: number
+  version: number
+  pauseFlags: number
+  hasTaxOverride: boolean
+  taxOverrideBps: number
+  creator: PublicKey
+  rewardPerToken: BN
+  rewardTokens: BN
+  rewardBalance: BN
+  costBasisMode: types.CostBasisModeKind
+  buybackBalance: BN
+  lastBuybackTimestamp: BN
+  reserved: Array<number>
 }
 
 export interface LiquidityPoolJSON {
@@ -19,6 +32,18 @@ export interface LiquidityPoolJSON {
   reserveOne: string
   reserveTwo: string
   bump: number
+  version: number
+  pauseFlags: number
+  hasTaxOverride: boolean
+  taxOverrideBps: number
+  creator: string
+  rewardPerToken: string
+  rewardTokens: string
+  rewardBalance: string
+  costBasisMode: types.CostBasisModeJSON
+  buybackBalance: string
+  lastBuybackTimestamp: string
+  reserved: Array<number>
 }
 
 export class LiquidityPool {
@@ -28,6 +53,18 @@ export class LiquidityPool {
   readonly reserveOne: BN
   readonly reserveTwo: BN
   readonly bump: number
+  readonly version: number
+  readonly pauseFlags: number
+  readonly hasTaxOverride: boolean
+  readonly taxOverrideBps: number
+  readonly creator: PublicKey
+  readonly rewardPerToken: BN
+  readonly rewardTokens: BN
+  readonly rewardBalance: BN
+  readonly costBasisMode: types.CostBasisModeKind
+  readonly buybackBalance: BN
+  readonly lastBuybackTimestamp: BN
+  readonly reserved: Array<number>
 
   static readonly discriminator = Buffer.from([
     66, 38, 17, 64, 188, 80, 68, 129,
@@ -40,6 +77,18 @@ export class LiquidityPool {
     borsh.u64("reserveOne"),
     borsh.u64("reserveTwo"),
     borsh.u8("bump"),
+    borsh.u8("version"),
+    borsh.u8("pauseFlags"),
+    borsh.bool("hasTaxOverride"),
+    borsh.u16("taxOverrideBps"),
+    borsh.publicKey("creator"),
+    borsh.u128("rewardPerToken"),
+    borsh.u64("rewardTokens"),
+    borsh.u64("rewardBalance"),
+    types.CostBasisMode.layout("costBasisMode"),
+    borsh.u64("buybackBalance"),
+    borsh.i64("lastBuybackTimestamp"),
+    borsh.array(borsh.u8(), 43, "reserved"),
   ])
 
   constructor(fields: LiquidityPoolFields) {
@@ -49,6 +98,18 @@ export class LiquidityPool {
     this.reserveOne = fields.reserveOne
     this.reserveTwo = fields.reserveTwo
     this.bump = fields.bump
+    this.version = fields.version
+    this.pauseFlags = fields.pauseFlags
+    this.hasTaxOverride = fields.hasTaxOverride
+    this.taxOverrideBps = fields.taxOverrideBps
+    this.creator = fields.creator
+    this.rewardPerToken = fields.rewardPerToken
+    this.rewardTokens = fields.rewardTokens
+    this.rewardBalance = fields.rewardBalance
+    this.costBasisMode = fields.costBasisMode
+    this.buybackBalance = fields.buybackBalance
+    this.lastBuybackTimestamp = fields.lastBuybackTimestamp
+    this.reserved = fields.reserved
   }
 
   static async fetch(
@@ -101,6 +162,18 @@ export class LiquidityPool {
       reserveOne: dec.reserveOne,
       reserveTwo: dec.reserveTwo,
       bump: dec.bump,
+      version: dec.version,
+      pauseFlags: dec.pauseFlags,
+      hasTaxOverride: dec.hasTaxOverride,
+      taxOverrideBps: dec.taxOverrideBps,
+      creator: dec.creator,
+      rewardPerToken: dec.rewardPerToken,
+      rewardTokens: dec.rewardTokens,
+      rewardBalance: dec.rewardBalance,
+      costBasisMode: types.CostBasisMode.fromDecoded(dec.costBasisMode),
+      buybackBalance: dec.buybackBalance,
+      lastBuybackTimestamp: dec.lastBuybackTimestamp,
+      reserved: dec.reserved,
     })
   }
 
@@ -112,6 +185,18 @@ export class LiquidityPool {
       reserveOne: this.reserveOne.toString(),
       reserveTwo: this.reserveTwo.toString(),
       bump: this.bump,
+      version: this.version,
+      pauseFlags: this.pauseFlags,
+      hasTaxOverride: this.hasTaxOverride,
+      taxOverrideBps: this.taxOverrideBps,
+      creator: this.creator.toString(),
+      rewardPerToken: this.rewardPerToken.toString(),
+      rewardTokens: this.rewardTokens.toString(),
+      rewardBalance: this.rewardBalance.toString(),
+      costBasisMode: this.costBasisMode.toJSON(),
+      buybackBalance: this.buybackBalance.toString(),
+      lastBuybackTimestamp: this.lastBuybackTimestamp.toString(),
+      reserved: this.reserved,
     }
   }
 
@@ -123,6 +208,18 @@ export class LiquidityPool {
       reserveOne: new BN(obj.reserveOne),
       reserveTwo: new BN(obj.reserveTwo),
       bump: obj.bump,
+      version: obj.version,
+      pauseFlags: obj.pauseFlags,
+      hasTaxOverride: obj.hasTaxOverride,
+      taxOverrideBps: obj.taxOverrideBps,
+      creator: new PublicKey(obj.creator),
+      rewardPerToken: new BN(obj.rewardPerToken),
+      rewardTokens: new BN(obj.rewardTokens),
+      rewardBalance: new BN(obj.rewardBalance),
+      costBasisMode: types.CostBasisMode.fromJSON(obj.costBasisMode),
+      buybackBalance: new BN(obj.buybackBalance),
+      lastBuybackTimestamp: new BN(obj.lastBuybackTimestamp),
+      reserved: obj.reserved,
     })
   }
 }
@@ -4,19 +4,26 @@ import * as borsh from "@coral-xyz/borsh" // eslint-disable-line @typescript-esl
 import { PROGRAM_ID } from "../programId"
 
 export interface InitializeArgs {
-  fee: number
+  feeBps: number
+  paperhandTaxBps: number
 }
 
 export interface InitializeAccounts {
   dexConfigurationAccount: PublicKey
   /** CHECK */
   globalAccount: PublicKey
+  /** CHECK */
+  treasuryVault: PublicKey
+  programData: PublicKey
   admin: PublicKey
   rent: PublicKey
   systemProgram: PublicKey
 }
 
-export const layout = borsh.struct([borsh.f64("fee")])
+export const layout = borsh.struct([
+  borsh.u16("feeBps"),
+  borsh.u16("paperhandTaxBps"),
+])
 
 export function initialize(
   args: InitializeArgs,
@@ -30,6 +37,8 @@ export function initialize(
       isWritable: true,
     },
     { pubkey: accounts.globalAccount, isSigner: false, isWritable: true },
+    { pubkey: accounts.treasuryVault, isSigner: false, isWritable: true },
+    { pubkey: accounts.programData, isSigner: false, isWritable: false },
     { pubkey: accounts.admin, isSigner: true, isWritable: true },
     { pubkey: accounts.rent, isSigner: false, isWritable: false },
     { pubkey: accounts.systemProgram, isSigner: false, isWritable: false },
@@ -38,7 +47,8 @@ export function initialize(
   const buffer = Buffer.alloc(1000)
   const len = layout.encode(
     {
-      fee: args.fee,
+      feeBps: args.feeBps,
+      paperhandTaxBps: args.paperhandTaxBps,
     },
     buffer
   )
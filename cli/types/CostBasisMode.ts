@@ -0,0 +1,88 @@
+import { PublicKey } from "@solana/web3.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import BN from "bn.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as borsh from "@coral-xyz/borsh"
+
+export interface AverageCostJSON {
+  kind: "AverageCost"
+}
+
+export class AverageCost {
+  static readonly discriminator = 0
+  static readonly kind = "AverageCost"
+  readonly discriminator = 0
+  readonly kind = "AverageCost"
+
+  toJSON(): AverageCostJSON {
+    return {
+      kind: "AverageCost",
+    }
+  }
+
+  toEncodable() {
+    return {
+      AverageCost: {},
+    }
+  }
+}
+
+export interface FifoJSON {
+  kind: "Fifo"
+}
+
+export class Fifo {
+  static readonly discriminator = 1
+  static readonly kind = "Fifo"
+  readonly discriminator = 1
+  readonly kind = "Fifo"
+
+  toJSON(): FifoJSON {
+    return {
+      kind: "Fifo",
+    }
+  }
+
+  toEncodable() {
+    return {
+      Fifo: {},
+    }
+  }
+}
+
+// eslint-disable-next-line @typescript-eslint/no-explicit-any
+export function fromDecoded(obj: any): types.CostBasisModeKind {
+  if (typeof obj !== "object") {
+    throw new Error("Invalid enum object")
+  }
+
+  if ("AverageCost" in obj) {
+    return new AverageCost()
+  }
+  if ("Fifo" in obj) {
+    return new Fifo()
+  }
+
+  throw new Error("Invalid enum object")
+}
+
+export function fromJSON(obj: types.CostBasisModeJSON): types.CostBasisModeKind {
+  switch (obj.kind) {
+    case "AverageCost": {
+      return new AverageCost()
+    }
+    case "Fifo": {
+      return new Fifo()
+    }
+  }
+}
+
+export function layout(property?: string) {
+  const ret = borsh.rustEnum([
+    borsh.struct([], "AverageCost"),
+    borsh.struct([], "Fifo"),
+  ])
+  if (property !== undefined) {
+    return ret.replicate(property)
+  }
+  return ret
+}
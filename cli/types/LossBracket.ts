@@ -0,0 +1,64 @@
+import { PublicKey } from "@solana/web3.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import BN from "bn.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as borsh from "@coral-xyz/borsh"
+
+export interface LossBracketFields {
+  minLossBps: number
+  taxBps: number
+}
+
+export interface LossBracketJSON {
+  minLossBps: number
+  taxBps: number
+}
+
+export class LossBracket {
+  readonly minLossBps: number
+  readonly taxBps: number
+
+  constructor(fields: LossBracketFields) {
+    this.minLossBps = fields.minLossBps
+    this.taxBps = fields.taxBps
+  }
+
+  static layout(property?: string) {
+    return borsh.struct(
+      [borsh.u16("minLossBps"), borsh.u16("taxBps")],
+      property
+    )
+  }
+
+  // eslint-disable-next-line @typescript-eslint/no-explicit-any
+  static fromDecoded(obj: any) {
+    return new LossBracket({
+      minLossBps: obj.minLossBps,
+      taxBps: obj.taxBps,
+    })
+  }
+
+  static toEncodable(fields: LossBracketFields) {
+    return {
+      minLossBps: fields.minLossBps,
+      taxBps: fields.taxBps,
+    }
+  }
+
+  toJSON(): LossBracketJSON {
+    return {
+      minLossBps: this.minLossBps,
+      taxBps: this.taxBps,
+    }
+  }
+
+  static fromJSON(obj: LossBracketJSON): LossBracket {
+    return new LossBracket({
+      minLossBps: obj.minLossBps,
+      taxBps: obj.taxBps,
+    })
+  }
+
+  toEncodable() {
+    return LossBracket.toEncodable(this)
+  }
+}
@@ -0,0 +1,64 @@
+import { PublicKey } from "@solana/web3.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import BN from "bn.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as borsh from "@coral-xyz/borsh"
+
+export interface TaxBracketFields {
+  minHoldSeconds: number
+  taxBps: number
+}
+
+export interface TaxBracketJSON {
+  minHoldSeconds: number
+  taxBps: number
+}
+
+export class TaxBracket {
+  readonly minHoldSeconds: number
+  readonly taxBps: number
+
+  constructor(fields: TaxBracketFields) {
+    this.minHoldSeconds = fields.minHoldSeconds
+    this.taxBps = fields.taxBps
+  }
+
+  static layout(property?: string) {
+    return borsh.struct(
+      [borsh.u32("minHoldSeconds"), borsh.u16("taxBps")],
+      property
+    )
+  }
+
+  // eslint-disable-next-line @typescript-eslint/no-explicit-any
+  static fromDecoded(obj: any) {
+    return new TaxBracket({
+      minHoldSeconds: obj.minHoldSeconds,
+      taxBps: obj.taxBps,
+    })
+  }
+
+  static toEncodable(fields: TaxBracketFields) {
+    return {
+      minHoldSeconds: fields.minHoldSeconds,
+      taxBps: fields.taxBps,
+    }
+  }
+
+  toJSON(): TaxBracketJSON {
+    return {
+      minHoldSeconds: this.minHoldSeconds,
+      taxBps: this.taxBps,
+    }
+  }
+
+  static fromJSON(obj: TaxBracketJSON): TaxBracket {
+    return new TaxBracket({
+      minHoldSeconds: obj.minHoldSeconds,
+      taxBps: obj.taxBps,
+    })
+  }
+
+  toEncodable() {
+    return TaxBracket.toEncodable(this)
+  }
+}
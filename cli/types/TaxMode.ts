@@ -0,0 +1,118 @@
+import { PublicKey } from "@solana/web3.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import BN from "bn.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as borsh from "@coral-xyz/borsh"
+
+export interface FlatOnProceedsJSON {
+  kind: "FlatOnProceeds"
+}
+
+export class FlatOnProceeds {
+  static readonly discriminator = 0
+  static readonly kind = "FlatOnProceeds"
+  readonly discriminator = 0
+  readonly kind = "FlatOnProceeds"
+
+  toJSON(): FlatOnProceedsJSON {
+    return {
+      kind: "FlatOnProceeds",
+    }
+  }
+
+  toEncodable() {
+    return {
+      FlatOnProceeds: {},
+    }
+  }
+}
+
+export interface PercentOfLossJSON {
+  kind: "PercentOfLoss"
+}
+
+export class PercentOfLoss {
+  static readonly discriminator = 1
+  static readonly kind = "PercentOfLoss"
+  readonly discriminator = 1
+  readonly kind = "PercentOfLoss"
+
+  toJSON(): PercentOfLossJSON {
+    return {
+      kind: "PercentOfLoss",
+    }
+  }
+
+  toEncodable() {
+    return {
+      PercentOfLoss: {},
+    }
+  }
+}
+
+export interface ProgressiveByLossJSON {
+  kind: "ProgressiveByLoss"
+}
+
+export class ProgressiveByLoss {
+  static readonly discriminator = 2
+  static readonly kind = "ProgressiveByLoss"
+  readonly discriminator = 2
+  readonly kind = "ProgressiveByLoss"
+
+  toJSON(): ProgressiveByLossJSON {
+    return {
+      kind: "ProgressiveByLoss",
+    }
+  }
+
+  toEncodable() {
+    return {
+      ProgressiveByLoss: {},
+    }
+  }
+}
+
+// eslint-disable-next-line @typescript-eslint/no-explicit-any
+export function fromDecoded(obj: any): types.TaxModeKind {
+  if (typeof obj !== "object") {
+    throw new Error("Invalid enum object")
+  }
+
+  if ("FlatOnProceeds" in obj) {
+    return new FlatOnProceeds()
+  }
+  if ("PercentOfLoss" in obj) {
+    return new PercentOfLoss()
+  }
+  if ("ProgressiveByLoss" in obj) {
+    return new ProgressiveByLoss()
+  }
+
+  throw new Error("Invalid enum object")
+}
+
+export function fromJSON(obj: types.TaxModeJSON): types.TaxModeKind {
+  switch (obj.kind) {
+    case "FlatOnProceeds": {
+      return new FlatOnProceeds()
+    }
+    case "PercentOfLoss": {
+      return new PercentOfLoss()
+    }
+    case "ProgressiveByLoss": {
+      return new ProgressiveByLoss()
+    }
+  }
+}
+
+export function layout(property?: string) {
+  const ret = borsh.rustEnum([
+    borsh.struct([], "FlatOnProceeds"),
+    borsh.struct([], "PercentOfLoss"),
+    borsh.struct([], "ProgressiveByLoss"),
+  ])
+  if (property !== undefined) {
+    return ret.replicate(property)
+  }
+  return ret
+}
@@ -0,0 +1,118 @@
+import { PublicKey } from "@solana/web3.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import BN from "bn.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as borsh from "@coral-xyz/borsh"
+
+export interface RejectJSON {
+  kind: "Reject"
+}
+
+export class Reject {
+  static readonly discriminator = 0
+  static readonly kind = "Reject"
+  readonly discriminator = 0
+  readonly kind = "Reject"
+
+  toJSON(): RejectJSON {
+    return {
+      kind: "Reject",
+    }
+  }
+
+  toEncodable() {
+    return {
+      Reject: {},
+    }
+  }
+}
+
+export interface ZeroCostBasisJSON {
+  kind: "ZeroCostBasis"
+}
+
+export class ZeroCostBasis {
+  static readonly discriminator = 1
+  static readonly kind = "ZeroCostBasis"
+  readonly discriminator = 1
+  readonly kind = "ZeroCostBasis"
+
+  toJSON(): ZeroCostBasisJSON {
+    return {
+      kind: "ZeroCostBasis",
+    }
+  }
+
+  toEncodable() {
+    return {
+      ZeroCostBasis: {},
+    }
+  }
+}
+
+export interface FullLossJSON {
+  kind: "FullLoss"
+}
+
+export class FullLoss {
+  static readonly discriminator = 2
+  static readonly kind = "FullLoss"
+  readonly discriminator = 2
+  readonly kind = "FullLoss"
+
+  toJSON(): FullLossJSON {
+    return {
+      kind: "FullLoss",
+    }
+  }
+
+  toEncodable() {
+    return {
+      FullLoss: {},
+    }
+  }
+}
+
+// eslint-disable-next-line @typescript-eslint/no-explicit-any
+export function fromDecoded(obj: any): types.UntrackedTokenPolicyKind {
+  if (typeof obj !== "object") {
+    throw new Error("Invalid enum object")
+  }
+
+  if ("Reject" in obj) {
+    return new Reject()
+  }
+  if ("ZeroCostBasis" in obj) {
+    return new ZeroCostBasis()
+  }
+  if ("FullLoss" in obj) {
+    return new FullLoss()
+  }
+
+  throw new Error("Invalid enum object")
+}
+
+export function fromJSON(obj: types.UntrackedTokenPolicyJSON): types.UntrackedTokenPolicyKind {
+  switch (obj.kind) {
+    case "Reject": {
+      return new Reject()
+    }
+    case "ZeroCostBasis": {
+      return new ZeroCostBasis()
+    }
+    case "FullLoss": {
+      return new FullLoss()
+    }
+  }
+}
+
+export function layout(property?: string) {
+  const ret = borsh.rustEnum([
+    borsh.struct([], "Reject"),
+    borsh.struct([], "ZeroCostBasis"),
+    borsh.struct([], "FullLoss"),
+  ])
+  if (property !== undefined) {
+    return ret.replicate(property)
+  }
+  return ret
+}
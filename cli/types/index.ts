@@ -0,0 +1,34 @@
+import * as TaxMode from "./TaxMode"
+import * as CostBasisMode from "./CostBasisMode"
+import * as UntrackedTokenPolicy from "./UntrackedTokenPolicy"
+
+export { TaxBracket } from "./TaxBracket"
+export type { TaxBracketFields, TaxBracketJSON } from "./TaxBracket"
+export { LossBracket } from "./LossBracket"
+export type { LossBracketFields, LossBracketJSON } from "./LossBracket"
+export { TaxMode }
+
+export type TaxModeKind =
+  | TaxMode.FlatOnProceeds
+  | TaxMode.PercentOfLoss
+  | TaxMode.ProgressiveByLoss
+export type TaxModeJSON =
+  | TaxMode.FlatOnProceedsJSON
+  | TaxMode.PercentOfLossJSON
+  | TaxMode.ProgressiveByLossJSON
+export { CostBasisMode }
+
+export type CostBasisModeKind = CostBasisMode.AverageCost | CostBasisMode.Fifo
+export type CostBasisModeJSON =
+  | CostBasisMode.AverageCostJSON
+  | CostBasisMode.FifoJSON
+export { UntrackedTokenPolicy }
+
+export type UntrackedTokenPolicyKind =
+  | UntrackedTokenPolicy.Reject
+  | UntrackedTokenPolicy.ZeroCostBasis
+  | UntrackedTokenPolicy.FullLoss
+export type UntrackedTokenPolicyJSON =
+  | UntrackedTokenPolicy.RejectJSON
+  | UntrackedTokenPolicy.ZeroCostBasisJSON
+  | UntrackedTokenPolicy.FullLossJSON
@@ -0,0 +1,308 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::Instruction, program::invoke_signed};
+use anchor_lang::{system_program, InstructionData};
+use anchor_spl::{
+    token_2022::spl_token_2022::{
+        self,
+        extension::{
+            transfer_hook::TransferHookAccount, BaseStateWithExtensions, StateWithExtensions,
+        },
+    },
+    token_interface::{Mint, TokenAccount},
+};
+use pump::{
+    consts::{HOOK_AUTHORITY_SEED, TRANSFER_HOOK_EXTRA_ACCOUNTS},
+    program::Pump,
+    state::{LiquidityPool, PoolStats, UserPosition},
+};
+use spl_discriminator::SplDiscriminate;
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList};
+use spl_transfer_hook_interface::instruction::{
+    ExecuteInstruction, InitializeExtraAccountMetaListInstruction, TransferHookInstruction,
+};
+
+declare_id!("8jL4kHLoeevdgiBs2qQMgyQ9mm5PUua1Qkkei4rm5xue");
+
+/// Seed of the extra account list, fixed by the transfer hook interface
+pub const EXTRA_ACCOUNT_METAS_SEED: &str = "extra-account-metas";
+
+/// Transfer hook of pump's Token-2022 launches
+///
+/// Token-2022 calls `execute` on every transfer of such a mint. Transfers made
+/// by pump itself (curve trades, `transfer_position`, vesting claims) already
+/// do their own accounting; for any other transfer the hook has pump move the
+/// tokens' share of the sender's cost basis with `record_hook_transfer`.
+#[program]
+pub mod pump_hook {
+    use super::*;
+
+    /// Write this hook's extra account list for a mint launched by pump
+    ///
+    /// The list is always the one from `extra_account_metas`, whatever the
+    /// caller passes. The interface has no payer, so the list account must
+    /// already hold its rent (pump's `launch` funds it).
+    #[instruction(discriminator = InitializeExtraAccountMetaListInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.mint.mint_authority == Some(ctx.accounts.authority.key()).into(),
+            HookError::Unauthorized
+        );
+        require_keys_eq!(ctx.accounts.authority.key(), pump_global(), HookError::Unauthorized);
+
+        let metas = extra_account_metas()?;
+        let space = ExtraAccountMetaList::size_of(metas.len())?;
+        let mint = ctx.accounts.mint.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            EXTRA_ACCOUNT_METAS_SEED.as_bytes(),
+            mint.as_ref(),
+            &[ctx.bumps.extra_account_meta_list],
+        ]];
+
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Allocate {
+                    account_to_allocate: ctx.accounts.extra_account_meta_list.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            space as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Assign {
+                    account_to_assign: ctx.accounts.extra_account_meta_list.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            &crate::ID,
+        )?;
+
+        let mut data = ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?;
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &metas)?;
+        Ok(())
+    }
+
+    /// Carry the cost basis along with a transfer made outside pump
+    ///
+    /// # Arguments
+    /// * `amount` - Number of tokens transferred
+    #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn execute(ctx: Context<Execute>, amount: u64) -> Result<()> {
+        // Only Token-2022 may call the hook, in the middle of a transfer
+        check_is_transferring(&ctx.accounts.source_token)?;
+
+        if is_pump_transfer(&ctx)? {
+            return Ok(());
+        }
+
+        let list_data = ctx.accounts.extra_account_meta_list.try_borrow_data()?;
+        ExtraAccountMetaList::check_account_infos::<ExecuteInstruction>(
+            &ctx.accounts.to_account_infos(),
+            &TransferHookInstruction::Execute { amount }.pack(),
+            &crate::ID,
+            &list_data,
+        )?;
+        drop(list_data);
+
+        let accounts = pump::accounts::RecordHookTransfer {
+            hook_authority: ctx.accounts.hook_authority.key(),
+            pool: ctx.accounts.pool.key(),
+            mint: ctx.accounts.mint.key(),
+            source_token_account: ctx.accounts.source_token.key(),
+            destination_token_account: ctx.accounts.destination_token.key(),
+            source_position: ctx.accounts.source_position.key(),
+            destination_position: ctx.accounts.destination_position.key(),
+            pool_stats: ctx.accounts.pool_stats.key(),
+        };
+        let ix = Instruction {
+            program_id: pump::ID,
+            accounts: accounts.to_account_metas(None),
+            data: pump::instruction::RecordHookTransfer { amount }.data(),
+        };
+        invoke_signed(
+            &ix,
+            &[
+                ctx.accounts.hook_authority.to_account_info(),
+                ctx.accounts.pool.to_account_info(),
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.source_token.to_account_info(),
+                ctx.accounts.destination_token.to_account_info(),
+                ctx.accounts.source_position.to_account_info(),
+                ctx.accounts.destination_position.to_account_info(),
+                ctx.accounts.pool_stats.to_account_info(),
+                ctx.accounts.pump_program.to_account_info(),
+            ],
+            &[&[HOOK_AUTHORITY_SEED.as_bytes(), &[ctx.bumps.hook_authority]]],
+        )?;
+        Ok(())
+    }
+}
+
+/// Accounts the hook needs after the interface's five (source, mint,
+/// destination, owner and this list)
+///
+/// The positions are derived from the owners stored in the token accounts,
+/// at offset 32 of their data, so they belong to the wallets and not to a
+/// delegate signing the transfer.
+pub fn extra_account_metas() -> Result<[ExtraAccountMeta; TRANSFER_HOOK_EXTRA_ACCOUNTS]> {
+    let position_of = |token_account_index: u8| {
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            6,
+            &[
+                Seed::Literal { bytes: UserPosition::SEED_PREFIX.as_bytes().to_vec() },
+                Seed::AccountKey { index: 7 },
+                Seed::AccountData { account_index: token_account_index, data_index: 32, length: 32 },
+            ],
+            false,
+            true,
+        )
+    };
+
+    Ok([
+        // 5: instructions sysvar, to tell pump's own transfers apart
+        ExtraAccountMeta::new_with_pubkey(&solana_instructions_sysvar::ID, false, false)?,
+        // 6: pump
+        ExtraAccountMeta::new_with_pubkey(&pump::ID, false, false)?,
+        // 7: the mint's pool
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            6,
+            &[
+                Seed::Literal { bytes: LiquidityPool::POOL_SEED_PREFIX.as_bytes().to_vec() },
+                Seed::AccountKey { index: 1 },
+            ],
+            false,
+            true,
+        )?,
+        // 8 and 9: positions of the source and destination owners
+        position_of(0)?,
+        position_of(2)?,
+        // 10: this program's signer for `record_hook_transfer`
+        ExtraAccountMeta::new_with_seeds(
+            &[Seed::Literal { bytes: HOOK_AUTHORITY_SEED.as_bytes().to_vec() }],
+            false,
+            false,
+        )?,
+        // 11: the pool's statistics, to count new holders
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            6,
+            &[
+                Seed::Literal { bytes: PoolStats::SEED_PREFIX.as_bytes().to_vec() },
+                Seed::AccountKey { index: 7 },
+            ],
+            false,
+            true,
+        )?,
+    ])
+}
+
+fn pump_global() -> Pubkey {
+    Pubkey::find_program_address(&[b"global"], &pump::ID).0
+}
+
+fn check_is_transferring(source: &InterfaceAccount<TokenAccount>) -> Result<()> {
+    let info = source.to_account_info();
+    let data = info.try_borrow_data()?;
+    let account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+    let extension = account.get_extension::<TransferHookAccount>()?;
+    require!(bool::from(extension.transferring), HookError::NotTransferring);
+    Ok(())
+}
+
+/// Whether pump made the transfer, and so already accounted for it
+///
+/// Curve trades and the initial buy move tokens to or from the global
+/// vault, and vesting claims are signed by pump's escrow. Everything else
+/// pump transfers runs as one of its top-level instructions; calling back
+/// into pump from there would be reentrant.
+fn is_pump_transfer(ctx: &Context<Execute>) -> Result<bool> {
+    let global = pump_global();
+    if ctx.accounts.source_token.owner == global || ctx.accounts.destination_token.owner == global {
+        return Ok(true);
+    }
+    if ctx.accounts.owner.owner == &pump::ID {
+        return Ok(true);
+    }
+
+    let instructions = ctx.accounts.instructions_sysvar.to_account_info();
+    let index = solana_instructions_sysvar::load_current_index_checked(&instructions)?;
+    let instruction =
+        solana_instructions_sysvar::load_instruction_at_checked(index as usize, &instructions)?;
+    Ok(instruction.program_id == pump::ID)
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    /// CHECK: Extra account list of the mint, allocated in the instruction
+    #[account(
+        mut,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED.as_bytes(), mint.key().as_ref()],
+        bump,
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Mint authority, pump's global vault
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Execute<'info> {
+    #[account(token::mint = mint)]
+    pub source_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(token::mint = mint)]
+    pub destination_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Owner or delegate signing the transfer
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: Extra account list of the mint, checked against the accounts below
+    #[account(
+        seeds = [EXTRA_ACCOUNT_METAS_SEED.as_bytes(), mint.key().as_ref()],
+        bump,
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar
+    #[account(address = solana_instructions_sysvar::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub pump_program: Program<'info, Pump>,
+
+    /// CHECK: Pool of the mint, checked by pump
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: Position of the source owner, checked by pump; may not exist
+    #[account(mut)]
+    pub source_position: UncheckedAccount<'info>,
+
+    /// CHECK: Position of the destination owner, checked by pump; may not exist
+    #[account(mut)]
+    pub destination_position: UncheckedAccount<'info>,
+
+    /// CHECK: Signer PDA of this program for `record_hook_transfer`
+    #[account(seeds = [HOOK_AUTHORITY_SEED.as_bytes()], bump)]
+    pub hook_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Statistics of the pool, checked by pump; may not exist
+    #[account(mut)]
+    pub pool_stats: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum HookError {
+    #[msg("The hook can only run during a token transfer")]
+    NotTransferring,
+
+    #[msg("Only pump's global vault can set up the hook of its mints")]
+    Unauthorized,
+}
@@ -1 +1,28 @@
-pub const INITIAL_PRICE: u64 = 600;       // lamports per one token (without decimal)
\ No newline at end of file
+use anchor_lang::prelude::*;
+
+pub const INITIAL_PRICE: u64 = 600;       // lamports per one token (without decimal)
+pub const MAX_COUNCIL_MEMBERS: usize = 10; // maximum members of the admin council
+pub const GLOBAL_VAULT_FUNDING: u64 = 10_000_000; // lamports sent to the global vault on initialize
+pub const MAX_TAX_BRACKETS: usize = 8;     // maximum holding-time brackets in the paperhand tax schedule
+pub const MAX_LOSS_BRACKETS: usize = 8;    // maximum loss brackets for the progressive paperhand tax
+pub const MAX_POSITION_LOTS: usize = 16;   // size of the FIFO lot ring of a position
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000; // fixed-point scale of the diamond-hand reward-per-token
+pub const MAX_RANKED_POOLS: usize = 10;   // size of the king-of-the-hill ranking in GlobalStats
+pub const MAX_SYMBOL_LEN: usize = 10;     // longest token symbol accepted by launch
+pub const PRICE_PRECISION: u128 = 1_000_000_000_000; // fixed-point scale of prices in lamports per token unit
+
+// Pause switches, combined as a bitflag in `CurveConfiguration::pause_flags`
+// and `LiquidityPool::pause_flags`
+pub const PAUSE_LAUNCH: u8 = 1 << 0;
+pub const PAUSE_BUY: u8 = 1 << 1;
+pub const PAUSE_SELL: u8 = 1 << 2;
+pub const PAUSE_ADD_LIQUIDITY: u8 = 1 << 3;
+pub const PAUSE_REMOVE_LIQUIDITY: u8 = 1 << 4;
+pub const PAUSE_ALL: u8 =
+    PAUSE_LAUNCH | PAUSE_BUY | PAUSE_SELL | PAUSE_ADD_LIQUIDITY | PAUSE_REMOVE_LIQUIDITY;
+
+// Transfer hook of Token-2022 launches (programs/pump-hook); it calls
+// `record_hook_transfer` signed by its `HOOK_AUTHORITY_SEED` PDA
+pub const TRANSFER_HOOK_PROGRAM_ID: Pubkey = pubkey!("8jL4kHLoeevdgiBs2qQMgyQ9mm5PUua1Qkkei4rm5xue");
+pub const HOOK_AUTHORITY_SEED: &str = "hook_authority";
+pub const TRANSFER_HOOK_EXTRA_ACCOUNTS: usize = 7; // accounts the hook lists after the validation account
@@ -47,4 +47,138 @@ pub enum CustomError {
 
     #[msg("Invalid paperhand tax basis points (must be <= 10000)")]
     InvalidTaxBps,
+
+    // Admin council errors
+    #[msg("Signer is not authorized to perform this action")]
+    Unauthorized,
+
+    #[msg("An admin council is already configured")]
+    CouncilAlreadyConfigured,
+
+    #[msg("An admin council is configured: this action requires an approved proposal")]
+    CouncilApprovalRequired,
+
+    #[msg("Invalid council members or threshold")]
+    InvalidCouncil,
+
+    #[msg("Signer is not a member of the admin council")]
+    NotCouncilMember,
+
+    #[msg("Council member has already approved this proposal")]
+    ProposalAlreadyApproved,
+
+    #[msg("Proposal has already been executed")]
+    ProposalAlreadyExecuted,
+
+    #[msg("Proposal has not reached the approval threshold")]
+    ThresholdNotReached,
+
+    #[msg("Proposal predates the current council membership")]
+    StaleProposal,
+
+    #[msg("Withdrawal destination does not match the proposal")]
+    InvalidDestination,
+
+    #[msg("Program is paused")]
+    ProgramPaused,
+
+    #[msg("This pool is paused")]
+    PoolPaused,
+
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
+
+    #[msg("Pool account does not match the proposal")]
+    InvalidPool,
+
+    #[msg("Tax brackets must be sorted by holding time and fit the schedule")]
+    InvalidTaxSchedule,
+
+    #[msg("Tax split must add up to 10000 bps")]
+    InvalidTaxSplit,
+
+    #[msg("Creator account does not match the pool")]
+    InvalidCreator,
+
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+
+    #[msg("FIFO pools require the position lots account")]
+    PositionLotsRequired,
+
+    #[msg("Recipient must be a different wallet")]
+    InvalidRecipient,
+
+    #[msg("A Token-2022 launch requires the transfer hook program and its extra account list")]
+    TransferHookAccountsRequired,
+
+    #[msg("Tokens with a tracked cost basis can only be sent to a wallet with a position")]
+    RecipientPositionRequired,
+
+    #[msg("Position still holds tokens or unclaimed rewards")]
+    PositionNotEmpty,
+
+    #[msg("Withdrawal exceeds the treasury's per-epoch limit")]
+    TreasuryLimitExceeded,
+
+    #[msg("Buyback share can't exceed 10000 bps")]
+    InvalidBuybackConfig,
+
+    #[msg("Buybacks are disabled")]
+    BuybacksDisabled,
+
+    #[msg("This pool was bought back too recently")]
+    BuybackCooldownActive,
+
+    #[msg("Referral fee can't exceed 10000 bps")]
+    InvalidReferralFee,
+
+    #[msg("Referrer does not match the position's referral")]
+    InvalidReferrer,
+
+    #[msg("Symbols must be 1 to 10 characters from A-Z and 0-9")]
+    InvalidSymbol,
+
+    #[msg("This symbol is blocked")]
+    SymbolBlocked,
+
+    #[msg("This symbol is reserved for another wallet")]
+    SymbolReserved,
+
+    #[msg("This symbol is being auctioned")]
+    SymbolInAuction,
+
+    #[msg("Premium symbols must be won at auction")]
+    SymbolRequiresAuction,
+
+    #[msg("This symbol can't be auctioned")]
+    SymbolNotAuctionable,
+
+    #[msg("Bid is below the minimum")]
+    BidTooLow,
+
+    #[msg("The auction has ended")]
+    AuctionEnded,
+
+    #[msg("The auction is still running or has no bids")]
+    AuctionNotEnded,
+
+    #[msg("Previous bidder account does not match the auction")]
+    InvalidBidder,
+
+    #[msg("Symbol registry account is missing")]
+    InvalidSymbolRegistry,
+
+    #[msg("An initial buy requires the creator's position, pool stats and token accounts")]
+    InitialBuyAccountsRequired,
+
+    #[msg("A creator allocation requires the vesting escrow and its token account")]
+    VestingAccountsRequired,
+
+    // Migration errors
+    #[msg("Account data does not match the expected layout")]
+    InvalidAccountData,
+
+    #[msg("Account is already at the current layout version")]
+    AccountAlreadyMigrated,
 }
@@ -5,10 +5,16 @@ use anchor_spl::{
 };
 
 use crate::{
-    state::{LiquidityPool, LiquidityPoolAccount, LiquidityProvider},
+    consts::PAUSE_ADD_LIQUIDITY,
+    errors::CustomError,
+    state::{CurveConfiguration, LiquidityPool, LiquidityPoolAccount, LiquidityProvider},
 };
 
 pub fn add_liquidity(ctx: Context<AddLiquidity>, amount_one: u64, amount_two: u64) -> Result<()> {
+    if ctx.accounts.dex_configuration_account.is_paused(PAUSE_ADD_LIQUIDITY) {
+        return err!(CustomError::ProgramPaused);
+    }
+
     let pool = &mut ctx.accounts.pool;
 
     let token_one_accounts = (
@@ -43,6 +49,12 @@ pub fn add_liquidity(ctx: Context<AddLiquidity>, amount_one: u64, amount_two: u6
 
 #[derive(Accounts)]
 pub struct AddLiquidity<'info> {
+    #[account(
+        seeds = [CurveConfiguration::SEED.as_bytes()],
+        bump,
+    )]
+    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+
     #[account(
         init,
         space = LiquidityPool::ACCOUNT_SIZE,
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::CustomError,
+    state::{AdminCouncil, AdminProposal},
+};
+
+/// Event emitted when a council member approves a proposal
+#[event]
+pub struct AdminActionApproved {
+    pub proposal: Pubkey,
+    pub member: Pubkey,
+    pub approvals: u32,
+    pub threshold: u8,
+}
+
+/// Record a council member's approval on a pending proposal
+pub fn approve_admin_action(ctx: Context<ApproveAdminAction>) -> Result<()> {
+    let council = &ctx.accounts.admin_council;
+    let proposal = &mut ctx.accounts.proposal;
+
+    let member_index = council
+        .member_index(&ctx.accounts.member.key())
+        .ok_or(CustomError::NotCouncilMember)?;
+
+    proposal.approve(member_index)?;
+
+    emit!(AdminActionApproved {
+        proposal: proposal.key(),
+        member: ctx.accounts.member.key(),
+        approvals: proposal.approval_count(),
+        threshold: council.threshold,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ApproveAdminAction<'info> {
+    #[account(
+        seeds = [AdminCouncil::SEED.as_bytes()],
+        bump = admin_council.bump,
+    )]
+    pub admin_council: Box<Account<'info, AdminCouncil>>,
+
+    #[account(
+        mut,
+        seeds = [
+            AdminProposal::SEED_PREFIX.as_bytes(),
+            admin_council.key().as_ref(),
+            proposal.id.to_le_bytes().as_ref(),
+        ],
+        bump = proposal.bump,
+        constraint = !proposal.executed @ CustomError::ProposalAlreadyExecuted,
+        constraint = proposal.id >= admin_council.first_valid_proposal @ CustomError::StaleProposal,
+    )]
+    pub proposal: Box<Account<'info, AdminProposal>>,
+
+    pub member: Signer<'info>,
+}
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::{
+    errors::CustomError,
+    state::{SymbolEntry, SymbolRegistry},
+};
+
+/// Event emitted when a bid leads a symbol auction
+#[event]
+pub struct SymbolBidPlaced {
+    pub symbol: String,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub auction_end_time: i64,
+    pub timestamp: i64,
+}
+
+/// Bid `amount` lamports for a premium symbol in an English auction
+///
+/// The first bid opens the auction for the registry's duration. Bids are
+/// escrowed on the symbol's entry, and an outbid bidder (`previous_bidder`)
+/// is refunded in the same instruction. Symbols are given in upper case.
+pub fn bid_symbol(ctx: Context<BidSymbol>, symbol: String, amount: u64) -> Result<()> {
+    SymbolEntry::validate(&symbol)?;
+
+    let registry = &ctx.accounts.symbol_registry;
+    if !registry.is_premium(&symbol) {
+        return err!(CustomError::SymbolNotAuctionable);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let entry = &mut ctx.accounts.symbol_entry;
+    entry.init_if_new(&symbol, ctx.bumps.symbol_entry);
+    if entry.blocked {
+        return err!(CustomError::SymbolBlocked);
+    }
+    if entry.is_reserved(now) {
+        return err!(CustomError::SymbolReserved);
+    }
+
+    // A settled auction whose reservation has lapsed can be auctioned again
+    if entry.auction_settled {
+        entry.auction_end_time = 0;
+        entry.highest_bid = 0;
+        entry.highest_bidder = Pubkey::default();
+        entry.auction_settled = false;
+    }
+    if !entry.has_auction() {
+        entry.auction_end_time = now
+            .checked_add(registry.auction_duration_seconds as i64)
+            .ok_or(CustomError::MathOverflow)?;
+    } else if now >= entry.auction_end_time {
+        return err!(CustomError::AuctionEnded);
+    }
+
+    if amount < registry.next_min_bid(entry)? {
+        return err!(CustomError::BidTooLow);
+    }
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.bidder.to_account_info(),
+                to: entry.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    // Refund the bid being beaten out of the escrow
+    let refund = entry.highest_bid;
+    if refund > 0 {
+        let previous_bidder = ctx
+            .accounts
+            .previous_bidder
+            .as_ref()
+            .ok_or(CustomError::InvalidBidder)?;
+        if previous_bidder.key() != entry.highest_bidder {
+            return err!(CustomError::InvalidBidder);
+        }
+        **entry.to_account_info().try_borrow_mut_lamports()? -= refund;
+        **previous_bidder.try_borrow_mut_lamports()? = previous_bidder
+            .lamports()
+            .checked_add(refund)
+            .ok_or(CustomError::MathOverflow)?;
+    }
+
+    entry.highest_bid = amount;
+    entry.highest_bidder = ctx.accounts.bidder.key();
+
+    emit!(SymbolBidPlaced {
+        symbol,
+        bidder: entry.highest_bidder,
+        amount,
+        auction_end_time: entry.auction_end_time,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct BidSymbol<'info> {
+    #[account(
+        seeds = [SymbolRegistry::SEED.as_bytes()],
+        bump = symbol_registry.bump,
+    )]
+    pub symbol_registry: Box<Account<'info, SymbolRegistry>>,
+
+    /// Entry of the symbol (init_if_needed on the first bid), escrowing the highest bid
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        space = SymbolEntry::ACCOUNT_SIZE,
+        seeds = [SymbolEntry::SEED_PREFIX.as_bytes(), symbol.as_bytes()],
+        bump,
+    )]
+    pub symbol_entry: Box<Account<'info, SymbolEntry>>,
+
+    /// CHECK: Current highest bidder, refunded when outbid
+    #[account(mut)]
+    pub previous_bidder: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    consts::PAUSE_BUY,
+    errors::CustomError,
+    state::{CurveConfiguration, LiquidityPool, TreasuryVault},
+};
+
+/// Event emitted when treasury SOL buys back and burns a pool's token
+#[event]
+pub struct BuybackExecuted {
+    pub pool: Pubkey,
+    pub sol_spent: u64,
+    pub tokens_burned: u64,
+    /// Lamports still earmarked for the pool's buybacks
+    pub buyback_balance: u64,
+    pub total_supply: u64,
+    pub cranker: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Spend up to `max_sol` lamports earmarked for the pool to buy its token
+/// along the curve, then burn the purchased tokens
+///
+/// Anyone may crank; the spend per call and the time between calls are
+/// capped by the configuration. No trading fee is charged.
+pub fn buyback_and_burn(ctx: Context<BuybackAndBurn>, max_sol: u64) -> Result<()> {
+    let config = &ctx.accounts.dex_configuration_account;
+    let pool = &mut ctx.accounts.pool;
+    let now = Clock::get()?.unix_timestamp;
+
+    if config.buyback_max_sol_per_call == 0 {
+        return err!(CustomError::BuybacksDisabled);
+    }
+    if config.is_paused(PAUSE_BUY) {
+        return err!(CustomError::ProgramPaused);
+    }
+    if pool.is_paused(PAUSE_BUY) {
+        return err!(CustomError::PoolPaused);
+    }
+    if pool.last_buyback_timestamp != 0
+        && now < pool.last_buyback_timestamp + config.buyback_cooldown_seconds as i64
+    {
+        return err!(CustomError::BuybackCooldownActive);
+    }
+
+    let sol_spent = max_sol
+        .min(config.buyback_max_sol_per_call)
+        .min(pool.buyback_balance);
+    if sol_spent == 0 {
+        return err!(CustomError::InvalidAmount);
+    }
+
+    let tokens_burned = pool.buy_output(sol_spent, ctx.accounts.mint_token_one.decimals)?;
+    if tokens_burned == 0 {
+        return err!(CustomError::InvalidAmount);
+    }
+
+    // The SOL joins the curve's reserve in the global vault
+    let vault_info = ctx.accounts.treasury_vault.to_account_info();
+    ctx.accounts.treasury_vault.spend_earmarked(
+        &vault_info,
+        &ctx.accounts.global_account.to_account_info(),
+        pool,
+        sol_spent,
+    )?;
+
+    pool.reserve_one = pool.reserve_one
+        .checked_sub(tokens_burned)
+        .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
+    pool.reserve_two = pool.reserve_two
+        .checked_add(sol_spent)
+        .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
+    pool.total_supply = pool.total_supply
+        .checked_sub(tokens_burned)
+        .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
+    pool.last_buyback_timestamp = now;
+
+    token_interface::burn(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::Burn {
+                mint: ctx.accounts.mint_token_one.to_account_info(),
+                from: ctx.accounts.pool_token_account_one.to_account_info(),
+                authority: ctx.accounts.global_account.to_account_info(),
+            },
+            &[&[b"global", &[ctx.bumps.global_account]]],
+        ),
+        tokens_burned,
+    )?;
+
+    emit!(BuybackExecuted {
+        pool: pool.key(),
+        sol_spent,
+        tokens_burned,
+        buyback_balance: pool.buyback_balance,
+        total_supply: pool.total_supply,
+        cranker: ctx.accounts.cranker.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct BuybackAndBurn<'info> {
+    #[account(
+        seeds = [CurveConfiguration::SEED.as_bytes()],
+        bump,
+    )]
+    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+
+    #[account(
+        mut,
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), mint_token_one.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    /// CHECK: Global SOL vault PDA
+    #[account(
+        mut,
+        seeds = [b"global"],
+        bump,
+    )]
+    pub global_account: AccountInfo<'info>,
+
+    /// Program-owned vault holding the earmarked SOL
+    #[account(
+        mut,
+        seeds = [TreasuryVault::SEED.as_bytes()],
+        bump = treasury_vault.bump,
+        constraint = treasury_vault.key() == dex_configuration_account.treasury @ CustomError::InvalidTreasury,
+    )]
+    pub treasury_vault: Box<Account<'info, TreasuryVault>>,
+
+    #[account(mut)]
+    pub mint_token_one: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_token_one,
+        associated_token::authority = global_account,
+        associated_token::token_program = token_program,
+    )]
+    pub pool_token_account_one: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub cranker: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
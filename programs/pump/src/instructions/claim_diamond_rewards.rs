@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::{
+    errors::CustomError,
+    state::{LiquidityPool, UserPosition},
+};
+
+/// Event emitted when a holder claims diamond-hand rewards
+#[event]
+pub struct DiamondRewardsClaimed {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Pay out the rewards a position earned from other holders' paperhand taxes
+pub fn claim_diamond_rewards(ctx: Context<ClaimDiamondRewards>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let position = &mut ctx.accounts.user_position;
+
+    position.settle_rewards(pool)?;
+
+    let amount = position.pending_rewards;
+    if amount == 0 {
+        return err!(CustomError::NothingToClaim);
+    }
+
+    position.pending_rewards = 0;
+    pool.reward_balance = pool.reward_balance
+        .checked_sub(amount)
+        .ok_or(CustomError::InsufficientFunds)?;
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.global_account.to_account_info(),
+                to: ctx.accounts.user.to_account_info(),
+            },
+            &[&[b"global", &[ctx.bumps.global_account]]],
+        ),
+        amount,
+    )?;
+
+    emit!(DiamondRewardsClaimed {
+        user: ctx.accounts.user.key(),
+        pool: pool.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimDiamondRewards<'info> {
+    #[account(
+        mut,
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), pool.token_one.as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(
+        mut,
+        seeds = [UserPosition::SEED_PREFIX.as_bytes(), pool.key().as_ref(), user.key().as_ref()],
+        bump = user_position.bump,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    /// CHECK: Global SOL vault PDA
+    #[account(
+        mut,
+        seeds = [b"global"],
+        bump,
+    )]
+    pub global_account: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
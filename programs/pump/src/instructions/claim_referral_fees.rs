@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::{errors::CustomError, state::Referrer};
+
+/// Event emitted when a referrer claims its fees
+#[event]
+pub struct ReferralFeesClaimed {
+    pub referrer: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Pay out the protocol fees a referrer earned from its referred traders
+pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+    let referrer = &mut ctx.accounts.referrer;
+
+    let amount = referrer.accrued_fees;
+    if amount == 0 {
+        return err!(CustomError::NothingToClaim);
+    }
+    referrer.accrued_fees = 0;
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.global_account.to_account_info(),
+                to: ctx.accounts.owner.to_account_info(),
+            },
+            &[&[b"global", &[ctx.bumps.global_account]]],
+        ),
+        amount,
+    )?;
+
+    emit!(ReferralFeesClaimed {
+        referrer: referrer.key(),
+        owner: ctx.accounts.owner.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralFees<'info> {
+    #[account(
+        mut,
+        seeds = [Referrer::SEED_PREFIX.as_bytes(), owner.key().as_ref()],
+        bump = referrer.bump,
+        has_one = owner @ CustomError::Unauthorized,
+    )]
+    pub referrer: Box<Account<'info, Referrer>>,
+
+    /// CHECK: Global SOL vault PDA
+    #[account(
+        mut,
+        seeds = [b"global"],
+        bump,
+    )]
+    pub global_account: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
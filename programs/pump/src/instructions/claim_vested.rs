@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    errors::CustomError,
+    instructions::PositionUpdated,
+    state::{CostBasisMode, LiquidityPool, PoolStats, PositionLots, UserPosition, VestingEscrow},
+    utils::transfer_tokens,
+};
+
+/// Event emitted when the creator claims unlocked tokens of its allocation
+#[event]
+pub struct VestedTokensClaimed {
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    /// Tokens claimed so far, including this claim
+    pub claimed_amount: u64,
+    pub total_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Transfer the unlocked, unclaimed part of the creator allocation to the creator
+///
+/// The claimed tokens are credited to the creator's position with a zero cost
+/// basis, since the allocation was free, so they can be sold whatever the
+/// untracked token policy.
+pub fn claim_vested<'info>(ctx: Context<'_, '_, '_, 'info, ClaimVested<'info>>) -> Result<()> {
+    let escrow = &mut ctx.accounts.vesting_escrow;
+    let pool = &mut ctx.accounts.pool;
+    let position = &mut ctx.accounts.user_position;
+    let now = Clock::get()?.unix_timestamp;
+
+    let amount = escrow.claimable_amount(now);
+    if amount == 0 {
+        return err!(CustomError::NothingToClaim);
+    }
+    escrow.claimed_amount = escrow.claimed_amount
+        .checked_add(amount)
+        .ok_or(CustomError::MathOverflow)?;
+
+    let mint = ctx.accounts.mint.key();
+    transfer_tokens(
+        &ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.escrow_token_account.to_account_info(),
+        ctx.accounts.mint.to_account_info(),
+        ctx.accounts.beneficiary_token_account.to_account_info(),
+        escrow.to_account_info(),
+        ctx.remaining_accounts,
+        amount,
+        ctx.accounts.mint.decimals,
+        &[&[VestingEscrow::SEED_PREFIX.as_bytes(), mint.as_ref(), &[escrow.bump]]],
+    )?;
+
+    // Freshly created position: record its pool, owner and layout version
+    if position.version == 0 {
+        position.set_inner(UserPosition::new(
+            pool.key(),
+            ctx.accounts.beneficiary.key(),
+            ctx.bumps.user_position,
+        ));
+    }
+
+    let stats = &mut ctx.accounts.pool_stats;
+    if stats.pool == Pubkey::default() {
+        stats.pool = pool.key();
+        stats.bump = ctx.bumps.pool_stats;
+    }
+    if position.total_tokens == 0 {
+        stats.record_new_holder();
+    }
+
+    // FIFO pools keep the claim as a free lot
+    if pool.cost_basis_mode == CostBasisMode::Fifo {
+        let lots: &mut PositionLots = ctx
+            .accounts
+            .position_lots
+            .as_deref_mut()
+            .ok_or(CustomError::PositionLotsRequired)?;
+        if lots.position == Pubkey::default() {
+            lots.position = position.key();
+            lots.bump = ctx.bumps.position_lots.ok_or(CustomError::PositionLotsRequired)?;
+        }
+        lots.push(amount, 0, now)?;
+    }
+    position.add_tokens(pool, amount, 0, now)?;
+    emit!(PositionUpdated::from_position(position));
+
+    emit!(VestedTokensClaimed {
+        beneficiary: ctx.accounts.beneficiary.key(),
+        mint,
+        amount,
+        claimed_amount: escrow.claimed_amount,
+        total_amount: escrow.total_amount,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(
+        mut,
+        seeds = [VestingEscrow::SEED_PREFIX.as_bytes(), mint.key().as_ref()],
+        bump = vesting_escrow.bump,
+        has_one = beneficiary @ CustomError::Unauthorized,
+        has_one = mint,
+        has_one = pool,
+    )]
+    pub vesting_escrow: Box<Account<'info, VestingEscrow>>,
+
+    #[account(
+        mut,
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), mint.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The creator's position, credited with the claimed tokens
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        space = UserPosition::ACCOUNT_SIZE,
+        seeds = [UserPosition::SEED_PREFIX.as_bytes(), pool.key().as_ref(), beneficiary.key().as_ref()],
+        bump,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    /// Cost-basis lots of the position, required for pools in FIFO mode
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        space = PositionLots::ACCOUNT_SIZE,
+        seeds = [PositionLots::SEED_PREFIX.as_bytes(), user_position.key().as_ref()],
+        bump,
+    )]
+    pub position_lots: Option<Box<Account<'info, PositionLots>>>,
+
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        space = PoolStats::ACCOUNT_SIZE,
+        seeds = [PoolStats::SEED_PREFIX.as_bytes(), pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_stats: Box<Account<'info, PoolStats>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program,
+    )]
+    pub beneficiary_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
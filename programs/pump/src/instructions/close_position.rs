@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::CustomError,
+    state::{CostBasisMode, LiquidityPool, PositionLots, UserPosition},
+};
+
+/// Event emitted when an empty position is closed and its rent refunded
+#[event]
+pub struct PositionClosed {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Close an empty position (and its lots in FIFO pools), returning the rent
+/// to its owner
+///
+/// The position is recreated by the next buy. A referrer binding lives in the
+/// wallet's separate `Referral` account, so it survives the close.
+pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+    // Leaving the lots behind would strand their rent
+    if ctx.accounts.pool.cost_basis_mode == CostBasisMode::Fifo {
+        require!(ctx.accounts.position_lots.is_some(), CustomError::PositionLotsRequired);
+    }
+
+    emit!(PositionClosed {
+        user: ctx.accounts.user.key(),
+        pool: ctx.accounts.pool.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClosePosition<'info> {
+    #[account(
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), pool.token_one.as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [UserPosition::SEED_PREFIX.as_bytes(), pool.key().as_ref(), user.key().as_ref()],
+        bump = user_position.bump,
+        constraint = user_position.is_closable() @ CustomError::PositionNotEmpty,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    /// Lots of the position, required in FIFO pools and closed along with it
+    #[account(
+        mut,
+        close = user,
+        seeds = [PositionLots::SEED_PREFIX.as_bytes(), user_position.key().as_ref()],
+        bump = position_lots.bump,
+    )]
+    pub position_lots: Option<Box<Account<'info, PositionLots>>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+use crate::{
+    errors::CustomError,
+    state::{AdminCouncil, CurveConfiguration},
+};
+
+/// Event emitted when the admin council is installed
+#[event]
+pub struct AdminCouncilCreated {
+    pub council: Pubkey,
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+/// Install the M-of-N admin council
+///
+/// Only the program upgrade authority can do this, and only once. From then on
+/// every admin action has to go through an approved AdminProposal.
+pub fn create_admin_council(
+    ctx: Context<CreateAdminCouncil>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    let council = &mut ctx.accounts.admin_council;
+    council.set_inner(AdminCouncil::new(&members, threshold, ctx.bumps.admin_council)?);
+
+    ctx.accounts.dex_configuration_account.admin_council = council.key();
+
+    emit!(AdminCouncilCreated {
+        council: council.key(),
+        members,
+        threshold,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateAdminCouncil<'info> {
+    #[account(
+        mut,
+        seeds = [CurveConfiguration::SEED.as_bytes()],
+        bump,
+        constraint = !dex_configuration_account.has_council() @ CustomError::CouncilAlreadyConfigured,
+    )]
+    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = AdminCouncil::ACCOUNT_SIZE,
+        seeds = [AdminCouncil::SEED.as_bytes()],
+        bump,
+    )]
+    pub admin_council: Box<Account<'info, AdminCouncil>>,
+
+    /// Program data account holding the upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ CustomError::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
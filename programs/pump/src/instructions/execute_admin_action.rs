@@ -0,0 +1,249 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::{
+    errors::CustomError,
+    instructions::{FeesWithdrawn, PauseUpdated, TreasuryWithdrawn},
+    state::{
+        AdminAction, AdminCouncil, AdminProposal, CurveConfiguration, LiquidityPool, SymbolRegistry,
+        TreasuryVault,
+    },
+};
+
+/// Event emitted when an approved admin action is applied
+#[event]
+pub struct AdminActionExecuted {
+    pub proposal: Pubkey,
+    pub action: AdminAction,
+    pub executor: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Apply a proposal once it has reached the council threshold
+///
+/// Anyone can execute an approved proposal; a proposal can only be executed once.
+pub fn execute_admin_action(ctx: Context<ExecuteAdminAction>) -> Result<()> {
+    let council = &ctx.accounts.admin_council;
+    let proposal = &mut ctx.accounts.proposal;
+
+    if proposal.approval_count() < council.threshold as u32 {
+        return err!(CustomError::ThresholdNotReached);
+    }
+
+    let config = &mut ctx.accounts.dex_configuration_account;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    match proposal.action {
+        AdminAction::SetFees { fee_bps } => config.set_fee_bps(fee_bps)?,
+        AdminAction::SetTreasury { treasury } => config.set_treasury(treasury)?,
+        AdminAction::SetPauseFlags { pause_flags } => {
+            let previous_flags = config.pause_flags;
+            config.set_pause_flags(pause_flags)?;
+
+            emit!(PauseUpdated {
+                pool: None,
+                previous_flags,
+                pause_flags,
+                authority: council.key(),
+                timestamp,
+            });
+        }
+        AdminAction::SetPoolPauseFlags { pool, pause_flags } => {
+            let pool_account = ctx
+                .accounts
+                .pool
+                .as_mut()
+                .ok_or(CustomError::InvalidPool)?;
+            if pool_account.key() != pool {
+                return err!(CustomError::InvalidPool);
+            }
+
+            let previous_flags = pool_account.pause_flags;
+            pool_account.set_pause_flags(pause_flags)?;
+
+            emit!(PauseUpdated {
+                pool: Some(pool),
+                previous_flags,
+                pause_flags,
+                authority: council.key(),
+                timestamp,
+            });
+        }
+        AdminAction::SetTaxSchedule { ref brackets } => config.set_tax_schedule(brackets)?,
+        AdminAction::SetTaxSplit { treasury_bps, reserve_bps, creator_bps, reward_bps } => {
+            config.set_tax_split(treasury_bps, reserve_bps, creator_bps, reward_bps)?
+        }
+        AdminAction::SetTaxBounds { min_bps, max_bps } => config.set_tax_bounds(min_bps, max_bps)?,
+        AdminAction::SetTaxMode { tax_mode, ref loss_brackets } => {
+            config.set_tax_mode(tax_mode, loss_brackets)?
+        }
+        AdminAction::SetUntrackedTokenPolicy { policy } => config.untracked_token_policy = policy,
+        AdminAction::SetAutoClosePositions { enabled } => config.auto_close_positions = enabled,
+        AdminAction::WithdrawTreasury { amount, destination } => {
+            let destination_account = ctx
+                .accounts
+                .destination
+                .as_ref()
+                .ok_or(CustomError::InvalidDestination)?;
+            if destination_account.key() != destination {
+                return err!(CustomError::InvalidDestination);
+            }
+            let vault = ctx
+                .accounts
+                .treasury_vault
+                .as_mut()
+                .ok_or(CustomError::InvalidTreasury)?;
+            let vault_info = vault.to_account_info();
+            let epoch = Clock::get()?.epoch;
+            vault.withdraw(&vault_info, &destination_account.to_account_info(), amount, epoch)?;
+
+            emit!(TreasuryWithdrawn {
+                amount,
+                destination,
+                epoch,
+                withdrawn_this_epoch: vault.withdrawn_this_epoch,
+                authority: council.key(),
+                timestamp,
+            });
+        }
+        AdminAction::SetTreasuryWithdrawalLimit { limit } => {
+            let vault = ctx
+                .accounts
+                .treasury_vault
+                .as_mut()
+                .ok_or(CustomError::InvalidTreasury)?;
+            vault.epoch_withdrawal_limit = limit;
+        }
+        AdminAction::SetBuybackConfig {
+            buyback_bps,
+            max_sol_per_call,
+            cooldown_seconds,
+        } => config.set_buyback_config(buyback_bps, max_sol_per_call, cooldown_seconds)?,
+        AdminAction::SetReferralFeeBps { referral_fee_bps } => {
+            config.set_referral_fee_bps(referral_fee_bps)?
+        }
+        AdminAction::SetSymbolAuthority { authority } => {
+            let registry = ctx
+                .accounts
+                .symbol_registry
+                .as_mut()
+                .ok_or(CustomError::InvalidSymbolRegistry)?;
+            registry.authority = authority;
+        }
+        AdminAction::WithdrawFees { amount, destination } => {
+            let destination_account = ctx
+                .accounts
+                .destination
+                .as_ref()
+                .ok_or(CustomError::InvalidDestination)?;
+            if destination_account.key() != destination {
+                return err!(CustomError::InvalidDestination);
+            }
+            config.take_accrued_fees(amount)?;
+
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.global_account.to_account_info(),
+                        to: destination_account.to_account_info(),
+                    },
+                    &[&[b"global", &[ctx.bumps.global_account]]],
+                ),
+                amount,
+            )?;
+
+            emit!(FeesWithdrawn {
+                amount,
+                destination,
+                remaining_fees: config.accrued_fees,
+                authority: council.key(),
+                timestamp,
+            });
+        }
+        AdminAction::SetCouncil { ref members, threshold } => {
+            ctx.accounts.admin_council.set_members(members, threshold)?
+        }
+    }
+
+    proposal.executed = true;
+
+    emit!(AdminActionExecuted {
+        proposal: proposal.key(),
+        action: proposal.action.clone(),
+        executor: ctx.accounts.executor.key(),
+        timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExecuteAdminAction<'info> {
+    #[account(
+        mut,
+        seeds = [CurveConfiguration::SEED.as_bytes()],
+        bump,
+        constraint = dex_configuration_account.admin_council == admin_council.key() @ CustomError::InvalidCouncil,
+    )]
+    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+
+    #[account(
+        mut,
+        seeds = [AdminCouncil::SEED.as_bytes()],
+        bump = admin_council.bump,
+    )]
+    pub admin_council: Box<Account<'info, AdminCouncil>>,
+
+    #[account(
+        mut,
+        seeds = [
+            AdminProposal::SEED_PREFIX.as_bytes(),
+            admin_council.key().as_ref(),
+            proposal.id.to_le_bytes().as_ref(),
+        ],
+        bump = proposal.bump,
+        constraint = !proposal.executed @ CustomError::ProposalAlreadyExecuted,
+        constraint = proposal.id >= admin_council.first_valid_proposal @ CustomError::StaleProposal,
+    )]
+    pub proposal: Box<Account<'info, AdminProposal>>,
+
+    /// CHECK: Global SOL vault PDA
+    #[account(
+        mut,
+        seeds = [b"global"],
+        bump,
+    )]
+    pub global_account: AccountInfo<'info>,
+
+    /// Pool targeted by a per-pool pause action
+    #[account(
+        mut,
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), pool.token_one.as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Option<Box<Account<'info, LiquidityPool>>>,
+
+    /// Treasury vault targeted by treasury actions
+    #[account(
+        mut,
+        seeds = [TreasuryVault::SEED.as_bytes()],
+        bump = treasury_vault.bump,
+    )]
+    pub treasury_vault: Option<Box<Account<'info, TreasuryVault>>>,
+
+    /// CHECK: Withdrawal recipient, checked against the proposal's action
+    #[account(mut)]
+    pub destination: Option<UncheckedAccount<'info>>,
+
+    /// Symbol registry targeted by moderator appointments
+    #[account(
+        mut,
+        seeds = [SymbolRegistry::SEED.as_bytes()],
+        bump = symbol_registry.bump,
+    )]
+    pub symbol_registry: Option<Box<Account<'info, SymbolRegistry>>>,
+
+    pub executor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,14 +1,27 @@
-use crate::{errors::CustomError, state::*};
+use crate::{consts::GLOBAL_VAULT_FUNDING, errors::CustomError, state::*};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+/// Event emitted when the bonding curve configuration is created
+#[event]
+pub struct ConfigInitialized {
+    pub admin: Pubkey,
+    pub fee_bps: u16,
+    pub treasury: Pubkey,
+    pub paperhand_tax_bps: u16,
+    pub global_vault_funding: u64,
+    pub timestamp: i64,
+}
 
 pub fn initialize(
     ctx: Context<InitializeCurveConfiguration>,
-    fees: f64,
+    fee_bps: u16,
     paperhand_tax_bps: u16,
 ) -> Result<()> {
     let dex_config = &mut ctx.accounts.dex_configuration_account;
 
-    if fees < 0_f64 || fees > 100_f64 {
+    // Validate fee bps (max 100% = 10000 bps)
+    if fee_bps > 10000 {
         return err!(CustomError::InvalidFee);
     }
 
@@ -17,21 +30,30 @@ pub fn initialize(
         return err!(CustomError::InvalidTaxBps);
     }
 
-    let _ = transfer_sol_to_pool(
+    transfer_sol_to_pool(
         ctx.accounts.admin.to_account_info(),
         ctx.accounts.global_account.to_account_info(),
-        10000000,
+        GLOBAL_VAULT_FUNDING,
         ctx.accounts.system_program.to_account_info()
-    );
-
+    )?;
 
+    ctx.accounts.treasury_vault.bump = ctx.bumps.treasury_vault;
 
     dex_config.set_inner(CurveConfiguration::new(
-        fees,
+        fee_bps,
         ctx.accounts.treasury_vault.key(),
         paperhand_tax_bps,
     ));
 
+    emit!(ConfigInitialized {
+        admin: ctx.accounts.admin.key(),
+        fee_bps,
+        treasury: ctx.accounts.treasury_vault.key(),
+        paperhand_tax_bps,
+        global_vault_funding: GLOBAL_VAULT_FUNDING,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
 
@@ -54,9 +76,24 @@ pub struct InitializeCurveConfiguration<'info> {
     )]
     pub global_account: AccountInfo<'info>,
 
-    /// CHECK: Treasury vault that will receive paperhand taxes
-    #[account(mut)]
-    pub treasury_vault: AccountInfo<'info>,
+    /// Program-owned vault that will receive paperhand taxes
+    #[account(
+        init,
+        space = TreasuryVault::ACCOUNT_SIZE,
+        payer = admin,
+        seeds = [TreasuryVault::SEED.as_bytes()],
+        bump,
+    )]
+    pub treasury_vault: Box<Account<'info, TreasuryVault>>,
+
+    /// Program data account holding the upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ CustomError::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
 
     #[account(mut)]
     pub admin: Signer<'info>,
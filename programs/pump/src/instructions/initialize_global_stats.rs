@@ -0,0 +1,27 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+/// Create the protocol-wide statistics account
+///
+/// Anyone can pay for it once; `launch` and `swap` require it afterwards.
+pub fn initialize_global_stats(ctx: Context<InitializeGlobalStats>) -> Result<()> {
+    let mut stats = ctx.accounts.global_stats.load_init()?;
+    stats.bump = ctx.bumps.global_stats;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeGlobalStats<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = GlobalStats::ACCOUNT_SIZE,
+        seeds = [GlobalStats::SEED.as_bytes()],
+        bump,
+    )]
+    pub global_stats: AccountLoader<'info, GlobalStats>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,58 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+/// Create the symbol registry consulted by `launch`
+///
+/// Anyone can pay for it once. The upgrade authority moderates it until an
+/// admin council is installed; with a council the registry starts without a
+/// moderator until the council appoints one through `propose_admin_action`.
+pub fn initialize_symbol_registry(ctx: Context<InitializeSymbolRegistry>) -> Result<()> {
+    let authority = if ctx.accounts.dex_configuration_account.has_council() {
+        Pubkey::default()
+    } else {
+        ctx.accounts.program_data.upgrade_authority_address.unwrap_or_default()
+    };
+
+    ctx.accounts.symbol_registry.set_inner(SymbolRegistry {
+        authority,
+        premium_max_len: 0,
+        auction_duration_seconds: 86_400,
+        min_bid: 100_000_000,
+        min_bid_increment_bps: 500,
+        bump: ctx.bumps.symbol_registry,
+        reserved: [0; 64],
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeSymbolRegistry<'info> {
+    #[account(
+        seeds = [CurveConfiguration::SEED.as_bytes()],
+        bump,
+    )]
+    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = SymbolRegistry::ACCOUNT_SIZE,
+        seeds = [SymbolRegistry::SEED.as_bytes()],
+        bump,
+    )]
+    pub symbol_registry: Box<Account<'info, SymbolRegistry>>,
+
+    /// Program data account holding the upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,38 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+/// Create the treasury vault for a configuration initialized before it
+/// existed, and route the PaperHand tax into it
+///
+/// The vault's address is fixed, so anyone may pay for this once.
+pub fn initialize_treasury_vault(ctx: Context<InitializeTreasuryVault>) -> Result<()> {
+    ctx.accounts.treasury_vault.bump = ctx.bumps.treasury_vault;
+
+    let treasury = ctx.accounts.treasury_vault.key();
+    ctx.accounts.dex_configuration_account.set_treasury(treasury)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeTreasuryVault<'info> {
+    #[account(
+        mut,
+        seeds = [CurveConfiguration::SEED.as_bytes()],
+        bump,
+    )]
+    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = TreasuryVault::ACCOUNT_SIZE,
+        seeds = [TreasuryVault::SEED.as_bytes()],
+        bump,
+    )]
+    pub treasury_vault: Box<Account<'info, TreasuryVault>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
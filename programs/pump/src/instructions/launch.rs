@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
 use anchor_lang::system_program;
 use anchor_spl::{
     metadata::{
@@ -6,10 +7,25 @@ use anchor_spl::{
         mpl_token_metadata::types::DataV2,
         CreateMetadataAccountsV3,
     },
-    token::{self, Mint, MintTo},
+    token_2022::spl_token_2022::extension::ExtensionType,
+    token_interface::{
+        self, find_mint_account_size, InitializeMint2, MintTo, Token2022, TokenInterface,
+        TransferHookInitialize,
+    },
 };
+use spl_tlv_account_resolution::state::ExtraAccountMetaList;
 
-use crate::state::{CurveConfiguration, LiquidityPool};
+use crate::consts::{
+    MAX_SYMBOL_LEN, PAUSE_BUY, PAUSE_LAUNCH, TRANSFER_HOOK_EXTRA_ACCOUNTS, TRANSFER_HOOK_PROGRAM_ID,
+};
+use crate::errors::CustomError;
+use crate::instructions::{PositionUpdated, TradeExecuted};
+use crate::state::{
+    CostBasisMode, CreatorProfile, CurveConfiguration, GlobalStats, LaunchRecord, LiquidityPool,
+    PoolStats, PositionLots, SymbolEntry, SymbolRegistry, UserPosition, VestingEscrow,
+    VestingParams,
+};
+use crate::utils::transfer_tokens;
 
 /// Event emitted when a new token is launched
 #[event]
@@ -21,47 +37,118 @@ pub struct TokenLaunched {
     pub symbol: String,
     pub uri: String,
     pub initial_supply: u64,
+    /// PaperHand tax the pool starts with, in basis points
+    pub paperhand_tax_bps: u16,
+    /// The creator's launch counter; the mint is the PDA
+    /// `["mint", creator, launch_index]` with `mint_bump`
+    pub launch_index: u64,
+    pub mint_bump: u8,
     pub timestamp: i64,
 }
 
+/// Event emitted when a creator allocation is locked at launch
+#[event]
+pub struct VestingCreated {
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub escrow: Pubkey,
+    pub total_amount: u64,
+    pub start_timestamp: i64,
+    pub cliff_timestamp: i64,
+    pub end_timestamp: i64,
+}
+
 /// Launch a new token with Paper Hand Tax enabled
 /// 
 /// This instruction:
-/// 1. Creates a new SPL Token Mint
+/// 1. Creates a new Mint, derived from the creator and its launch counter
 /// 2. Creates Metaplex Metadata (name, symbol, image)
 /// 3. Initializes the Bonding Curve Pool
-/// 4. Mints initial supply to the pool
+/// 4. Mints initial supply to the pool, less the creator allocation which is
+///    minted to its vesting escrow
 /// 5. Revokes mint authority (fixed supply)
-pub fn launch(
-    ctx: Context<Launch>,
+/// 6. Records the launch under the mint
+/// 7. Optionally buys `initial_buy_sol` worth of tokens for the creator, in
+///    the same transaction so nobody can trade before it
+///
+/// The symbol must be made of A-Z and 0-9 (in any case) and pass the symbol
+/// registry: not blocked, not reserved for another wallet, and premium symbols
+/// only for their auction winner.
+///
+/// When `token_program` is Token-2022 the mint gets a transfer hook run by
+/// `TRANSFER_HOOK_PROGRAM_ID`, whose extra account list is set up here, so
+/// plain transfers carry the cost basis between positions. Such pools only
+/// support average cost.
+#[allow(clippy::too_many_arguments)]
+pub fn launch<'info>(
+    mut ctx: Context<'_, '_, '_, 'info, Launch<'info>>,
     name: String,
     symbol: String,
     uri: String,
-    _decimals: u8,
+    decimals: u8,
     initial_supply: u64,
     initial_sol_reserve: u64,
+    paperhand_tax_bps: Option<u16>,
+    cost_basis_mode: CostBasisMode,
+    initial_buy_sol: Option<u64>,
+    creator_vesting: Option<VestingParams>,
 ) -> Result<()> {
     // Validate inputs first (small stack usage)
     require!(name.len() <= 32, LaunchError::NameTooLong);
-    require!(symbol.len() <= 10, LaunchError::SymbolTooLong);
+    require!(symbol.len() <= MAX_SYMBOL_LEN, LaunchError::SymbolTooLong);
     require!(uri.len() <= 200, LaunchError::UriTooLong);
     require!(initial_supply > 0, LaunchError::InvalidSupply);
     require!(initial_sol_reserve > 0, LaunchError::InvalidSolReserve);
+    require!(
+        !ctx.accounts.dex_configuration_account.is_paused(PAUSE_LAUNCH),
+        CustomError::ProgramPaused
+    );
+    if let Some(tax_bps) = paperhand_tax_bps {
+        require!(
+            ctx.accounts.dex_configuration_account.is_tax_within_bounds(tax_bps),
+            LaunchError::TaxOutOfBounds
+        );
+    }
+    if let Some(vesting) = creator_vesting {
+        require!(vesting.is_valid(initial_supply), LaunchError::InvalidVestingSchedule);
+    }
+    let transfer_hook = ctx.accounts.token_program.key() == Token2022::id();
+    require!(
+        !transfer_hook || cost_basis_mode == CostBasisMode::AverageCost,
+        LaunchError::FifoWithTransferHook
+    );
+
+    check_symbol_helper(&ctx, &symbol)?;
 
     msg!("Launching token: {} ({})", name, symbol);
 
     // Call helper functions with #[inline(never)] to use separate stack frames
+    create_mint_helper(&ctx, decimals, transfer_hook)?;
+    if transfer_hook {
+        init_transfer_hook_helper(&ctx)?;
+    }
     create_metadata_helper(&ctx, &name, &symbol, &uri)?;
     initialize_pool_helper(
         &mut ctx.accounts.pool,
         ctx.accounts.mint.key(),
         ctx.bumps.pool,
         initial_supply,
-        initial_sol_reserve
+        initial_sol_reserve,
+        paperhand_tax_bps,
+        cost_basis_mode,
+        ctx.accounts.creator.key(),
     )?;
+    record_launch_helper(&mut ctx, &symbol)?;
     create_pool_token_account_helper(&ctx)?;
-    mint_tokens_helper(&ctx, initial_supply)?;
+    if let Some(vesting) = creator_vesting {
+        create_vesting_helper(&mut ctx, vesting)?;
+    }
+    mint_tokens_helper(&ctx, ctx.accounts.pool.reserve_one)?;
     transfer_sol_helper(&ctx, initial_sol_reserve)?;
+    if let Some(amount) = initial_buy_sol {
+        initial_buy_helper(&mut ctx, amount, decimals)?;
+    }
+    update_global_stats_helper(&ctx)?;
     
     // Initialize LP and emit event
     finalize_launch_helper(&ctx, initial_supply, name, symbol, uri)?;
@@ -70,6 +157,130 @@ pub fn launch(
     Ok(())
 }
 
+/// Create the mint, with the transfer hook extension for Token-2022
+#[inline(never)]
+fn create_mint_helper(ctx: &Context<Launch>, decimals: u8, transfer_hook: bool) -> Result<()> {
+    let space = if transfer_hook {
+        find_mint_account_size(Some(&vec![ExtensionType::TransferHook]))?
+    } else {
+        find_mint_account_size(None)?
+    };
+    let creator = ctx.accounts.creator.key();
+    let launch_index = ctx.accounts.creator_profile.launch_count.to_le_bytes();
+    let global = ctx.accounts.global_account.key();
+
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::CreateAccount {
+                from: ctx.accounts.creator.to_account_info(),
+                to: ctx.accounts.mint.to_account_info(),
+            },
+            &[&[
+                LaunchRecord::MINT_SEED_PREFIX.as_bytes(),
+                creator.as_ref(),
+                &launch_index,
+                &[ctx.bumps.mint],
+            ]],
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        ctx.accounts.token_program.key,
+    )?;
+    if transfer_hook {
+        token_interface::transfer_hook_initialize(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferHookInitialize {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                },
+            ),
+            Some(global),
+            Some(TRANSFER_HOOK_PROGRAM_ID),
+        )?;
+    }
+    token_interface::initialize_mint2(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            InitializeMint2 {
+                mint: ctx.accounts.mint.to_account_info(),
+            },
+        ),
+        decimals,
+        &global,
+        Some(&global),
+    )?;
+    Ok(())
+}
+
+/// Have the transfer hook write its extra account list for the mint; the
+/// creator pays the list's rent up front since the interface has no payer
+#[inline(never)]
+fn init_transfer_hook_helper(ctx: &Context<Launch>) -> Result<()> {
+    let extra_account_meta_list = ctx
+        .accounts
+        .extra_account_meta_list
+        .as_ref()
+        .ok_or(CustomError::TransferHookAccountsRequired)?;
+    let transfer_hook_program = ctx
+        .accounts
+        .transfer_hook_program
+        .as_ref()
+        .ok_or(CustomError::TransferHookAccountsRequired)?;
+
+    let space = ExtraAccountMetaList::size_of(TRANSFER_HOOK_EXTRA_ACCOUNTS)?;
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.creator.to_account_info(),
+                to: extra_account_meta_list.to_account_info(),
+            },
+        ),
+        Rent::get()?.minimum_balance(space),
+    )?;
+
+    // The hook writes its own list, whatever the caller passes
+    let ix = spl_transfer_hook_interface::instruction::initialize_extra_account_meta_list(
+        &TRANSFER_HOOK_PROGRAM_ID,
+        extra_account_meta_list.key,
+        ctx.accounts.mint.key,
+        ctx.accounts.global_account.key,
+        &[],
+    );
+    invoke_signed(
+        &ix,
+        &[
+            extra_account_meta_list.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.global_account.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            transfer_hook_program.to_account_info(),
+        ],
+        &[&[b"global", &[ctx.bumps.global_account]]],
+    )?;
+    Ok(())
+}
+
+#[inline(never)]
+fn check_symbol_helper(ctx: &Context<Launch>, symbol: &str) -> Result<()> {
+    let symbol = SymbolEntry::normalize(symbol);
+    SymbolEntry::validate(&symbol)?;
+    let entry_info = &ctx.accounts.symbol_entry;
+    require_keys_eq!(entry_info.key(), SymbolEntry::address(&symbol), CustomError::InvalidSymbol);
+    let premium = ctx.accounts.symbol_registry.is_premium(&symbol);
+
+    // Symbols that were never moderated or auctioned have no entry
+    if entry_info.data_is_empty() {
+        require!(!premium, CustomError::SymbolRequiresAuction);
+        return Ok(());
+    }
+    require_keys_eq!(*entry_info.owner, crate::ID, CustomError::InvalidSymbol);
+    let entry = SymbolEntry::try_deserialize(&mut &entry_info.try_borrow_data()?[..])?;
+    entry.check_launch(&ctx.accounts.creator.key(), Clock::get()?.unix_timestamp, premium)
+}
+
 #[inline(never)]
 fn create_pool_token_account_helper(ctx: &Context<Launch>) -> Result<()> {
     use anchor_spl::associated_token::Create;
@@ -129,12 +340,16 @@ fn create_metadata_helper(
 }
 
 #[inline(never)]
+#[allow(clippy::too_many_arguments)]
 fn initialize_pool_helper(
     pool: &mut Box<Account<'_, LiquidityPool>>,
     mint_key: Pubkey,
     bump: u8,
     initial_supply: u64,
     initial_sol_reserve: u64,
+    paperhand_tax_bps: Option<u16>,
+    cost_basis_mode: CostBasisMode,
+    creator: Pubkey,
 ) -> Result<()> {
     pool.token_one = mint_key;
     pool.token_two = mint_key;
@@ -142,12 +357,122 @@ fn initialize_pool_helper(
     pool.reserve_one = initial_supply;
     pool.reserve_two = initial_sol_reserve;
     pool.bump = bump;
+    pool.version = LiquidityPool::VERSION;
+    pool.creator = creator;
+    pool.cost_basis_mode = cost_basis_mode;
+    if let Some(tax_bps) = paperhand_tax_bps {
+        pool.has_tax_override = true;
+        pool.tax_override_bps = tax_bps;
+    }
+    Ok(())
+}
+
+#[inline(never)]
+fn record_launch_helper(ctx: &mut Context<Launch>, symbol: &str) -> Result<()> {
+    let creator = ctx.accounts.creator.key();
+    let profile = &mut ctx.accounts.creator_profile;
+    if profile.creator == Pubkey::default() {
+        profile.creator = creator;
+        profile.bump = ctx.bumps.creator_profile;
+    }
+    // The mint was derived from the current count, the next launch uses the following one
+    let launch_index = profile.launch_count;
+    profile.launch_count = launch_index
+        .checked_add(1)
+        .ok_or(CustomError::MathOverflow)?;
+
+    let clock = Clock::get()?;
+    let mint = ctx.accounts.mint.key();
+    let pool = ctx.accounts.pool.key();
+    ctx.accounts.launch_record.set_inner(LaunchRecord {
+        mint,
+        pool,
+        creator,
+        launch_index,
+        mint_bump: ctx.bumps.mint,
+        symbol: symbol.to_string(),
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+        bump: ctx.bumps.launch_record,
+        reserved: [0; 32],
+    });
+    Ok(())
+}
+
+/// Lock the creator allocation in its vesting escrow; the pool's token
+/// reserve is what remains of the initial supply
+#[inline(never)]
+fn create_vesting_helper(ctx: &mut Context<Launch>, vesting: VestingParams) -> Result<()> {
+    let escrow = ctx
+        .accounts
+        .vesting_escrow
+        .as_mut()
+        .ok_or(CustomError::VestingAccountsRequired)?;
+    let escrow_token_account = ctx
+        .accounts
+        .vesting_token_account
+        .as_ref()
+        .ok_or(CustomError::VestingAccountsRequired)?;
+    let pool = &mut ctx.accounts.pool;
+    let now = Clock::get()?.unix_timestamp;
+
+    pool.reserve_one = pool.reserve_one
+        .checked_sub(vesting.amount)
+        .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
+
+    escrow.set_inner(VestingEscrow {
+        mint: ctx.accounts.mint.key(),
+        pool: pool.key(),
+        beneficiary: ctx.accounts.creator.key(),
+        total_amount: vesting.amount,
+        claimed_amount: 0,
+        start_timestamp: now,
+        cliff_timestamp: now + vesting.cliff_seconds as i64,
+        end_timestamp: now + vesting.duration_seconds as i64,
+        bump: ctx.bumps.vesting_escrow.ok_or(CustomError::VestingAccountsRequired)?,
+        reserved: [0; 32],
+    });
+
+    anchor_spl::associated_token::create(CpiContext::new(
+        ctx.accounts.associated_token_program.to_account_info(),
+        anchor_spl::associated_token::Create {
+            payer: ctx.accounts.creator.to_account_info(),
+            associated_token: escrow_token_account.to_account_info(),
+            authority: escrow.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+    ))?;
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.mint.to_account_info(),
+                to: escrow_token_account.to_account_info(),
+                authority: ctx.accounts.global_account.to_account_info(),
+            },
+            &[&[b"global", &[ctx.bumps.global_account]]],
+        ),
+        vesting.amount,
+    )?;
+
+    emit!(VestingCreated {
+        beneficiary: escrow.beneficiary,
+        mint: escrow.mint,
+        escrow: escrow.key(),
+        total_amount: escrow.total_amount,
+        start_timestamp: escrow.start_timestamp,
+        cliff_timestamp: escrow.cliff_timestamp,
+        end_timestamp: escrow.end_timestamp,
+    });
+
     Ok(())
 }
 
 #[inline(never)]
 fn mint_tokens_helper(ctx: &Context<Launch>, initial_supply: u64) -> Result<()> {
-    token::mint_to(
+    token_interface::mint_to(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             MintTo {
@@ -179,6 +504,136 @@ fn transfer_sol_helper(ctx: &Context<Launch>, initial_sol_reserve: u64) -> Resul
     Ok(())
 }
 
+/// Buy for the creator through the same curve math and fee as `swap`
+///
+/// The program has no max-wallet limit, so the buy is only bounded by the
+/// curve's token reserve.
+#[inline(never)]
+fn initial_buy_helper<'info>(
+    ctx: &mut Context<'_, '_, '_, 'info, Launch<'info>>,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    require!(amount > 0, CustomError::InvalidAmount);
+    let config = &mut ctx.accounts.dex_configuration_account;
+    require!(!config.is_paused(PAUSE_BUY), CustomError::ProgramPaused);
+
+    let pool = &mut ctx.accounts.pool;
+    let position = ctx
+        .accounts
+        .creator_position
+        .as_mut()
+        .ok_or(CustomError::InitialBuyAccountsRequired)?;
+    let stats: &mut PoolStats = ctx
+        .accounts
+        .pool_stats
+        .as_deref_mut()
+        .ok_or(CustomError::InitialBuyAccountsRequired)?;
+    let creator_token_account = ctx
+        .accounts
+        .creator_token_account
+        .as_ref()
+        .ok_or(CustomError::InitialBuyAccountsRequired)?;
+    let clock = Clock::get()?;
+
+    let adjusted_amount = config.amount_after_fee(amount)?;
+    let tokens_out = pool.buy_output(adjusted_amount, decimals)?;
+
+    // The fee part of the SOL stays in the global vault as accrued protocol fees
+    let fee = amount
+        .checked_sub(adjusted_amount)
+        .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
+    config.accrued_fees = config.accrued_fees
+        .checked_add(fee)
+        .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
+
+    pool.reserve_one = pool.reserve_one
+        .checked_sub(tokens_out)
+        .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
+    pool.reserve_two = pool.reserve_two
+        .checked_add(adjusted_amount)
+        .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
+
+    position.set_inner(UserPosition::new(
+        pool.key(),
+        ctx.accounts.creator.key(),
+        ctx.bumps.creator_position.ok_or(CustomError::InitialBuyAccountsRequired)?,
+    ));
+    position.record_buy(pool, tokens_out, amount, clock.unix_timestamp)?;
+    if pool.cost_basis_mode == CostBasisMode::Fifo {
+        let lots: &mut PositionLots = ctx
+            .accounts
+            .creator_position_lots
+            .as_deref_mut()
+            .ok_or(CustomError::PositionLotsRequired)?;
+        lots.position = position.key();
+        lots.bump = ctx.bumps.creator_position_lots.ok_or(CustomError::PositionLotsRequired)?;
+        lots.push(tokens_out, amount, clock.unix_timestamp)?;
+    }
+
+    stats.pool = pool.key();
+    stats.bump = ctx.bumps.pool_stats.ok_or(CustomError::InitialBuyAccountsRequired)?;
+    stats.record_buy(amount, tokens_out, fee, true)?;
+    stats.update_ath(pool, clock.slot);
+    ctx.accounts.global_stats.load_mut()?.record_trade(amount, 0)?;
+
+    anchor_spl::associated_token::create(CpiContext::new(
+        ctx.accounts.associated_token_program.to_account_info(),
+        anchor_spl::associated_token::Create {
+            payer: ctx.accounts.creator.to_account_info(),
+            associated_token: creator_token_account.to_account_info(),
+            authority: ctx.accounts.creator.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+    ))?;
+    transfer_tokens(
+        &ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.pool_token_account.to_account_info(),
+        ctx.accounts.mint.to_account_info(),
+        creator_token_account.to_account_info(),
+        ctx.accounts.global_account.to_account_info(),
+        ctx.remaining_accounts,
+        tokens_out,
+        decimals,
+        &[&[b"global", &[ctx.bumps.global_account]]],
+    )?;
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.creator.to_account_info(),
+                to: ctx.accounts.global_account.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    emit!(TradeExecuted {
+        user: ctx.accounts.creator.key(),
+        pool: pool.key(),
+        side: "buy".to_string(),
+        token_amount: tokens_out,
+        sol_amount: amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(PositionUpdated::from_position(position));
+
+    Ok(())
+}
+
+#[inline(never)]
+fn update_global_stats_helper(ctx: &Context<Launch>) -> Result<()> {
+    let mut stats = ctx.accounts.global_stats.load_mut()?;
+    stats.total_launches = stats.total_launches
+        .checked_add(1)
+        .ok_or(CustomError::MathOverflow)?;
+    stats.update_ranking(ctx.accounts.mint.key(), ctx.accounts.pool.market_cap());
+    Ok(())
+}
+
 #[inline(never)]
 fn finalize_launch_helper(
     ctx: &Context<Launch>,
@@ -198,6 +653,9 @@ fn finalize_launch_helper(
         symbol,
         uri,
         initial_supply,
+        paperhand_tax_bps: ctx.accounts.pool.base_tax_bps(&ctx.accounts.dex_configuration_account),
+        launch_index: ctx.accounts.launch_record.launch_index,
+        mint_bump: ctx.accounts.launch_record.mint_bump,
         timestamp: clock.unix_timestamp,
     });
     
@@ -209,22 +667,34 @@ fn finalize_launch_helper(
 pub struct Launch<'info> {
     /// The CurveConfiguration must exist (initialized)
     #[account(
+        mut,
         seeds = [CurveConfiguration::SEED.as_bytes()],
         bump,
     )]
     pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
 
-    /// The new token mint (PDA derived from symbol for uniqueness)
+    /// Launch counter of the creator (init_if_needed on the first launch)
     #[account(
-        init,
+        init_if_needed,
         payer = creator,
-        mint::decimals = decimals,
-        mint::authority = global_account,
-        mint::freeze_authority = global_account,
-        seeds = [b"mint", symbol.as_bytes()],
+        space = CreatorProfile::ACCOUNT_SIZE,
+        seeds = [CreatorProfile::SEED_PREFIX.as_bytes(), creator.key().as_ref()],
         bump,
     )]
-    pub mint: Box<Account<'info, Mint>>,
+    pub creator_profile: Box<Account<'info, CreatorProfile>>,
+
+    /// The new token mint (PDA derived from the creator and its launch counter)
+    /// CHECK: Created in the instruction by `token_program`
+    #[account(
+        mut,
+        seeds = [
+            LaunchRecord::MINT_SEED_PREFIX.as_bytes(),
+            creator.key().as_ref(),
+            &creator_profile.launch_count.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub mint: UncheckedAccount<'info>,
 
     /// CHECK: Metaplex Metadata account (created via CPI)
     #[account(
@@ -249,6 +719,34 @@ pub struct Launch<'info> {
     )]
     pub pool: Box<Account<'info, LiquidityPool>>,
 
+    /// Reverse index from the mint to this launch
+    #[account(
+        init,
+        payer = creator,
+        space = LaunchRecord::ACCOUNT_SIZE,
+        seeds = [LaunchRecord::SEED_PREFIX.as_bytes(), mint.key().as_ref()],
+        bump,
+    )]
+    pub launch_record: Box<Account<'info, LaunchRecord>>,
+
+    /// Protocol-wide statistics and king-of-the-hill ranking
+    #[account(
+        mut,
+        seeds = [GlobalStats::SEED.as_bytes()],
+        bump = global_stats.load()?.bump,
+    )]
+    pub global_stats: AccountLoader<'info, GlobalStats>,
+
+    #[account(
+        seeds = [SymbolRegistry::SEED.as_bytes()],
+        bump = symbol_registry.bump,
+    )]
+    pub symbol_registry: Box<Account<'info, SymbolRegistry>>,
+
+    /// CHECK: Registry entry of the upper-cased symbol, checked in the
+    /// instruction; may not exist yet
+    pub symbol_entry: UncheckedAccount<'info>,
+
     /// CHECK: Global SOL vault PDA
     #[account(
         mut,
@@ -262,6 +760,73 @@ pub struct Launch<'info> {
     #[account(mut)]
     pub pool_token_account: UncheckedAccount<'info>,
 
+    /// Creator's position, required with an initial buy
+    #[account(
+        init,
+        payer = creator,
+        space = UserPosition::ACCOUNT_SIZE,
+        seeds = [UserPosition::SEED_PREFIX.as_bytes(), pool.key().as_ref(), creator.key().as_ref()],
+        bump,
+    )]
+    pub creator_position: Option<Box<Account<'info, UserPosition>>>,
+
+    /// Lots of the creator's position, required with an initial buy in FIFO pools
+    #[account(
+        init,
+        payer = creator,
+        space = PositionLots::ACCOUNT_SIZE,
+        seeds = [
+            PositionLots::SEED_PREFIX.as_bytes(),
+            creator_position.as_ref().map(|p| p.key()).unwrap_or_default().as_ref(),
+        ],
+        bump,
+    )]
+    pub creator_position_lots: Option<Box<Account<'info, PositionLots>>>,
+
+    /// Trading statistics of the pool, required with an initial buy
+    #[account(
+        init,
+        payer = creator,
+        space = PoolStats::ACCOUNT_SIZE,
+        seeds = [PoolStats::SEED_PREFIX.as_bytes(), pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_stats: Option<Box<Account<'info, PoolStats>>>,
+
+    /// Creator's token account, required with an initial buy
+    /// CHECK: Manually initialized in instruction
+    #[account(mut)]
+    pub creator_token_account: Option<UncheckedAccount<'info>>,
+
+    /// Escrow of the creator allocation, required with `creator_vesting`
+    #[account(
+        init,
+        payer = creator,
+        space = VestingEscrow::ACCOUNT_SIZE,
+        seeds = [VestingEscrow::SEED_PREFIX.as_bytes(), mint.key().as_ref()],
+        bump,
+    )]
+    pub vesting_escrow: Option<Box<Account<'info, VestingEscrow>>>,
+
+    /// Escrow's token account, required with `creator_vesting`
+    /// CHECK: Manually initialized in instruction
+    #[account(mut)]
+    pub vesting_token_account: Option<UncheckedAccount<'info>>,
+
+    /// Extra account list of the transfer hook, required for Token-2022
+    /// CHECK: Created by the hook program
+    #[account(
+        mut,
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump,
+        seeds::program = TRANSFER_HOOK_PROGRAM_ID,
+    )]
+    pub extra_account_meta_list: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Transfer hook program, required for Token-2022
+    #[account(address = TRANSFER_HOOK_PROGRAM_ID)]
+    pub transfer_hook_program: Option<UncheckedAccount<'info>>,
+
     /// The creator/payer
     #[account(mut)]
     pub creator: Signer<'info>,
@@ -270,9 +835,8 @@ pub struct Launch<'info> {
     /// CHECK: System program
     pub system_program: UncheckedAccount<'info>,
 
-    /// Token program
-    /// CHECK: Token program
-    pub token_program: UncheckedAccount<'info>,
+    /// SPL Token, or Token-2022 for a mint with the transfer hook
+    pub token_program: Interface<'info, TokenInterface>,
 
     /// Associated token program
     /// CHECK: Associated token program
@@ -298,4 +862,10 @@ pub enum LaunchError {
     InvalidSupply,
     #[msg("Initial SOL reserve must be greater than 0")]
     InvalidSolReserve,
+    #[msg("PaperHand tax is outside the allowed range")]
+    TaxOutOfBounds,
+    #[msg("Token-2022 launches only support average cost basis")]
+    FifoWithTransferHook,
+    #[msg("Creator allocation must be below the initial supply, with the cliff within the vesting duration")]
+    InvalidVestingSchedule,
 }
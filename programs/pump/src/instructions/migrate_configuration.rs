@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+use crate::{errors::CustomError, state::CurveConfiguration, utils::realloc_with_rent_topup};
+
+/// Event emitted when the configuration is converted to the current layout
+#[event]
+pub struct ConfigurationMigrated {
+    pub previous_size: u64,
+    pub version: u8,
+    pub fee_bps: u16,
+    pub payer: Pubkey,
+}
+
+/// Convert a legacy CurveConfiguration to the current versioned layout
+///
+/// Reads the old fields (fees as an f64 percent), reallocates the account to
+/// `CurveConfiguration::ACCOUNT_SIZE` and rewrites it with integer `fee_bps`.
+/// Only the upgrade authority may migrate, and it covers the extra rent.
+pub fn migrate_configuration(ctx: Context<MigrateConfiguration>) -> Result<()> {
+    let config_info = ctx.accounts.dex_configuration_account.to_account_info();
+    let previous_size = config_info.data_len();
+
+    let migrated = {
+        let data = config_info.try_borrow_data()?;
+        if !data.starts_with(CurveConfiguration::DISCRIMINATOR) {
+            return err!(CustomError::InvalidAccountData);
+        }
+        if data.len() >= CurveConfiguration::ACCOUNT_SIZE {
+            return err!(CustomError::AccountAlreadyMigrated);
+        }
+        CurveConfiguration::from_legacy(&data)?
+    };
+
+    realloc_with_rent_topup(
+        &config_info,
+        CurveConfiguration::ACCOUNT_SIZE,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let mut data = config_info.try_borrow_mut_data()?;
+    migrated.try_serialize(&mut &mut data[..])?;
+
+    emit!(ConfigurationMigrated {
+        previous_size: previous_size as u64,
+        version: migrated.version,
+        fee_bps: migrated.fee_bps,
+        payer: ctx.accounts.payer.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfiguration<'info> {
+    /// CHECK: May still be in the legacy layout, so it is decoded manually
+    #[account(
+        mut,
+        seeds = [CurveConfiguration::SEED.as_bytes()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub dex_configuration_account: UncheckedAccount<'info>,
+
+    /// Program data account holding the upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(payer.key()) @ CustomError::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::CustomError, state::LiquidityPool, utils::realloc_with_rent_topup};
+
+/// Event emitted when a pool is reallocated to the current layout
+#[event]
+pub struct PoolMigrated {
+    pub pool: Pubkey,
+    pub previous_size: u64,
+    pub version: u8,
+    pub payer: Pubkey,
+}
+
+/// Reallocate a pre-versioning LiquidityPool to the current layout
+///
+/// Permissionless: existing fields keep their offsets, the new bytes are zeroed
+/// and the caller pays the extra rent.
+pub fn migrate_pool(ctx: Context<MigratePool>) -> Result<()> {
+    let pool_info = ctx.accounts.pool.to_account_info();
+    let previous_size = pool_info.data_len();
+
+    {
+        let data = pool_info.try_borrow_data()?;
+        if !data.starts_with(LiquidityPool::DISCRIMINATOR) {
+            return err!(CustomError::InvalidAccountData);
+        }
+        if data.len() >= LiquidityPool::ACCOUNT_SIZE {
+            return err!(CustomError::AccountAlreadyMigrated);
+        }
+    }
+
+    realloc_with_rent_topup(
+        &pool_info,
+        LiquidityPool::ACCOUNT_SIZE,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let mut data = pool_info.try_borrow_mut_data()?;
+    let mut pool = LiquidityPool::try_deserialize(&mut &data[..])?;
+    pool.version = LiquidityPool::VERSION;
+    pool.try_serialize(&mut &mut data[..])?;
+
+    emit!(PoolMigrated {
+        pool: pool_info.key(),
+        previous_size: previous_size as u64,
+        version: pool.version,
+        payer: ctx.accounts.payer.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigratePool<'info> {
+    /// CHECK: Still in the legacy layout, so it is decoded after the realloc
+    #[account(
+        mut,
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), mint.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: Only used to derive the pool PDA
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::CustomError, state::UserPosition, utils::realloc_with_rent_topup};
+
+/// Event emitted when a user position is reallocated to the current layout
+#[event]
+pub struct PositionMigrated {
+    pub position: Pubkey,
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub previous_size: u64,
+    pub version: u8,
+    pub payer: Pubkey,
+}
+
+/// Reallocate a pre-versioning UserPosition to the current layout
+///
+/// Permissionless: the cost basis is preserved, the new bytes are zeroed and
+/// the caller pays the extra rent. Legacy positions never stored their pool,
+/// owner or bump, so those are filled in from the verified PDA seeds.
+pub fn migrate_position(ctx: Context<MigratePosition>) -> Result<()> {
+    let position_info = ctx.accounts.position.to_account_info();
+    let previous_size = position_info.data_len();
+
+    {
+        let data = position_info.try_borrow_data()?;
+        if !data.starts_with(UserPosition::DISCRIMINATOR) {
+            return err!(CustomError::InvalidAccountData);
+        }
+        if data.len() >= UserPosition::ACCOUNT_SIZE {
+            return err!(CustomError::AccountAlreadyMigrated);
+        }
+    }
+
+    realloc_with_rent_topup(
+        &position_info,
+        UserPosition::ACCOUNT_SIZE,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let mut data = position_info.try_borrow_mut_data()?;
+    let mut position = UserPosition::try_deserialize(&mut &data[..])?;
+    position.pool = ctx.accounts.pool.key();
+    position.owner = ctx.accounts.owner.key();
+    position.bump = ctx.bumps.position;
+    position.version = UserPosition::VERSION;
+    position.try_serialize(&mut &mut data[..])?;
+
+    emit!(PositionMigrated {
+        position: position_info.key(),
+        pool: position.pool,
+        owner: position.owner,
+        previous_size: previous_size as u64,
+        version: position.version,
+        payer: ctx.accounts.payer.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigratePosition<'info> {
+    /// CHECK: Still in the legacy layout, so it is decoded after the realloc
+    #[account(
+        mut,
+        seeds = [UserPosition::SEED_PREFIX.as_bytes(), pool.key().as_ref(), owner.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub position: UncheckedAccount<'info>,
+
+    /// CHECK: Only used to derive the position PDA
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: Position owner, only used to derive the position PDA
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
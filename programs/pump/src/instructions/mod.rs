@@ -12,5 +12,77 @@ pub use remove_liquidity::*;
 pub use swap::*;
 pub mod update_config;
 pub use update_config::*;
+pub mod approve_admin_action;
+pub mod bid_symbol;
+pub mod buyback_and_burn;
+pub mod claim_diamond_rewards;
+pub mod claim_referral_fees;
+pub mod claim_vested;
+pub mod close_position;
+pub mod create_admin_council;
+pub mod execute_admin_action;
+pub mod initialize_global_stats;
+pub mod initialize_symbol_registry;
+pub mod initialize_treasury_vault;
+pub mod migrate_configuration;
+pub mod migrate_pool;
+pub mod migrate_position;
+pub mod propose_admin_action;
+pub mod record_hook_transfer;
+pub mod register_referrer;
+pub mod reserve_symbol;
+pub mod set_auto_close_positions;
+pub mod set_buyback_config;
+pub mod set_pause;
+pub mod set_referral_fee_bps;
+pub mod set_symbol_authority;
+pub mod set_symbol_blocked;
+pub mod set_symbol_registry_config;
+pub mod set_tax_bounds;
+pub mod set_tax_mode;
+pub mod set_tax_schedule;
+pub mod set_tax_split;
+pub mod set_treasury_withdrawal_limit;
+pub mod set_untracked_token_policy;
+pub mod settle_symbol_auction;
+pub mod transfer_position;
+pub mod withdraw_fees;
+pub mod withdraw_treasury;
+pub use approve_admin_action::*;
+pub use bid_symbol::*;
+pub use buyback_and_burn::*;
+pub use claim_diamond_rewards::*;
+pub use claim_referral_fees::*;
+pub use claim_vested::*;
+pub use close_position::*;
+pub use create_admin_council::*;
+pub use execute_admin_action::*;
+pub use initialize_global_stats::*;
+pub use initialize_symbol_registry::*;
+pub use initialize_treasury_vault::*;
+pub use migrate_configuration::*;
+pub use migrate_pool::*;
+pub use migrate_position::*;
+pub use propose_admin_action::*;
+pub use record_hook_transfer::*;
+pub use register_referrer::*;
+pub use reserve_symbol::*;
+pub use set_auto_close_positions::*;
+pub use set_buyback_config::*;
+pub use set_pause::*;
+pub use set_referral_fee_bps::*;
+pub use set_symbol_authority::*;
+pub use set_symbol_blocked::*;
+pub use set_symbol_registry_config::*;
+pub use set_tax_bounds::*;
+pub use set_tax_mode::*;
+pub use set_tax_schedule::*;
+pub use set_tax_split::*;
+pub use set_treasury_withdrawal_limit::*;
+pub use set_untracked_token_policy::*;
+pub use settle_symbol_auction::*;
+pub use transfer_position::*;
+pub use withdraw_fees::*;
+pub use withdraw_treasury::*;
 // pub use create_raydium_pool::*;
 
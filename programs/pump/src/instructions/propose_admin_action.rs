@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::CustomError,
+    state::{AdminAction, AdminCouncil, AdminProposal},
+};
+
+/// Event emitted when a council member proposes an admin action
+#[event]
+pub struct AdminActionProposed {
+    pub council: Pubkey,
+    pub proposal: Pubkey,
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+}
+
+/// Create a proposal for an admin action
+///
+/// The proposer's approval is recorded immediately.
+pub fn propose_admin_action(ctx: Context<ProposeAdminAction>, action: AdminAction) -> Result<()> {
+    let council = &mut ctx.accounts.admin_council;
+    let proposal = &mut ctx.accounts.proposal;
+
+    let member_index = council
+        .member_index(&ctx.accounts.proposer.key())
+        .ok_or(CustomError::NotCouncilMember)?;
+
+    proposal.set_inner(AdminProposal {
+        council: council.key(),
+        id: council.proposal_count,
+        proposer: ctx.accounts.proposer.key(),
+        action: action.clone(),
+        approvals: 0,
+        executed: false,
+        bump: ctx.bumps.proposal,
+    });
+    proposal.approve(member_index)?;
+
+    council.proposal_count = council
+        .proposal_count
+        .checked_add(1)
+        .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
+
+    emit!(AdminActionProposed {
+        council: council.key(),
+        proposal: proposal.key(),
+        id: proposal.id,
+        proposer: proposal.proposer,
+        action,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdminAction<'info> {
+    #[account(
+        mut,
+        seeds = [AdminCouncil::SEED.as_bytes()],
+        bump = admin_council.bump,
+    )]
+    pub admin_council: Box<Account<'info, AdminCouncil>>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = AdminProposal::ACCOUNT_SIZE,
+        seeds = [
+            AdminProposal::SEED_PREFIX.as_bytes(),
+            admin_council.key().as_ref(),
+            admin_council.proposal_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub proposal: Box<Account<'info, AdminProposal>>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
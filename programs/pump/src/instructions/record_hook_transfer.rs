@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::{
+    consts::{HOOK_AUTHORITY_SEED, TRANSFER_HOOK_PROGRAM_ID},
+    errors::CustomError,
+    instructions::{PositionTransferred, PositionUpdated},
+    state::{LiquidityPool, PoolStats, UserPosition},
+};
+
+/// Move the cost basis of tokens sent by a plain Token-2022 transfer
+///
+/// Called by the transfer hook for transfers made outside this program. The
+/// sender's position gives up the tokens' proportional share of its cost
+/// basis and the recipient's position takes them over with their entry time,
+/// as in `transfer_position`. A recipient without a position can't hold the
+/// basis, so the transfer is rejected rather than letting it be dropped.
+pub fn record_hook_transfer(ctx: Context<RecordHookTransfer>, amount: u64) -> Result<()> {
+    let source_info = ctx.accounts.source_position.to_account_info();
+    let destination_info = ctx.accounts.destination_position.to_account_info();
+
+    // Nothing to move without a sending position, or between a wallet's own accounts
+    if !is_pump_account(&source_info) || source_info.key == destination_info.key {
+        return Ok(());
+    }
+
+    let pool = &mut ctx.accounts.pool;
+    let mut source = UserPosition::try_deserialize(&mut &source_info.try_borrow_data()?[..])?;
+    let now = Clock::get()?.unix_timestamp;
+
+    let moved = amount.min(source.total_tokens);
+    if moved == 0 {
+        return Ok(());
+    }
+    require!(is_pump_account(&destination_info), CustomError::RecipientPositionRequired);
+    let cost_basis = source.calculate_cost_basis_for_sale(moved, None)?;
+    // Unknown entry times count as just bought, as in `transfer_position`
+    let entry_timestamp = if source.entry_timestamp == 0 { now } else { source.entry_timestamp };
+
+    source.remove_tokens(pool, moved, cost_basis)?;
+    source.try_serialize(&mut &mut source_info.try_borrow_mut_data()?[..])?;
+    emit!(PositionUpdated::from_position(&source));
+
+    let mut destination =
+        UserPosition::try_deserialize(&mut &destination_info.try_borrow_data()?[..])?;
+    // Pools that never traded have no statistics yet
+    let stats_info = ctx.accounts.pool_stats.to_account_info();
+    if destination.total_tokens == 0 && is_pump_account(&stats_info) {
+        let mut stats = PoolStats::try_deserialize(&mut &stats_info.try_borrow_data()?[..])?;
+        stats.record_new_holder();
+        stats.try_serialize(&mut &mut stats_info.try_borrow_mut_data()?[..])?;
+    }
+    destination.add_tokens(pool, moved, cost_basis, entry_timestamp)?;
+    destination.try_serialize(&mut &mut destination_info.try_borrow_mut_data()?[..])?;
+    emit!(PositionUpdated::from_position(&destination));
+
+    emit!(PositionTransferred {
+        pool: pool.key(),
+        from: ctx.accounts.source_token_account.owner,
+        to: ctx.accounts.destination_token_account.owner,
+        token_amount: moved,
+        cost_basis,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+fn is_pump_account(info: &AccountInfo) -> bool {
+    info.owner == &crate::ID && !info.data_is_empty()
+}
+
+#[derive(Accounts)]
+pub struct RecordHookTransfer<'info> {
+    /// Signer PDA of the transfer hook program
+    #[account(
+        seeds = [HOOK_AUTHORITY_SEED.as_bytes()],
+        bump,
+        seeds::program = TRANSFER_HOOK_PROGRAM_ID,
+    )]
+    pub hook_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), mint.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(token::mint = mint)]
+    pub source_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(token::mint = mint)]
+    pub destination_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Position of the source account's owner; may not exist
+    #[account(
+        mut,
+        seeds = [
+            UserPosition::SEED_PREFIX.as_bytes(),
+            pool.key().as_ref(),
+            source_token_account.owner.as_ref(),
+        ],
+        bump,
+    )]
+    pub source_position: UncheckedAccount<'info>,
+
+    /// CHECK: Position of the destination account's owner; may not exist
+    #[account(
+        mut,
+        seeds = [
+            UserPosition::SEED_PREFIX.as_bytes(),
+            pool.key().as_ref(),
+            destination_token_account.owner.as_ref(),
+        ],
+        bump,
+    )]
+    pub destination_position: UncheckedAccount<'info>,
+
+    /// CHECK: Statistics of the pool; may not exist
+    #[account(
+        mut,
+        seeds = [PoolStats::SEED_PREFIX.as_bytes(), pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_stats: UncheckedAccount<'info>,
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Referrer;
+
+/// Event emitted when a wallet starts referring traders
+#[event]
+pub struct ReferrerRegistered {
+    pub referrer: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Create the caller's referrer account, to be passed to `swap` by referred
+/// traders
+pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+    let referrer = &mut ctx.accounts.referrer;
+    referrer.owner = ctx.accounts.owner.key();
+    referrer.bump = ctx.bumps.referrer;
+
+    emit!(ReferrerRegistered {
+        referrer: referrer.key(),
+        owner: referrer.owner,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = Referrer::ACCOUNT_SIZE,
+        seeds = [Referrer::SEED_PREFIX.as_bytes(), owner.key().as_ref()],
+        bump,
+    )]
+    pub referrer: Box<Account<'info, Referrer>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
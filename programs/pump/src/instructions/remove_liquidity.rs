@@ -1,19 +1,36 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token};
 
-use crate::state::LiquidityPool;
+use crate::{
+    consts::PAUSE_REMOVE_LIQUIDITY,
+    errors::CustomError,
+    state::{CurveConfiguration, LiquidityPool},
+};
 
 pub fn remove_liquidity(
-    _ctx: Context<RemoveLiquidity>,
+    ctx: Context<RemoveLiquidity>,
     _nonce: u8,
     _init_pc_amount: u64,
 ) -> Result<()> {
+    if ctx.accounts.dex_configuration_account.is_paused(PAUSE_REMOVE_LIQUIDITY) {
+        return err!(CustomError::ProgramPaused);
+    }
+    if ctx.accounts.pool.is_paused(PAUSE_REMOVE_LIQUIDITY) {
+        return err!(CustomError::PoolPaused);
+    }
+
     // If you want to Interact with CPI, then plz contact to me.
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct RemoveLiquidity<'info> {
+    #[account(
+        seeds = [CurveConfiguration::SEED.as_bytes()],
+        bump,
+    )]
+    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+
     #[account(
         mut,
         seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), coin_mint.key().as_ref()],
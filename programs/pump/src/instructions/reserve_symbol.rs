@@ -0,0 +1,77 @@
+use crate::{errors::CustomError, state::*};
+use anchor_lang::prelude::*;
+
+/// Event emitted when a symbol's reservation changes
+#[event]
+pub struct SymbolReserved {
+    pub symbol: String,
+    /// Wallet allowed to launch the symbol (default = reservation lifted)
+    pub holder: Pubkey,
+    pub expires_at: i64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Reserve a symbol for a verified project until `expires_at` (0 = no
+/// expiry), or lift the reservation with the default pubkey (registry
+/// moderator only)
+///
+/// Symbols are given in upper case, as stored in the registry.
+pub fn reserve_symbol(
+    ctx: Context<ReserveSymbol>,
+    symbol: String,
+    holder: Pubkey,
+    expires_at: i64,
+) -> Result<()> {
+    SymbolEntry::validate(&symbol)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    if holder != Pubkey::default() && expires_at != 0 && expires_at <= now {
+        return err!(CustomError::InvalidAmount);
+    }
+
+    let entry = &mut ctx.accounts.symbol_entry;
+    entry.init_if_new(&symbol, ctx.bumps.symbol_entry);
+    if entry.blocked {
+        return err!(CustomError::SymbolBlocked);
+    }
+    if entry.has_auction() && !entry.auction_settled {
+        return err!(CustomError::SymbolInAuction);
+    }
+    entry.reserved_for = holder;
+    entry.reservation_expires_at = expires_at;
+
+    emit!(SymbolReserved {
+        symbol,
+        holder,
+        expires_at,
+        authority: ctx.accounts.authority.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct ReserveSymbol<'info> {
+    #[account(
+        seeds = [SymbolRegistry::SEED.as_bytes()],
+        bump = symbol_registry.bump,
+        has_one = authority @ CustomError::Unauthorized,
+    )]
+    pub symbol_registry: Box<Account<'info, SymbolRegistry>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SymbolEntry::ACCOUNT_SIZE,
+        seeds = [SymbolEntry::SEED_PREFIX.as_bytes(), symbol.as_bytes()],
+        bump,
+    )]
+    pub symbol_entry: Box<Account<'info, SymbolEntry>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
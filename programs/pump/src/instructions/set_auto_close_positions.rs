@@ -0,0 +1,48 @@
+use crate::{errors::CustomError, state::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+/// Event emitted when closing of emptied positions is switched on or off
+#[event]
+pub struct AutoClosePositionsUpdated {
+    pub enabled: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Choose whether a sell that empties a position also closes it, refunding
+/// the rent to the seller
+pub fn set_auto_close_positions(ctx: Context<SetAutoClosePositions>, enabled: bool) -> Result<()> {
+    ctx.accounts.dex_configuration_account.auto_close_positions = enabled;
+
+    emit!(AutoClosePositionsUpdated {
+        enabled,
+        authority: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetAutoClosePositions<'info> {
+    #[account(
+        mut,
+        seeds = [CurveConfiguration::SEED.as_bytes()],
+        bump,
+        constraint = !dex_configuration_account.has_council() @ CustomError::CouncilApprovalRequired,
+    )]
+    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+
+    /// Program data account holding the upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ CustomError::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
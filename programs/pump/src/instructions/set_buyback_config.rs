@@ -0,0 +1,59 @@
+use crate::{errors::CustomError, state::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+/// Event emitted when the buyback settings change
+#[event]
+pub struct BuybackConfigUpdated {
+    pub buyback_bps: u16,
+    pub max_sol_per_call: u64,
+    pub cooldown_seconds: u32,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Configure how much of the treasury's tax funds buybacks, and how fast the
+/// `buyback_and_burn` crank may spend it
+pub fn set_buyback_config(
+    ctx: Context<SetBuybackConfig>,
+    buyback_bps: u16,
+    max_sol_per_call: u64,
+    cooldown_seconds: u32,
+) -> Result<()> {
+    ctx.accounts
+        .dex_configuration_account
+        .set_buyback_config(buyback_bps, max_sol_per_call, cooldown_seconds)?;
+
+    emit!(BuybackConfigUpdated {
+        buyback_bps,
+        max_sol_per_call,
+        cooldown_seconds,
+        authority: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetBuybackConfig<'info> {
+    #[account(
+        mut,
+        seeds = [CurveConfiguration::SEED.as_bytes()],
+        bump,
+        constraint = !dex_configuration_account.has_council() @ CustomError::CouncilApprovalRequired,
+    )]
+    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+
+    /// Program data account holding the upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ CustomError::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
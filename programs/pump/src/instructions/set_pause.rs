@@ -0,0 +1,109 @@
+use crate::{errors::CustomError, state::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+/// Event emitted whenever global or per-pool pause switches change
+#[event]
+pub struct PauseUpdated {
+    /// `None` for the global switches, the pool otherwise
+    pub pool: Option<Pubkey>,
+    pub previous_flags: u8,
+    pub pause_flags: u8,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Set the pause switches, globally or for a single pool when `pool` is passed
+///
+/// Before a council exists this is gated on the upgrade authority. Afterwards
+/// any single council member may turn switches on, so trading can be halted
+/// without waiting for approvals; turning them off takes an approved
+/// `SetPauseFlags` or `SetPoolPauseFlags` proposal.
+pub fn set_pause(ctx: Context<SetPause>, pause_flags: u8) -> Result<()> {
+    let previous_flags = match ctx.accounts.pool.as_ref() {
+        Some(pool) => pool.pause_flags,
+        None => ctx.accounts.dex_configuration_account.pause_flags,
+    };
+    ctx.accounts.check_authority(previous_flags, pause_flags)?;
+
+    let pool = match ctx.accounts.pool.as_mut() {
+        Some(pool) => {
+            pool.set_pause_flags(pause_flags)?;
+            Some(pool.key())
+        }
+        None => {
+            ctx.accounts.dex_configuration_account.set_pause_flags(pause_flags)?;
+            None
+        }
+    };
+
+    emit!(PauseUpdated {
+        pool,
+        previous_flags,
+        pause_flags,
+        authority: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(
+        mut,
+        seeds = [CurveConfiguration::SEED.as_bytes()],
+        bump,
+    )]
+    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+
+    /// Pool to pause; the global switches are set when omitted
+    #[account(
+        mut,
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), pool.token_one.as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Option<Box<Account<'info, LiquidityPool>>>,
+
+    /// Program data account holding the upgrade authority, required before a
+    /// council exists
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ CustomError::Unauthorized,
+    )]
+    pub program_data: Option<Account<'info, ProgramData>>,
+
+    /// The admin council, required once one exists
+    #[account(
+        seeds = [AdminCouncil::SEED.as_bytes()],
+        bump = admin_council.bump,
+        constraint = admin_council.key() == dex_configuration_account.admin_council @ CustomError::InvalidCouncil,
+    )]
+    pub admin_council: Option<Box<Account<'info, AdminCouncil>>>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+impl SetPause<'_> {
+    fn check_authority(&self, previous_flags: u8, pause_flags: u8) -> Result<()> {
+        if !self.dex_configuration_account.has_council() {
+            // The account constraint checks the upgrade authority
+            require!(self.program_data.is_some(), CustomError::Unauthorized);
+            return Ok(());
+        }
+
+        let council = self.admin_council.as_ref().ok_or(CustomError::InvalidCouncil)?;
+        require!(
+            council.member_index(&self.admin.key()).is_some(),
+            CustomError::NotCouncilMember
+        );
+        require!(
+            pause_flags & previous_flags == previous_flags,
+            CustomError::CouncilApprovalRequired
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{errors::CustomError, state::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+/// Event emitted when the referrers' share of the protocol fee changes
+#[event]
+pub struct ReferralFeeUpdated {
+    pub previous_referral_fee_bps: u16,
+    pub referral_fee_bps: u16,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Set the share of the protocol fee paid to the referrer of each trade
+pub fn set_referral_fee_bps(ctx: Context<SetReferralFeeBps>, referral_fee_bps: u16) -> Result<()> {
+    let config = &mut ctx.accounts.dex_configuration_account;
+    let previous_referral_fee_bps = config.referral_fee_bps;
+    config.set_referral_fee_bps(referral_fee_bps)?;
+
+    emit!(ReferralFeeUpdated {
+        previous_referral_fee_bps,
+        referral_fee_bps,
+        authority: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetReferralFeeBps<'info> {
+    #[account(
+        mut,
+        seeds = [CurveConfiguration::SEED.as_bytes()],
+        bump,
+        constraint = !dex_configuration_account.has_council() @ CustomError::CouncilApprovalRequired,
+    )]
+    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+
+    /// Program data account holding the upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ CustomError::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
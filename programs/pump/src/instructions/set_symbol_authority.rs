@@ -0,0 +1,40 @@
+use crate::{errors::CustomError, state::*};
+use anchor_lang::prelude::*;
+
+/// Event emitted when the symbol registry gets a new moderator
+#[event]
+pub struct SymbolAuthorityUpdated {
+    pub previous_authority: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Hand the symbol registry over to another moderator
+///
+/// The admin council can also appoint one through `propose_admin_action`.
+pub fn set_symbol_authority(ctx: Context<SetSymbolAuthority>, new_authority: Pubkey) -> Result<()> {
+    let registry = &mut ctx.accounts.symbol_registry;
+    let previous_authority = registry.authority;
+    registry.authority = new_authority;
+
+    emit!(SymbolAuthorityUpdated {
+        previous_authority,
+        authority: new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetSymbolAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [SymbolRegistry::SEED.as_bytes()],
+        bump = symbol_registry.bump,
+        has_one = authority @ CustomError::Unauthorized,
+    )]
+    pub symbol_registry: Box<Account<'info, SymbolRegistry>>,
+
+    pub authority: Signer<'info>,
+}
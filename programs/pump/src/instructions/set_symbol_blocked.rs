@@ -0,0 +1,64 @@
+use crate::{errors::CustomError, state::*};
+use anchor_lang::prelude::*;
+
+/// Event emitted when a symbol is blocked or unblocked
+#[event]
+pub struct SymbolBlockUpdated {
+    pub symbol: String,
+    pub blocked: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Block an offensive or impersonating symbol from being launched, or lift
+/// the block (registry moderator only)
+///
+/// Symbols are given in upper case, as stored in the registry.
+pub fn set_symbol_blocked(
+    ctx: Context<SetSymbolBlocked>,
+    symbol: String,
+    blocked: bool,
+) -> Result<()> {
+    SymbolEntry::validate(&symbol)?;
+
+    let entry = &mut ctx.accounts.symbol_entry;
+    entry.init_if_new(&symbol, ctx.bumps.symbol_entry);
+    // Blocking can't strand the escrowed bids of a running auction
+    if blocked && entry.has_auction() && !entry.auction_settled {
+        return err!(CustomError::SymbolInAuction);
+    }
+    entry.blocked = blocked;
+
+    emit!(SymbolBlockUpdated {
+        symbol,
+        blocked,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct SetSymbolBlocked<'info> {
+    #[account(
+        seeds = [SymbolRegistry::SEED.as_bytes()],
+        bump = symbol_registry.bump,
+        has_one = authority @ CustomError::Unauthorized,
+    )]
+    pub symbol_registry: Box<Account<'info, SymbolRegistry>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SymbolEntry::ACCOUNT_SIZE,
+        seeds = [SymbolEntry::SEED_PREFIX.as_bytes(), symbol.as_bytes()],
+        bump,
+    )]
+    pub symbol_entry: Box<Account<'info, SymbolEntry>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,57 @@
+use crate::{errors::CustomError, state::*};
+use anchor_lang::prelude::*;
+
+/// Event emitted when the symbol registry's settings change
+#[event]
+pub struct SymbolRegistryConfigUpdated {
+    pub premium_max_len: u8,
+    pub auction_duration_seconds: u32,
+    pub min_bid: u64,
+    pub min_bid_increment_bps: u16,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Tune which symbols are premium and how their auctions run (registry
+/// moderator only)
+pub fn set_symbol_registry_config(
+    ctx: Context<SetSymbolRegistryConfig>,
+    premium_max_len: u8,
+    auction_duration_seconds: u32,
+    min_bid: u64,
+    min_bid_increment_bps: u16,
+) -> Result<()> {
+    if auction_duration_seconds == 0 || min_bid == 0 || min_bid_increment_bps > 10000 {
+        return err!(CustomError::InvalidAmount);
+    }
+
+    let registry = &mut ctx.accounts.symbol_registry;
+    registry.premium_max_len = premium_max_len;
+    registry.auction_duration_seconds = auction_duration_seconds;
+    registry.min_bid = min_bid;
+    registry.min_bid_increment_bps = min_bid_increment_bps;
+
+    emit!(SymbolRegistryConfigUpdated {
+        premium_max_len,
+        auction_duration_seconds,
+        min_bid,
+        min_bid_increment_bps,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetSymbolRegistryConfig<'info> {
+    #[account(
+        mut,
+        seeds = [SymbolRegistry::SEED.as_bytes()],
+        bump = symbol_registry.bump,
+        has_one = authority @ CustomError::Unauthorized,
+    )]
+    pub symbol_registry: Box<Account<'info, SymbolRegistry>>,
+
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,51 @@
+use crate::{errors::CustomError, state::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+/// Event emitted when the launch-time paperhand tax bounds change
+#[event]
+pub struct TaxBoundsUpdated {
+    pub min_paperhand_tax_bps: u16,
+    pub max_paperhand_tax_bps: u16,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Set the range of paperhand tax creators may choose in `launch`
+pub fn set_tax_bounds(ctx: Context<SetTaxBounds>, min_bps: u16, max_bps: u16) -> Result<()> {
+    ctx.accounts
+        .dex_configuration_account
+        .set_tax_bounds(min_bps, max_bps)?;
+
+    emit!(TaxBoundsUpdated {
+        min_paperhand_tax_bps: min_bps,
+        max_paperhand_tax_bps: max_bps,
+        authority: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetTaxBounds<'info> {
+    #[account(
+        mut,
+        seeds = [CurveConfiguration::SEED.as_bytes()],
+        bump,
+        constraint = !dex_configuration_account.has_council() @ CustomError::CouncilApprovalRequired,
+    )]
+    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+
+    /// Program data account holding the upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ CustomError::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
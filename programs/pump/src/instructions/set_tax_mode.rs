@@ -0,0 +1,55 @@
+use crate::{errors::CustomError, state::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+/// Event emitted when the paperhand tax formula changes
+#[event]
+pub struct TaxModeUpdated {
+    pub tax_mode: TaxMode,
+    pub loss_brackets: Vec<LossBracket>,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Switch the paperhand tax formula
+pub fn set_tax_mode(
+    ctx: Context<SetTaxMode>,
+    tax_mode: TaxMode,
+    loss_brackets: Vec<LossBracket>,
+) -> Result<()> {
+    ctx.accounts
+        .dex_configuration_account
+        .set_tax_mode(tax_mode, &loss_brackets)?;
+
+    emit!(TaxModeUpdated {
+        tax_mode,
+        loss_brackets,
+        authority: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetTaxMode<'info> {
+    #[account(
+        mut,
+        seeds = [CurveConfiguration::SEED.as_bytes()],
+        bump,
+        constraint = !dex_configuration_account.has_council() @ CustomError::CouncilApprovalRequired,
+    )]
+    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+
+    /// Program data account holding the upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ CustomError::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
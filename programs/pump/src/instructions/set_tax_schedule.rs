@@ -0,0 +1,49 @@
+use crate::{errors::CustomError, state::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+/// Event emitted when the holding-time tax schedule is replaced
+#[event]
+pub struct TaxScheduleUpdated {
+    pub brackets: Vec<TaxBracket>,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Replace the holding-time paperhand tax schedule
+pub fn set_tax_schedule(ctx: Context<SetTaxSchedule>, brackets: Vec<TaxBracket>) -> Result<()> {
+    ctx.accounts
+        .dex_configuration_account
+        .set_tax_schedule(&brackets)?;
+
+    emit!(TaxScheduleUpdated {
+        brackets,
+        authority: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetTaxSchedule<'info> {
+    #[account(
+        mut,
+        seeds = [CurveConfiguration::SEED.as_bytes()],
+        bump,
+        constraint = !dex_configuration_account.has_council() @ CustomError::CouncilApprovalRequired,
+    )]
+    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+
+    /// Program data account holding the upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ CustomError::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,62 @@
+use crate::{errors::CustomError, state::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+/// Event emitted when the paperhand tax split changes
+#[event]
+pub struct TaxSplitUpdated {
+    pub treasury_bps: u16,
+    pub reserve_bps: u16,
+    pub creator_bps: u16,
+    pub reward_bps: u16,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Set how each paperhand tax payment is split between the treasury, the
+/// curve's SOL reserve, the pool creator and diamond-hand rewards
+pub fn set_tax_split(
+    ctx: Context<SetTaxSplit>,
+    treasury_bps: u16,
+    reserve_bps: u16,
+    creator_bps: u16,
+    reward_bps: u16,
+) -> Result<()> {
+    ctx.accounts
+        .dex_configuration_account
+        .set_tax_split(treasury_bps, reserve_bps, creator_bps, reward_bps)?;
+
+    emit!(TaxSplitUpdated {
+        treasury_bps,
+        reserve_bps,
+        creator_bps,
+        reward_bps,
+        authority: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetTaxSplit<'info> {
+    #[account(
+        mut,
+        seeds = [CurveConfiguration::SEED.as_bytes()],
+        bump,
+        constraint = !dex_configuration_account.has_council() @ CustomError::CouncilApprovalRequired,
+    )]
+    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+
+    /// Program data account holding the upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ CustomError::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,60 @@
+use crate::{errors::CustomError, state::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+/// Event emitted when the treasury's per-epoch withdrawal limit changes
+#[event]
+pub struct TreasuryWithdrawalLimitUpdated {
+    pub previous_limit: u64,
+    pub limit: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Cap how many lamports may leave the treasury vault per epoch (0 = no limit)
+pub fn set_treasury_withdrawal_limit(
+    ctx: Context<SetTreasuryWithdrawalLimit>,
+    limit: u64,
+) -> Result<()> {
+    let vault = &mut ctx.accounts.treasury_vault;
+    let previous_limit = vault.epoch_withdrawal_limit;
+    vault.epoch_withdrawal_limit = limit;
+
+    emit!(TreasuryWithdrawalLimitUpdated {
+        previous_limit,
+        limit,
+        authority: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetTreasuryWithdrawalLimit<'info> {
+    #[account(
+        seeds = [CurveConfiguration::SEED.as_bytes()],
+        bump,
+        constraint = !dex_configuration_account.has_council() @ CustomError::CouncilApprovalRequired,
+    )]
+    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+
+    #[account(
+        mut,
+        seeds = [TreasuryVault::SEED.as_bytes()],
+        bump = treasury_vault.bump,
+    )]
+    pub treasury_vault: Box<Account<'info, TreasuryVault>>,
+
+    /// Program data account holding the upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ CustomError::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
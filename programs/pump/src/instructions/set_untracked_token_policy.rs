@@ -0,0 +1,54 @@
+use crate::{errors::CustomError, state::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+/// Event emitted when the policy for untracked tokens changes
+#[event]
+pub struct UntrackedTokenPolicyUpdated {
+    pub previous_policy: UntrackedTokenPolicy,
+    pub policy: UntrackedTokenPolicy,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Choose how sales of tokens acquired outside the bonding curve are handled
+pub fn set_untracked_token_policy(
+    ctx: Context<SetUntrackedTokenPolicy>,
+    policy: UntrackedTokenPolicy,
+) -> Result<()> {
+    let config = &mut ctx.accounts.dex_configuration_account;
+    let previous_policy = config.untracked_token_policy;
+    config.untracked_token_policy = policy;
+
+    emit!(UntrackedTokenPolicyUpdated {
+        previous_policy,
+        policy,
+        authority: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetUntrackedTokenPolicy<'info> {
+    #[account(
+        mut,
+        seeds = [CurveConfiguration::SEED.as_bytes()],
+        bump,
+        constraint = !dex_configuration_account.has_council() @ CustomError::CouncilApprovalRequired,
+    )]
+    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+
+    /// Program data account holding the upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ CustomError::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::CustomError,
+    state::{CurveConfiguration, SymbolEntry, TreasuryVault},
+};
+
+/// Event emitted when a symbol auction is settled
+#[event]
+pub struct SymbolAuctionSettled {
+    pub symbol: String,
+    pub winner: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Close an ended auction: the winning bid goes to the treasury and the
+/// symbol is reserved for the winner without expiry
+///
+/// Permissionless, so a winner can't be blocked from claiming the symbol.
+pub fn settle_symbol_auction(ctx: Context<SettleSymbolAuction>, _symbol: String) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let entry = &mut ctx.accounts.symbol_entry;
+    if !entry.has_auction()
+        || entry.auction_settled
+        || entry.highest_bid == 0
+        || now < entry.auction_end_time
+    {
+        return err!(CustomError::AuctionNotEnded);
+    }
+
+    let amount = entry.highest_bid;
+    let vault_info = ctx.accounts.treasury_vault.to_account_info();
+    **entry.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **vault_info.try_borrow_mut_lamports()? = vault_info
+        .lamports()
+        .checked_add(amount)
+        .ok_or(CustomError::MathOverflow)?;
+
+    entry.reserved_for = entry.highest_bidder;
+    entry.reservation_expires_at = 0;
+    entry.auction_settled = true;
+
+    emit!(SymbolAuctionSettled {
+        symbol: entry.symbol.clone(),
+        winner: entry.highest_bidder,
+        amount,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct SettleSymbolAuction<'info> {
+    #[account(
+        seeds = [CurveConfiguration::SEED.as_bytes()],
+        bump,
+    )]
+    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+
+    #[account(
+        mut,
+        seeds = [SymbolEntry::SEED_PREFIX.as_bytes(), symbol.as_bytes()],
+        bump = symbol_entry.bump,
+    )]
+    pub symbol_entry: Box<Account<'info, SymbolEntry>>,
+
+    /// Program-owned vault receiving the winning bid
+    #[account(
+        mut,
+        seeds = [TreasuryVault::SEED.as_bytes()],
+        bump = treasury_vault.bump,
+        constraint = treasury_vault.key() == dex_configuration_account.treasury @ CustomError::InvalidTreasury,
+    )]
+    pub treasury_vault: Box<Account<'info, TreasuryVault>>,
+
+    pub cranker: Signer<'info>,
+}
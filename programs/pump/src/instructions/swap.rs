@@ -2,14 +2,19 @@ use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
-use std::ops::{Div, Mul, Sub};
+use std::ops::Div;
 
 use crate::{
+    consts::{PAUSE_BUY, PAUSE_SELL},
     errors::CustomError,
-    state::{CurveConfiguration, LiquidityPool, UserPosition},
-    utils::{convert_from_float, convert_to_float},
+    instructions::PositionClosed,
+    state::{
+        CostBasisMode, CurveConfiguration, GlobalStats, LiquidityPool, PoolStats, PositionLots,
+        Referral, Referrer, TaxMode, TreasuryVault, UntrackedTokenPolicy, UserPosition,
+    },
+    utils::{apply_bps, convert_from_float, convert_to_float, transfer_tokens},
 };
 
 /// Events for tracking trades and tax application
@@ -29,37 +34,169 @@ pub struct PaperhandTaxApplied {
     pub pool: Pubkey,
     pub sol_out_before_tax: u64,
     pub cost_basis_for_sale: u64,
+    pub tax_mode: TaxMode,
+    /// Tokens sold beyond the seller's position, taxed by the untracked token policy
+    pub untracked_tokens: u64,
+    /// Realized loss on the tracked tokens (`cost_basis_for_sale` minus their proceeds) in lamports
+    pub loss: u64,
+    /// Realized loss as basis points of the cost basis
+    pub loss_bps: u16,
+    pub holding_seconds: i64,
+    pub tax_bps: u16,
     pub tax: u64,
+    /// Part of `tax` owed on the untracked tokens
+    pub untracked_tax: u64,
+    pub tax_to_treasury: u64,
+    /// Share kept in the curve's SOL reserve
+    pub tax_to_reserve: u64,
+    pub tax_to_creator: u64,
+    /// Share distributed to the pool's remaining holders as diamond-hand rewards
+    pub tax_to_rewards: u64,
     pub sol_to_user: u64,
 }
 
+/// Event emitted when a wallet's trades in a pool are bound to its referrer
+#[event]
+pub struct ReferralBound {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub referrer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralFeeAccrued {
+    pub referrer: Pubkey,
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct PositionUpdated {
     pub user: Pubkey,
     pub pool: Pubkey,
     pub total_tokens: u64,
     pub total_sol: u64,
+    pub realized_profit: u64,
+    pub realized_loss: u64,
+    pub total_tax_paid: u64,
+    pub buy_count: u32,
+    pub sell_count: u32,
+    pub first_trade_timestamp: i64,
+    pub last_trade_timestamp: i64,
+    pub total_sol_in: u64,
+    pub total_sol_out: u64,
 }
 
-pub fn swap(ctx: Context<Swap>, amount: u64, style: u64) -> Result<()> {
+impl PositionUpdated {
+    pub fn from_position(position: &UserPosition) -> Self {
+        Self {
+            user: position.owner,
+            pool: position.pool,
+            total_tokens: position.total_tokens,
+            total_sol: position.total_sol,
+            realized_profit: position.realized_profit,
+            realized_loss: position.realized_loss,
+            total_tax_paid: position.total_tax_paid,
+            buy_count: position.buy_count,
+            sell_count: position.sell_count,
+            first_trade_timestamp: position.first_trade_timestamp,
+            last_trade_timestamp: position.last_trade_timestamp,
+            total_sol_in: position.total_sol_in,
+            total_sol_out: position.total_sol_out,
+        }
+    }
+}
+
+pub fn swap<'info>(
+    ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+    amount: u64,
+    style: u64,
+) -> Result<()> {
     if amount <= 0 {
         return err!(CustomError::InvalidAmount);
     }
 
     let pool = &mut ctx.accounts.pool;
-    let config = &ctx.accounts.dex_configuration_account;
+    let config = &mut ctx.accounts.dex_configuration_account;
+
+    // Buys and sells are switched independently so holders can still exit
+    // while buying is halted
+    let pause_flag = if style == 1 { PAUSE_SELL } else { PAUSE_BUY };
+    if config.is_paused(pause_flag) {
+        return err!(CustomError::ProgramPaused);
+    }
+    if pool.is_paused(pause_flag) {
+        return err!(CustomError::PoolPaused);
+    }
     let position = &mut ctx.accounts.user_position;
     
     let clock = Clock::get()?;
 
+    // Freshly created position: record its pool, owner and layout version
+    if position.version == 0 {
+        position.set_inner(UserPosition::new(
+            pool.key(),
+            ctx.accounts.user.key(),
+            ctx.bumps.user_position,
+        ));
+    }
+
+    // Referrals are bound on the wallet's first trade in the pool and can't
+    // change afterwards, even if the position is closed and reopened
+    let referral = &mut ctx.accounts.referral;
+    let first_trade = referral.pool == Pubkey::default();
+    if first_trade {
+        referral.pool = pool.key();
+        referral.user = ctx.accounts.user.key();
+        referral.bump = ctx.bumps.referral;
+    }
+    let referrer_key = ctx.accounts.referrer.as_ref().map(|referrer| referrer.key());
+    if referral.bind(referrer_key, first_trade)? {
+        if let Some(referrer) = ctx.accounts.referrer.as_deref_mut() {
+            referrer.referred_positions = referrer.referred_positions
+                .checked_add(1)
+                .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
+        }
+
+        emit!(ReferralBound {
+            user: ctx.accounts.user.key(),
+            pool: pool.key(),
+            referrer: referral.referrer,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    let stats = &mut ctx.accounts.pool_stats;
+    if stats.pool == Pubkey::default() {
+        stats.pool = pool.key();
+        stats.bump = ctx.bumps.pool_stats;
+    }
+
+    // FIFO pools keep the position's buys as lots
+    let mut lots = match pool.cost_basis_mode {
+        CostBasisMode::Fifo => {
+            let lots: &mut PositionLots = ctx
+                .accounts
+                .position_lots
+                .as_deref_mut()
+                .ok_or(CustomError::PositionLotsRequired)?;
+            if lots.position == Pubkey::default() {
+                lots.position = position.key();
+                lots.bump = ctx.bumps.position_lots.ok_or(CustomError::PositionLotsRequired)?;
+            }
+            Some(lots)
+        }
+        CostBasisMode::AverageCost => None,
+    };
+
     msg!("Mint: {:?} ", ctx.accounts.mint_token_one.key());
     msg!("Swap: {:?} {:?} {:?}", ctx.accounts.user.key(), style, amount);
 
     // Compute fee-adjusted amount
-    let adjusted_amount_in_float = convert_to_float(amount, ctx.accounts.mint_token_one.decimals)
-        .div(100_f64)
-        .mul(100_f64.sub(config.fees));
-    let adjusted_amount = convert_from_float(adjusted_amount_in_float, ctx.accounts.mint_token_one.decimals);
+    let adjusted_amount = config.amount_after_fee(amount)?;
 
     if style == 1 {
         // SELL: User sells tokens for SOL
@@ -76,27 +213,86 @@ pub fn swap(ctx: Context<Swap>, amount: u64, style: u64) -> Result<()> {
         let amount_out_in_float = convert_to_float(pool.reserve_two, 9_u8).div(div_amt);
         let sol_out_before_tax = convert_from_float(amount_out_in_float, 9_u8);
 
-        // Check if user has sufficient position to sell
-        if position.total_tokens < amount {
+        // Tokens beyond the position were acquired outside the curve; whether
+        // they can be sold depends on the untracked token policy
+        let tracked_amount = amount.min(position.total_tokens);
+        let untracked_amount = amount - tracked_amount;
+        let untracked_policy = config.untracked_token_policy;
+        if untracked_amount > 0 && untracked_policy == UntrackedTokenPolicy::Reject {
             return err!(CustomError::InsufficientPosition);
         }
 
-        // Calculate cost basis for this sale
-        let cost_basis_for_sale = position.calculate_cost_basis_for_sale(amount)?;
+        // Split the proceeds between the tracked and untracked parts
+        let untracked_sol_out = ((sol_out_before_tax as u128) * (untracked_amount as u128)
+            / (amount as u128)) as u64;
+        let tracked_sol_out = sol_out_before_tax - untracked_sol_out;
+
+        // Calculate cost basis for the tracked part of this sale
+        let cost_basis_for_sale =
+            position.calculate_cost_basis_for_sale(tracked_amount, lots.as_deref())?;
+        let holding_seconds = match lots.as_deref() {
+            // Holding time of the lots being sold
+            Some(lots) => UserPosition::holding_seconds_since(
+                lots.preview_sale(tracked_amount)?.1,
+                clock.unix_timestamp,
+            ),
+            None => position.holding_seconds(clock.unix_timestamp),
+        };
 
-        // Determine if this is a loss (sol_out < cost_basis)
-        let (sol_to_user, tax_amount) = if sol_out_before_tax < cost_basis_for_sale {
-            // It's a loss! Apply PaperHandBitchTax
-            // tax = sol_out_before_tax * paperhand_tax_bps / 10000
-            let tax = (sol_out_before_tax as u128)
-                .checked_mul(config.paperhand_tax_bps as u128)
-                .ok_or(CustomError::MathOverflow)?
-                .checked_div(10000_u128)
-                .ok_or(CustomError::MathOverflow)? as u64;
+        // Update user position first: rewards settle and the sold tokens stop
+        // earning before this sale's tax is distributed to holders
+        position.record_sell(
+            pool,
+            tracked_amount,
+            cost_basis_for_sale,
+            tracked_sol_out,
+            untracked_sol_out,
+            clock.unix_timestamp,
+        )?;
+        if let Some(lots) = lots.as_mut() {
+            lots.consume(tracked_amount)?;
+        }
+
+        // Determine if this is a loss (sol_out < cost_basis), or untracked
+        // tokens count as one
+        let is_loss = tracked_sol_out < cost_basis_for_sale
+            || (untracked_amount > 0 && untracked_policy == UntrackedTokenPolicy::FullLoss);
+        let (sol_to_user, tax_to_treasury, tax_to_reserve, tax_to_creator) = if is_loss {
+            // It's a loss! Apply PaperHandBitchTax using the configured formula,
+            // at a rate decaying with holding time
+            let base_tax_bps = pool.base_tax_bps(config);
+            let tax_bps = config.paperhand_tax_bps_for(base_tax_bps, holding_seconds);
+            let tracked_tax = config.paperhand_tax(tax_bps, tracked_sol_out, cost_basis_for_sale)?;
+            // Untracked tokens have no entry time, so they count as just bought
+            let untracked_tax = config.untracked_tax(
+                config.paperhand_tax_bps_for(base_tax_bps, 0),
+                untracked_sol_out,
+            )?;
+            let tax = tracked_tax
+                .checked_add(untracked_tax)
+                .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
+
+            let (tax_to_treasury, tax_to_reserve, tax_to_creator, reward_share) =
+                config.split_tax(tax, pool.has_creator())?;
+
+            // Rewards stay in the global vault until claimed; whatever can't be
+            // distributed (no holders left, rounding) goes to the treasury
+            let tax_to_rewards = pool.distribute_rewards(reward_share)?;
+            let tax_to_treasury = tax_to_treasury
+                .checked_add(reward_share - tax_to_rewards)
+                .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
+
+            let loss = cost_basis_for_sale.saturating_sub(tracked_sol_out);
+            let loss_bps = if cost_basis_for_sale == 0 {
+                0
+            } else {
+                ((loss as u128) * 10000 / (cost_basis_for_sale as u128)) as u16
+            };
 
             let user_receives = sol_out_before_tax
                 .checked_sub(tax)
                 .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
+            position.record_tax_paid(tax)?;
 
             msg!("PaperHandBitchTax applied! Cost basis: {}, SOL out: {}, Tax: {}", 
                 cost_basis_for_sale, sol_out_before_tax, tax);
@@ -107,22 +303,36 @@ pub fn swap(ctx: Context<Swap>, amount: u64, style: u64) -> Result<()> {
                 pool: pool.key(),
                 sol_out_before_tax,
                 cost_basis_for_sale,
+                tax_mode: config.tax_mode,
+                untracked_tokens: untracked_amount,
+                loss,
+                loss_bps,
+                holding_seconds,
+                tax_bps,
                 tax,
+                untracked_tax,
+                tax_to_treasury,
+                tax_to_reserve,
+                tax_to_creator,
+                tax_to_rewards,
                 sol_to_user: user_receives,
             });
 
-            (user_receives, tax)
+            (user_receives, tax_to_treasury, tax_to_reserve, tax_to_creator)
         } else {
             // No loss, no tax
-            (sol_out_before_tax, 0_u64)
+            (sol_out_before_tax, 0_u64, 0_u64, 0_u64)
         };
 
         // Update pool reserves
         let new_reserves_one = pool.reserve_one
             .checked_add(amount)
             .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
+        // The retained share of the tax never leaves the global vault, so it
+        // goes back into the SOL reserve
         let new_reserves_two = pool.reserve_two
             .checked_sub(sol_out_before_tax)
+            .and_then(|reserve| reserve.checked_add(tax_to_reserve))
             .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
         
         pool.reserve_one = new_reserves_one;
@@ -130,17 +340,30 @@ pub fn swap(ctx: Context<Swap>, amount: u64, style: u64) -> Result<()> {
 
         msg!("Reserves: {:?} {:?}", new_reserves_one, new_reserves_two);
 
+        let tax = sol_out_before_tax - sol_to_user;
+        stats.record_sell(sol_out_before_tax, amount, tax)?;
+        // The sell fee stays in the pool as tokens, so referrers earn on buys only
+        if let Some(referrer) = ctx.accounts.referrer.as_deref_mut() {
+            referrer.record_trade(sol_out_before_tax, 0)?;
+        }
+        stats.update_ath(pool, clock.slot);
+
+        let mut global_stats = ctx.accounts.global_stats.load_mut()?;
+        global_stats.record_trade(sol_out_before_tax, tax)?;
+        global_stats.update_ranking(pool.token_one, pool.market_cap());
+        drop(global_stats);
+
         // Transfer tokens from user to pool
-        token::transfer(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                token::Transfer {
-                    from: ctx.accounts.user_token_account_one.to_account_info(),
-                    to: ctx.accounts.pool_token_account_one.to_account_info(),
-                    authority: ctx.accounts.user.to_account_info(),
-                },
-            ),
+        transfer_tokens(
+            &ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.user_token_account_one.to_account_info(),
+            ctx.accounts.mint_token_one.to_account_info(),
+            ctx.accounts.pool_token_account_one.to_account_info(),
+            ctx.accounts.user.to_account_info(),
+            ctx.remaining_accounts,
             amount,
+            ctx.accounts.mint_token_one.decimals,
+            &[],
         )?;
 
         // Transfer SOL from global account to user
@@ -156,8 +379,8 @@ pub fn swap(ctx: Context<Swap>, amount: u64, style: u64) -> Result<()> {
             sol_to_user,
         )?;
 
-        // If there's tax, transfer it to treasury
-        if tax_amount > 0 {
+        // Route the treasury and creator shares of the tax
+        if tax_to_treasury > 0 {
             system_program::transfer(
                 CpiContext::new_with_signer(
                     ctx.accounts.system_program.to_account_info(),
@@ -167,12 +390,33 @@ pub fn swap(ctx: Context<Swap>, amount: u64, style: u64) -> Result<()> {
                     },
                     &[&[b"global", &[ctx.bumps.global_account]]],
                 ),
-                tax_amount,
+                tax_to_treasury,
             )?;
+
+            // Part of the treasury's share is set aside to buy back this pool's token
+            let earmark = apply_bps(tax_to_treasury, config.buyback_bps)
+                .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
+            ctx.accounts.treasury_vault.earmark(pool, earmark)?;
         }
 
-        // Update user position
-        position.record_sell(amount, cost_basis_for_sale)?;
+        if tax_to_creator > 0 {
+            let creator = ctx
+                .accounts
+                .creator
+                .as_ref()
+                .ok_or(CustomError::InvalidCreator)?;
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.global_account.to_account_info(),
+                        to: creator.to_account_info(),
+                    },
+                    &[&[b"global", &[ctx.bumps.global_account]]],
+                ),
+                tax_to_creator,
+            )?;
+        }
 
         // Emit events
         emit!(TradeExecuted {
@@ -184,33 +428,62 @@ pub fn swap(ctx: Context<Swap>, amount: u64, style: u64) -> Result<()> {
             timestamp: clock.unix_timestamp,
         });
 
-        emit!(PositionUpdated {
-            user: ctx.accounts.user.key(),
-            pool: pool.key(),
-            total_tokens: position.total_tokens,
-            total_sol: position.total_sol,
-        });
+        emit!(PositionUpdated::from_position(position));
+
+        // Refund the rent of a position this sale emptied
+        if config.auto_close_positions && position.is_closable() {
+            ctx.accounts.user_position.close(ctx.accounts.user.to_account_info())?;
+            if let Some(lots) = ctx.accounts.position_lots.as_ref() {
+                lots.close(ctx.accounts.user.to_account_info())?;
+            }
+
+            emit!(PositionClosed {
+                user: ctx.accounts.user.key(),
+                pool: pool.key(),
+                timestamp: clock.unix_timestamp,
+            });
+        }
 
     } else {
         // BUY: User sends SOL to buy tokens
         // style == 2 (or any other) means user sends SOL and receives tokens
         
-        let denominator_sum = pool.reserve_two
-            .checked_add(adjusted_amount)
-            .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
+        let tokens_out = pool.buy_output(adjusted_amount, ctx.accounts.mint_token_one.decimals)?;
 
-        let div_amt = convert_to_float(denominator_sum, ctx.accounts.mint_token_one.decimals)
-            .div(convert_to_float(adjusted_amount, ctx.accounts.mint_token_one.decimals));
+        // The fee part of the SOL stays in the global vault as accrued protocol
+        // fees, less the referrer's share which is accrued on its account
+        let fee = amount
+            .checked_sub(adjusted_amount)
+            .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
+        let referral_fee = match ctx.accounts.referrer.as_deref_mut() {
+            Some(referrer) => {
+                let referral_fee = apply_bps(fee, config.referral_fee_bps)
+                    .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
+                referrer.record_trade(amount, referral_fee)?;
+                referral_fee
+            }
+            None => 0,
+        };
+        config.accrued_fees = config.accrued_fees
+            .checked_add(fee - referral_fee)
+            .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
 
-        let amount_out_in_float = convert_to_float(pool.reserve_one, 9_u8).div(div_amt);
-        let tokens_out = convert_from_float(amount_out_in_float, 9_u8);
+        if referral_fee > 0 {
+            emit!(ReferralFeeAccrued {
+                referrer: ctx.accounts.referral.referrer,
+                user: ctx.accounts.user.key(),
+                pool: pool.key(),
+                fee: referral_fee,
+                timestamp: clock.unix_timestamp,
+            });
+        }
 
         // Update reserves
         let new_reserves_one = pool.reserve_one
             .checked_sub(tokens_out)
             .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
         let new_reserves_two = pool.reserve_two
-            .checked_add(amount)
+            .checked_add(adjusted_amount)
             .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
 
         pool.reserve_one = new_reserves_one;
@@ -218,18 +491,26 @@ pub fn swap(ctx: Context<Swap>, amount: u64, style: u64) -> Result<()> {
 
         msg!("Reserves: {:?} {:?}", new_reserves_one, new_reserves_two);
 
+        let new_holder = position.total_tokens == 0 && tokens_out > 0;
+        stats.record_buy(amount, tokens_out, fee, new_holder)?;
+        stats.update_ath(pool, clock.slot);
+
+        let mut global_stats = ctx.accounts.global_stats.load_mut()?;
+        global_stats.record_trade(amount, 0)?;
+        global_stats.update_ranking(pool.token_one, pool.market_cap());
+        drop(global_stats);
+
         // Transfer tokens from pool to user
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                token::Transfer {
-                    from: ctx.accounts.pool_token_account_one.to_account_info(),
-                    to: ctx.accounts.user_token_account_one.to_account_info(),
-                    authority: ctx.accounts.global_account.to_account_info(),
-                },
-                &[&[b"global", &[ctx.bumps.global_account]]],
-            ),
+        transfer_tokens(
+            &ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.pool_token_account_one.to_account_info(),
+            ctx.accounts.mint_token_one.to_account_info(),
+            ctx.accounts.user_token_account_one.to_account_info(),
+            ctx.accounts.global_account.to_account_info(),
+            ctx.remaining_accounts,
             tokens_out,
+            ctx.accounts.mint_token_one.decimals,
+            &[&[b"global", &[ctx.bumps.global_account]]],
         )?;
 
         // Transfer SOL from user to global account
@@ -246,7 +527,10 @@ pub fn swap(ctx: Context<Swap>, amount: u64, style: u64) -> Result<()> {
 
         // Update user position: record the buy
         // We track the actual SOL spent (before fees go elsewhere, this is the user's cost)
-        position.record_buy(tokens_out, amount)?;
+        position.record_buy(pool, tokens_out, amount, clock.unix_timestamp)?;
+        if let Some(lots) = lots.as_mut() {
+            lots.push(tokens_out, amount, clock.unix_timestamp)?;
+        }
 
         // Emit events
         emit!(TradeExecuted {
@@ -258,12 +542,7 @@ pub fn swap(ctx: Context<Swap>, amount: u64, style: u64) -> Result<()> {
             timestamp: clock.unix_timestamp,
         });
 
-        emit!(PositionUpdated {
-            user: ctx.accounts.user.key(),
-            pool: pool.key(),
-            total_tokens: position.total_tokens,
-            total_sol: position.total_sol,
-        });
+        emit!(PositionUpdated::from_position(position));
     }
 
     Ok(())
@@ -293,12 +572,40 @@ pub struct Swap<'info> {
     )]
     pub global_account: AccountInfo<'info>,
 
-    /// CHECK: Treasury account that receives paperhand taxes
+    /// Program-owned vault that receives paperhand taxes
     #[account(
         mut,
-        constraint = treasury_vault.key() == dex_configuration_account.treasury
+        seeds = [TreasuryVault::SEED.as_bytes()],
+        bump = treasury_vault.bump,
+        constraint = treasury_vault.key() == dex_configuration_account.treasury @ CustomError::InvalidTreasury,
+    )]
+    pub treasury_vault: Box<Account<'info, TreasuryVault>>,
+
+    /// CHECK: Pool creator that receives its share of paperhand taxes
+    #[account(
+        mut,
+        constraint = creator.key() == pool.creator @ CustomError::InvalidCreator
+    )]
+    pub creator: Option<UncheckedAccount<'info>>,
+
+    /// Referrer of the trader, bound to its referral on the first trade
+    #[account(
+        mut,
+        seeds = [Referrer::SEED_PREFIX.as_bytes(), referrer.owner.as_ref()],
+        bump = referrer.bump,
+        constraint = referrer.owner != user.key() @ CustomError::InvalidReferrer,
+    )]
+    pub referrer: Option<Box<Account<'info, Referrer>>>,
+
+    /// The trader's referral binding in this pool (init_if_needed on the first trade)
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = Referral::ACCOUNT_SIZE,
+        seeds = [Referral::SEED_PREFIX.as_bytes(), pool.key().as_ref(), user.key().as_ref()],
+        bump,
     )]
-    pub treasury_vault: AccountInfo<'info>,
+    pub referral: Box<Account<'info, Referral>>,
 
     /// User position account for tracking cost basis (init_if_needed on first buy)
     #[account(
@@ -310,27 +617,56 @@ pub struct Swap<'info> {
     )]
     pub user_position: Box<Account<'info, UserPosition>>,
 
+    /// Cost-basis lots of the position, required for pools in FIFO mode
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = PositionLots::ACCOUNT_SIZE,
+        seeds = [PositionLots::SEED_PREFIX.as_bytes(), user_position.key().as_ref()],
+        bump,
+    )]
+    pub position_lots: Option<Box<Account<'info, PositionLots>>>,
+
+    /// Trading statistics of the pool (init_if_needed on the first trade)
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = PoolStats::ACCOUNT_SIZE,
+        seeds = [PoolStats::SEED_PREFIX.as_bytes(), pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_stats: Box<Account<'info, PoolStats>>,
+
+    #[account(
+        mut,
+        seeds = [GlobalStats::SEED.as_bytes()],
+        bump = global_stats.load()?.bump,
+    )]
+    pub global_stats: AccountLoader<'info, GlobalStats>,
+
     #[account(mut)]
-    pub mint_token_one: Box<Account<'info, Mint>>,
+    pub mint_token_one: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(
         mut,
         associated_token::mint = mint_token_one,
-        associated_token::authority = global_account
+        associated_token::authority = global_account,
+        associated_token::token_program = token_program,
     )]
-    pub pool_token_account_one: Box<Account<'info, TokenAccount>>,
+    pub pool_token_account_one: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         mut,
         associated_token::mint = mint_token_one,
         associated_token::authority = user,
+        associated_token::token_program = token_program,
     )]
-    pub user_token_account_one: Box<Account<'info, TokenAccount>>,
+    pub user_token_account_one: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(mut)]
     pub user: Signer<'info>,
     pub rent: Sysvar<'info, Rent>,
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
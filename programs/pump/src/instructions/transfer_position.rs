@@ -0,0 +1,215 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    errors::CustomError,
+    instructions::PositionUpdated,
+    state::{CostBasisMode, LiquidityPool, PoolStats, PositionLots, UserPosition},
+    utils::transfer_tokens,
+};
+
+/// Event emitted when tokens move between wallets together with their cost basis
+#[event]
+pub struct PositionTransferred {
+    pub pool: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub token_amount: u64,
+    /// SOL cost basis moved along with the tokens (in lamports)
+    pub cost_basis: u64,
+    pub timestamp: i64,
+}
+
+/// Move `amount` tokens to another wallet along with their share of the cost basis
+///
+/// The recipient's position keeps the tokens' entry time, so a transfer
+/// neither resets nor launders the paperhand tax.
+pub fn transfer_position<'info>(
+    ctx: Context<'_, '_, '_, 'info, TransferPosition<'info>>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return err!(CustomError::InvalidAmount);
+    }
+
+    let pool = &mut ctx.accounts.pool;
+    let source = &mut ctx.accounts.source_position;
+    let destination = &mut ctx.accounts.destination_position;
+    let now = Clock::get()?.unix_timestamp;
+
+    if source.total_tokens < amount {
+        return err!(CustomError::InsufficientPosition);
+    }
+
+    // Freshly created position: record its pool, owner and layout version
+    if destination.version == 0 {
+        destination.set_inner(UserPosition::new(
+            pool.key(),
+            ctx.accounts.recipient.key(),
+            ctx.bumps.destination_position,
+        ));
+    }
+
+    let stats = &mut ctx.accounts.pool_stats;
+    if stats.pool == Pubkey::default() {
+        stats.pool = pool.key();
+        stats.bump = ctx.bumps.pool_stats;
+    }
+    if destination.total_tokens == 0 {
+        stats.record_new_holder();
+    }
+
+    let (cost_basis, entry_timestamp) = match pool.cost_basis_mode {
+        CostBasisMode::Fifo => {
+            let source_lots: &mut PositionLots = ctx
+                .accounts
+                .source_lots
+                .as_deref_mut()
+                .ok_or(CustomError::PositionLotsRequired)?;
+            let destination_lots: &mut PositionLots = ctx
+                .accounts
+                .destination_lots
+                .as_deref_mut()
+                .ok_or(CustomError::PositionLotsRequired)?;
+            if destination_lots.position == Pubkey::default() {
+                destination_lots.position = destination.key();
+                destination_lots.bump = ctx
+                    .bumps
+                    .destination_lots
+                    .ok_or(CustomError::PositionLotsRequired)?;
+            }
+
+            // The transferred part of the oldest lots arrives as a single lot
+            let (cost_basis, entry_timestamp) = source_lots.preview_sale(amount)?;
+            source_lots.consume(amount)?;
+            destination_lots.push(amount, cost_basis, entry_timestamp)?;
+            (cost_basis, entry_timestamp)
+        }
+        CostBasisMode::AverageCost => (
+            source.calculate_cost_basis_for_sale(amount, None)?,
+            source.entry_timestamp,
+        ),
+    };
+
+    // Rewards settle on both sides before the tokens change hands; the pool's
+    // reward_tokens is unchanged overall
+    source.remove_tokens(pool, amount, cost_basis)?;
+    // Unknown entry times count as just bought, as for the source position
+    let entry_timestamp = if entry_timestamp == 0 { now } else { entry_timestamp };
+    destination.add_tokens(pool, amount, cost_basis, entry_timestamp)?;
+
+    transfer_tokens(
+        &ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.owner_token_account.to_account_info(),
+        ctx.accounts.mint_token_one.to_account_info(),
+        ctx.accounts.recipient_token_account.to_account_info(),
+        ctx.accounts.owner.to_account_info(),
+        ctx.remaining_accounts,
+        amount,
+        ctx.accounts.mint_token_one.decimals,
+        &[],
+    )?;
+
+    emit!(PositionTransferred {
+        pool: pool.key(),
+        from: ctx.accounts.owner.key(),
+        to: ctx.accounts.recipient.key(),
+        token_amount: amount,
+        cost_basis,
+        timestamp: now,
+    });
+
+    emit!(PositionUpdated::from_position(source));
+    emit!(PositionUpdated::from_position(destination));
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct TransferPosition<'info> {
+    #[account(
+        mut,
+        seeds = [LiquidityPool::POOL_SEED_PREFIX.as_bytes(), mint_token_one.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    pub mint_token_one: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [UserPosition::SEED_PREFIX.as_bytes(), pool.key().as_ref(), owner.key().as_ref()],
+        bump = source_position.bump,
+        has_one = owner @ CustomError::Unauthorized,
+    )]
+    pub source_position: Box<Account<'info, UserPosition>>,
+
+    /// Recipient's position, created on the first transfer
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = UserPosition::ACCOUNT_SIZE,
+        seeds = [UserPosition::SEED_PREFIX.as_bytes(), pool.key().as_ref(), recipient.key().as_ref()],
+        bump,
+        constraint = destination_position.version == 0
+            || destination_position.owner == recipient.key() @ CustomError::Unauthorized,
+    )]
+    pub destination_position: Box<Account<'info, UserPosition>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = PoolStats::ACCOUNT_SIZE,
+        seeds = [PoolStats::SEED_PREFIX.as_bytes(), pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_stats: Box<Account<'info, PoolStats>>,
+
+    /// Lots of the source position, required for pools in FIFO mode
+    #[account(
+        mut,
+        seeds = [PositionLots::SEED_PREFIX.as_bytes(), source_position.key().as_ref()],
+        bump = source_lots.bump,
+    )]
+    pub source_lots: Option<Box<Account<'info, PositionLots>>>,
+
+    /// Lots of the recipient's position, required for pools in FIFO mode
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = PositionLots::ACCOUNT_SIZE,
+        seeds = [PositionLots::SEED_PREFIX.as_bytes(), destination_position.key().as_ref()],
+        bump,
+    )]
+    pub destination_lots: Option<Box<Account<'info, PositionLots>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_token_one,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint_token_one,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program,
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Wallet receiving the tokens; only used as the position and ATA owner
+    #[account(constraint = recipient.key() != owner.key() @ CustomError::InvalidRecipient)]
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -1,21 +1,20 @@
 use crate::{errors::CustomError, state::*};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
 
+/// Directly update the configuration
 pub fn update_configuration(
     ctx: Context<UpdateCurveConfiguration>,
     new_treasury: Pubkey,
-    new_fees: Option<f64>,
+    new_fee_bps: Option<u16>,
 ) -> Result<()> {
     let dex_config = &mut ctx.accounts.dex_configuration_account;
 
-    if let Some(fees) = new_fees {
-        if fees < 0_f64 || fees > 100_f64 {
-            return err!(CustomError::InvalidFee);
-        }
-        dex_config.fees = fees;
+    if let Some(fee_bps) = new_fee_bps {
+        dex_config.set_fee_bps(fee_bps)?;
     }
 
-    dex_config.treasury = new_treasury;
+    dex_config.set_treasury(new_treasury)?;
 
     Ok(())
 }
@@ -26,9 +25,19 @@ pub struct UpdateCurveConfiguration<'info> {
         mut,
         seeds = [CurveConfiguration::SEED.as_bytes()],
         bump,
+        constraint = !dex_configuration_account.has_council() @ CustomError::CouncilApprovalRequired,
     )]
     pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
 
+    /// Program data account holding the upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ CustomError::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
     #[account(mut)]
     pub admin: Signer<'info>,
 }
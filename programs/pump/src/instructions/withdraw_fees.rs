@@ -0,0 +1,79 @@
+use crate::{errors::CustomError, state::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::system_program;
+
+/// Event emitted when accrued protocol fees leave the global vault
+#[event]
+pub struct FeesWithdrawn {
+    pub amount: u64,
+    pub destination: Pubkey,
+    /// Fees still held in the global vault after this withdrawal
+    pub remaining_fees: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Pay `amount` lamports of the accrued protocol fees to `destination`
+pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+    let config = &mut ctx.accounts.dex_configuration_account;
+    config.take_accrued_fees(amount)?;
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.global_account.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+            },
+            &[&[b"global", &[ctx.bumps.global_account]]],
+        ),
+        amount,
+    )?;
+
+    emit!(FeesWithdrawn {
+        amount,
+        destination: ctx.accounts.destination.key(),
+        remaining_fees: config.accrued_fees,
+        authority: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(
+        mut,
+        seeds = [CurveConfiguration::SEED.as_bytes()],
+        bump,
+        constraint = !dex_configuration_account.has_council() @ CustomError::CouncilApprovalRequired,
+    )]
+    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+
+    /// CHECK: Global SOL vault PDA
+    #[account(
+        mut,
+        seeds = [b"global"],
+        bump,
+    )]
+    pub global_account: AccountInfo<'info>,
+
+    /// CHECK: Any account may receive the withdrawal
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// Program data account holding the upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ CustomError::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,70 @@
+use crate::{errors::CustomError, state::*};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+/// Event emitted when lamports leave the treasury vault
+#[event]
+pub struct TreasuryWithdrawn {
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub epoch: u64,
+    /// Total withdrawn during `epoch`, this withdrawal included
+    pub withdrawn_this_epoch: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Pay `amount` lamports from the treasury vault to `destination`
+pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let vault_info = ctx.accounts.treasury_vault.to_account_info();
+    let destination = ctx.accounts.destination.to_account_info();
+
+    let vault = &mut ctx.accounts.treasury_vault;
+    vault.withdraw(&vault_info, &destination, amount, clock.epoch)?;
+
+    emit!(TreasuryWithdrawn {
+        amount,
+        destination: destination.key(),
+        epoch: clock.epoch,
+        withdrawn_this_epoch: vault.withdrawn_this_epoch,
+        authority: ctx.accounts.admin.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(
+        seeds = [CurveConfiguration::SEED.as_bytes()],
+        bump,
+        constraint = !dex_configuration_account.has_council() @ CustomError::CouncilApprovalRequired,
+    )]
+    pub dex_configuration_account: Box<Account<'info, CurveConfiguration>>,
+
+    #[account(
+        mut,
+        seeds = [TreasuryVault::SEED.as_bytes()],
+        bump = treasury_vault.bump,
+        constraint = treasury_vault.key() == dex_configuration_account.treasury @ CustomError::InvalidTreasury,
+    )]
+    pub treasury_vault: Box<Account<'info, TreasuryVault>>,
+
+    /// CHECK: Any account may receive the withdrawal
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// Program data account holding the upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ CustomError::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
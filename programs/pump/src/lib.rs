@@ -7,6 +7,10 @@ pub mod state;
 pub mod utils;
 
 use crate::instructions::*;
+use crate::state::{
+    AdminAction, CostBasisMode, LossBracket, TaxBracket, TaxMode, UntrackedTokenPolicy,
+    VestingParams,
+};
 
 declare_id!("J3pvSaDxrBDX38nBG4CcTodGmkaFuRabVW6Erp712GF2");
 
@@ -16,15 +20,17 @@ pub mod pump {
 
     /// Initialize the bonding curve configuration with fee and PaperHandBitchTax settings
     /// 
+    /// Can only be called by the program upgrade authority.
+    /// 
     /// # Arguments
-    /// * `fee` - Trading fee percentage (0-100)
+    /// * `fee_bps` - Trading fee in basis points (100 = 1%)
     /// * `paperhand_tax_bps` - PaperHand tax in basis points (5000 = 50%)
     pub fn initialize(
         ctx: Context<InitializeCurveConfiguration>, 
-        fee: f64,
+        fee_bps: u16,
         paperhand_tax_bps: u16,
     ) -> Result<()> {
-        instructions::initialize(ctx, fee, paperhand_tax_bps)
+        instructions::initialize(ctx, fee_bps, paperhand_tax_bps)
     }
 
     // pub fn create_pool(ctx: Context<CreateLiquidityPool>) -> Result<()> {
@@ -56,7 +62,15 @@ pub mod pump {
     /// # PaperHandBitchTax
     /// When selling at a loss (SOL received < cost basis), a 50% tax is applied
     /// to the SOL proceeds and sent to the treasury vault.
-    pub fn swap(ctx: Context<Swap>, amount: u64, style: u64) -> Result<()> {
+    ///
+    /// Pools of Token-2022 launches take the transfer hook's accounts as
+    /// remaining accounts: the hook program, its validation account and the
+    /// accounts it lists.
+    pub fn swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+        amount: u64,
+        style: u64,
+    ) -> Result<()> {
         instructions::swap(ctx, amount, style)
     }
 
@@ -65,8 +79,16 @@ pub mod pump {
     /// Launch a new token with Paper Hand Tax enabled
     /// 
     /// Creates a new SPL token, sets Metaplex metadata, initializes the bonding
-    /// curve pool, and mints the initial supply.
-    /// 
+    /// curve pool, and mints the initial supply. The mint is the PDA
+    /// `["mint", creator, launch_index]`, where `launch_index` counts the
+    /// creator's previous launches, and a `LaunchRecord` under `["launch", mint]`
+    /// maps it back to the launch.
+    ///
+    /// Passing Token-2022 as the token program creates a mint whose transfer
+    /// hook moves cost basis on plain transfers; the hook program and its
+    /// extra account list are then required, and the pool must use average
+    /// cost.
+    ///
     /// # Arguments
     /// * `name` - Token name (max 32 chars)
     /// * `symbol` - Token symbol (max 10 chars)
@@ -74,23 +96,414 @@ pub mod pump {
     /// * `decimals` - Token decimals (typically 6 or 9)
     /// * `initial_supply` - Total supply to mint
     /// * `initial_sol_reserve` - Initial SOL for bonding curve
-    pub fn launch(
-        ctx: Context<Launch>,
+    /// * `paperhand_tax_bps` - Pool-specific PaperHand tax in basis points, within the
+    ///   configured min/max (None = use the global rate)
+    /// * `cost_basis_mode` - Average cost, or FIFO lots tracked in `PositionLots`
+    /// * `initial_buy_sol` - Lamports the creator spends buying its own token
+    ///   before anyone else can trade (None = no initial buy)
+    /// * `creator_vesting` - Part of the initial supply locked in a vesting
+    ///   escrow for the creator instead of the pool (None = no allocation)
+    #[allow(clippy::too_many_arguments)]
+    pub fn launch<'info>(
+        ctx: Context<'_, '_, '_, 'info, Launch<'info>>,
         name: String,
         symbol: String,
         uri: String,
         decimals: u8,
         initial_supply: u64,
         initial_sol_reserve: u64,
+        paperhand_tax_bps: Option<u16>,
+        cost_basis_mode: CostBasisMode,
+        initial_buy_sol: Option<u64>,
+        creator_vesting: Option<VestingParams>,
     ) -> Result<()> {
-        instructions::launch(ctx, name, symbol, uri, decimals, initial_supply, initial_sol_reserve)
+        instructions::launch(
+            ctx,
+            name,
+            symbol,
+            uri,
+            decimals,
+            initial_supply,
+            initial_sol_reserve,
+            paperhand_tax_bps,
+            cost_basis_mode,
+            initial_buy_sol,
+            creator_vesting,
+        )
     }
 
+    /// Update fees and treasury directly (upgrade authority, before a council exists)
     pub fn update_configuration(
         ctx: Context<UpdateCurveConfiguration>,
         new_treasury: Pubkey,
-        new_fees: Option<f64>,
+        new_fee_bps: Option<u16>,
+    ) -> Result<()> {
+        instructions::update_configuration(ctx, new_treasury, new_fee_bps)
+    }
+
+    /// Set the pause switches directly (upgrade authority before a council
+    /// exists; afterwards any council member, but only to turn switches on)
+    ///
+    /// # Arguments
+    /// * `pause_flags` - Bitflag of `PAUSE_LAUNCH`, `PAUSE_BUY`, `PAUSE_SELL`,
+    ///   `PAUSE_ADD_LIQUIDITY` and `PAUSE_REMOVE_LIQUIDITY`; applies to the
+    ///   `pool` account when passed, globally otherwise
+    pub fn set_pause(ctx: Context<SetPause>, pause_flags: u8) -> Result<()> {
+        instructions::set_pause(ctx, pause_flags)
+    }
+
+    /// Set the holding-time paperhand tax schedule directly (upgrade authority,
+    /// before a council exists)
+    ///
+    /// # Arguments
+    /// * `brackets` - Tax rates by minimum holding time, sorted by holding time
+    ///   (max 8); sales held for less than the first bracket pay `paperhand_tax_bps`
+    pub fn set_tax_schedule(
+        ctx: Context<SetTaxSchedule>,
+        brackets: Vec<TaxBracket>,
+    ) -> Result<()> {
+        instructions::set_tax_schedule(ctx, brackets)
+    }
+
+    /// Set the paperhand tax range creators may choose at launch directly
+    /// (upgrade authority, before a council exists)
+    ///
+    /// # Arguments
+    /// * `min_bps` - Lowest allowed tax in basis points
+    /// * `max_bps` - Highest allowed tax in basis points (max 10000)
+    pub fn set_tax_bounds(ctx: Context<SetTaxBounds>, min_bps: u16, max_bps: u16) -> Result<()> {
+        instructions::set_tax_bounds(ctx, min_bps, max_bps)
+    }
+
+    /// Switch the paperhand tax formula directly (upgrade authority, before a
+    /// council exists)
+    ///
+    /// # Arguments
+    /// * `tax_mode` - Flat on proceeds, percent of the realized loss, or progressive
+    ///   brackets by loss percentage
+    /// * `loss_brackets` - Marginal rates by loss in basis points of the cost basis,
+    ///   sorted (max 8, required for progressive mode)
+    pub fn set_tax_mode(
+        ctx: Context<SetTaxMode>,
+        tax_mode: TaxMode,
+        loss_brackets: Vec<LossBracket>,
+    ) -> Result<()> {
+        instructions::set_tax_mode(ctx, tax_mode, loss_brackets)
+    }
+
+    /// Set how sales of tokens acquired outside the bonding curve are handled
+    /// directly (upgrade authority, before a council exists)
+    ///
+    /// # Arguments
+    /// * `policy` - Reject them, treat them as zero cost basis (never taxed), or
+    ///   as a full loss (always taxed)
+    pub fn set_untracked_token_policy(
+        ctx: Context<SetUntrackedTokenPolicy>,
+        policy: UntrackedTokenPolicy,
+    ) -> Result<()> {
+        instructions::set_untracked_token_policy(ctx, policy)
+    }
+
+    /// Switch closing of positions emptied by a sell directly (upgrade
+    /// authority, before a council exists)
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether such sells close the position and refund its rent
+    pub fn set_auto_close_positions(
+        ctx: Context<SetAutoClosePositions>,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::set_auto_close_positions(ctx, enabled)
+    }
+
+    /// Set how the paperhand tax is split directly (upgrade authority, before a
+    /// council exists)
+    ///
+    /// # Arguments
+    /// * `treasury_bps` - Share sent to the treasury
+    /// * `reserve_bps` - Share kept in the curve's SOL reserve
+    /// * `creator_bps` - Share paid to the pool's creator
+    /// * `reward_bps` - Share distributed to the pool's holders as diamond-hand rewards
+    ///
+    /// The four shares must add up to 10000.
+    pub fn set_tax_split(
+        ctx: Context<SetTaxSplit>,
+        treasury_bps: u16,
+        reserve_bps: u16,
+        creator_bps: u16,
+        reward_bps: u16,
+    ) -> Result<()> {
+        instructions::set_tax_split(ctx, treasury_bps, reserve_bps, creator_bps, reward_bps)
+    }
+
+    /// Create the treasury vault for a configuration initialized before it
+    /// existed, and point the treasury at it
+    pub fn initialize_treasury_vault(ctx: Context<InitializeTreasuryVault>) -> Result<()> {
+        instructions::initialize_treasury_vault(ctx)
+    }
+
+    /// Withdraw from the treasury vault directly (upgrade authority, before a
+    /// council exists)
+    ///
+    /// # Arguments
+    /// * `amount` - Lamports to send to the `destination` account, within the
+    ///   per-epoch limit and above the vault's rent reserve
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        instructions::withdraw_treasury(ctx, amount)
+    }
+
+    /// Withdraw accrued protocol fees from the global vault directly (upgrade
+    /// authority, before a council exists)
+    ///
+    /// # Arguments
+    /// * `amount` - Lamports to send to the `destination` account, at most
+    ///   the accrued fees
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        instructions::withdraw_fees(ctx, amount)
+    }
+
+    /// Set the treasury's per-epoch withdrawal limit directly (upgrade
+    /// authority, before a council exists)
+    ///
+    /// # Arguments
+    /// * `limit` - Most lamports that may be withdrawn per epoch (0 = no limit)
+    pub fn set_treasury_withdrawal_limit(
+        ctx: Context<SetTreasuryWithdrawalLimit>,
+        limit: u64,
+    ) -> Result<()> {
+        instructions::set_treasury_withdrawal_limit(ctx, limit)
+    }
+
+    /// Set the buyback settings directly (upgrade authority, before a council
+    /// exists)
+    ///
+    /// # Arguments
+    /// * `buyback_bps` - Share of each pool's treasury tax earmarked for its buybacks
+    /// * `max_sol_per_call` - Most lamports one `buyback_and_burn` may spend (0 = disabled)
+    /// * `cooldown_seconds` - Minimum time between two buybacks of the same pool
+    pub fn set_buyback_config(
+        ctx: Context<SetBuybackConfig>,
+        buyback_bps: u16,
+        max_sol_per_call: u64,
+        cooldown_seconds: u32,
+    ) -> Result<()> {
+        instructions::set_buyback_config(ctx, buyback_bps, max_sol_per_call, cooldown_seconds)
+    }
+
+    /// Buy back a pool's token with the treasury SOL earmarked for it and burn it
+    ///
+    /// # Arguments
+    /// * `max_sol` - Most lamports to spend, further capped by the configuration
+    ///   and the pool's earmarked balance
+    pub fn buyback_and_burn(ctx: Context<BuybackAndBurn>, max_sol: u64) -> Result<()> {
+        instructions::buyback_and_burn(ctx, max_sol)
+    }
+
+    /// Set the referrers' share of the protocol fee directly (upgrade
+    /// authority, before a council exists)
+    ///
+    /// # Arguments
+    /// * `referral_fee_bps` - Share of each trade's protocol fee paid to the
+    ///   trader's referrer
+    pub fn set_referral_fee_bps(
+        ctx: Context<SetReferralFeeBps>,
+        referral_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::set_referral_fee_bps(ctx, referral_fee_bps)
+    }
+
+    /// Create the caller's referrer account
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        instructions::register_referrer(ctx)
+    }
+
+    /// Pay out the caller's accrued referral fees
+    pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+        instructions::claim_referral_fees(ctx)
+    }
+
+    /// Claim the unlocked part of the caller's creator allocation
+    ///
+    /// Takes the transfer hook's accounts as remaining accounts for Token-2022
+    /// launches, like `swap`.
+    pub fn claim_vested<'info>(ctx: Context<'_, '_, '_, 'info, ClaimVested<'info>>) -> Result<()> {
+        instructions::claim_vested(ctx)
+    }
+
+    /// Create the symbol registry consulted by `launch`
+    pub fn initialize_symbol_registry(ctx: Context<InitializeSymbolRegistry>) -> Result<()> {
+        instructions::initialize_symbol_registry(ctx)
+    }
+
+    /// Tune premium symbols and their auctions (registry moderator)
+    ///
+    /// # Arguments
+    /// * `premium_max_len` - Symbols up to this length must be won at auction (0 = none)
+    /// * `auction_duration_seconds` - How long an auction runs after its first bid
+    /// * `min_bid` - Smallest opening bid in lamports
+    /// * `min_bid_increment_bps` - How much a bid must beat the highest bid by
+    pub fn set_symbol_registry_config(
+        ctx: Context<SetSymbolRegistryConfig>,
+        premium_max_len: u8,
+        auction_duration_seconds: u32,
+        min_bid: u64,
+        min_bid_increment_bps: u16,
+    ) -> Result<()> {
+        instructions::set_symbol_registry_config(
+            ctx,
+            premium_max_len,
+            auction_duration_seconds,
+            min_bid,
+            min_bid_increment_bps,
+        )
+    }
+
+    /// Hand the symbol registry over to another moderator
+    ///
+    /// # Arguments
+    /// * `new_authority` - The new moderator
+    pub fn set_symbol_authority(
+        ctx: Context<SetSymbolAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::set_symbol_authority(ctx, new_authority)
+    }
+
+    /// Block or unblock a symbol (registry moderator)
+    ///
+    /// # Arguments
+    /// * `symbol` - The symbol in upper case
+    /// * `blocked` - Whether launches with this symbol are refused
+    pub fn set_symbol_blocked(
+        ctx: Context<SetSymbolBlocked>,
+        symbol: String,
+        blocked: bool,
+    ) -> Result<()> {
+        instructions::set_symbol_blocked(ctx, symbol, blocked)
+    }
+
+    /// Reserve a symbol for a wallet, or lift the reservation (registry moderator)
+    ///
+    /// # Arguments
+    /// * `symbol` - The symbol in upper case
+    /// * `holder` - Only wallet allowed to launch the symbol (default pubkey = lift)
+    /// * `expires_at` - Unix timestamp the reservation ends at (0 = no expiry)
+    pub fn reserve_symbol(
+        ctx: Context<ReserveSymbol>,
+        symbol: String,
+        holder: Pubkey,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::reserve_symbol(ctx, symbol, holder, expires_at)
+    }
+
+    /// Bid for a premium symbol; the first bid opens its auction
+    ///
+    /// # Arguments
+    /// * `symbol` - The symbol in upper case
+    /// * `amount` - Lamports escrowed until outbid or the auction is settled
+    pub fn bid_symbol(ctx: Context<BidSymbol>, symbol: String, amount: u64) -> Result<()> {
+        instructions::bid_symbol(ctx, symbol, amount)
+    }
+
+    /// Pay an ended auction's winning bid to the treasury and reserve the
+    /// symbol for the winner
+    ///
+    /// # Arguments
+    /// * `symbol` - The symbol in upper case
+    pub fn settle_symbol_auction(
+        ctx: Context<SettleSymbolAuction>,
+        symbol: String,
+    ) -> Result<()> {
+        instructions::settle_symbol_auction(ctx, symbol)
+    }
+
+    /// Create the protocol-wide statistics account read by `launch` and `swap`
+    pub fn initialize_global_stats(ctx: Context<InitializeGlobalStats>) -> Result<()> {
+        instructions::initialize_global_stats(ctx)
+    }
+
+    /// Pay out the caller's accumulated diamond-hand rewards for a pool
+    pub fn claim_diamond_rewards(ctx: Context<ClaimDiamondRewards>) -> Result<()> {
+        instructions::claim_diamond_rewards(ctx)
+    }
+
+    /// Close the caller's empty position in a pool and reclaim its rent
+    pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+        instructions::close_position(ctx)
+    }
+
+    /// Move tokens to another wallet along with their share of the cost basis,
+    /// creating the recipient's position if needed
+    ///
+    /// Takes the transfer hook's accounts as remaining accounts for Token-2022
+    /// launches, like `swap`. Plain transfers of those tokens carry the cost
+    /// basis too (see `record_hook_transfer`).
+    ///
+    /// # Arguments
+    /// * `amount` - Number of tokens to transfer
+    pub fn transfer_position<'info>(
+        ctx: Context<'_, '_, '_, 'info, TransferPosition<'info>>,
+        amount: u64,
     ) -> Result<()> {
-        instructions::update_configuration(ctx, new_treasury, new_fees)
+        instructions::transfer_position(ctx, amount)
+    }
+
+    /// Move cost basis along with a Token-2022 transfer made outside this
+    /// program (transfer hook only)
+    ///
+    /// # Arguments
+    /// * `amount` - Number of tokens transferred
+    pub fn record_hook_transfer(ctx: Context<RecordHookTransfer>, amount: u64) -> Result<()> {
+        instructions::record_hook_transfer(ctx, amount)
+    }
+
+    /// Convert a legacy CurveConfiguration (f64 fee percent) to the current
+    /// versioned layout, reallocating it in place (upgrade authority only)
+    pub fn migrate_configuration(ctx: Context<MigrateConfiguration>) -> Result<()> {
+        instructions::migrate_configuration(ctx)
+    }
+
+    /// Reallocate a legacy LiquidityPool to the current versioned layout
+    /// (permissionless, caller pays the extra rent)
+    pub fn migrate_pool(ctx: Context<MigratePool>) -> Result<()> {
+        instructions::migrate_pool(ctx)
+    }
+
+    /// Reallocate a legacy UserPosition to the current versioned layout
+    /// (permissionless, caller pays the extra rent)
+    pub fn migrate_position(ctx: Context<MigratePosition>) -> Result<()> {
+        instructions::migrate_position(ctx)
+    }
+
+    /// Install the native M-of-N admin council
+    ///
+    /// # Arguments
+    /// * `members` - Council member pubkeys (max 10)
+    /// * `threshold` - Approvals required to execute a proposal
+    pub fn create_admin_council(
+        ctx: Context<CreateAdminCouncil>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::create_admin_council(ctx, members, threshold)
+    }
+
+    /// Propose an admin action (fee change, treasury change, pause, fee withdrawal)
+    pub fn propose_admin_action(
+        ctx: Context<ProposeAdminAction>,
+        action: AdminAction,
+    ) -> Result<()> {
+        instructions::propose_admin_action(ctx, action)
+    }
+
+    /// Approve a pending admin proposal as a council member
+    pub fn approve_admin_action(ctx: Context<ApproveAdminAction>) -> Result<()> {
+        instructions::approve_admin_action(ctx)
+    }
+
+    /// Apply an admin proposal once the approval threshold is reached
+    pub fn execute_admin_action(ctx: Context<ExecuteAdminAction>) -> Result<()> {
+        instructions::execute_admin_action(ctx)
     }
 }
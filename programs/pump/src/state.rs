@@ -1,4 +1,9 @@
+use crate::consts::{
+    MAX_COUNCIL_MEMBERS, MAX_LOSS_BRACKETS, MAX_POSITION_LOTS, MAX_RANKED_POOLS, MAX_SYMBOL_LEN,
+    MAX_TAX_BRACKETS, PAUSE_ALL, PAUSE_LAUNCH, PRICE_PRECISION, REWARD_PRECISION,
+};
 use crate::errors::CustomError;
+use crate::utils::apply_bps;
 use crate::utils::convert_from_float;
 use crate::utils::convert_to_float;
 use anchor_lang::prelude::*;
@@ -7,30 +12,711 @@ use anchor_spl::token::{self, Mint, Token, TokenAccount};
 use std::cmp;
 use std::ops::Div;
 use std::ops::Mul;
-use std::ops::Sub;
 
 #[account]
 pub struct CurveConfiguration {
-    pub fees: f64,
+    /// Account layout version (see `CurveConfiguration::VERSION`)
+    pub version: u8,
+    /// Trading fee in basis points (e.g., 100 = 1%)
+    pub fee_bps: u16,
     /// Treasury wallet that receives the PaperHand tax
     pub treasury: Pubkey,
     /// Tax rate in basis points (e.g., 5000 = 50%)
     pub paperhand_tax_bps: u16,
+    /// AdminCouncil that must approve admin actions (default = no council yet)
+    pub admin_council: Pubkey,
+    /// Global pause switches (see `PAUSE_*` in consts)
+    pub pause_flags: u8,
+    /// Protocol fees collected on buys, held in the global vault (in lamports)
+    pub accrued_fees: u64,
+    /// Number of populated entries in `tax_brackets`
+    pub tax_bracket_count: u8,
+    /// Paperhand tax schedule by holding time, sorted by `min_hold_seconds`
+    pub tax_brackets: [TaxBracket; MAX_TAX_BRACKETS],
+    /// Lowest paperhand tax a creator may choose at launch (in basis points)
+    pub min_paperhand_tax_bps: u16,
+    /// Highest paperhand tax a creator may choose at launch (in basis points,
+    /// 0 = bounds never set, any rate is allowed)
+    pub max_paperhand_tax_bps: u16,
+    /// How the paperhand tax is computed on a loss-making sale
+    pub tax_mode: TaxMode,
+    /// Number of populated entries in `loss_brackets`
+    pub loss_bracket_count: u8,
+    /// Marginal brackets for `TaxMode::ProgressiveByLoss`, sorted by `min_loss_bps`
+    pub loss_brackets: [LossBracket; MAX_LOSS_BRACKETS],
+    /// Share of the paperhand tax sent to the treasury (in basis points)
+    pub tax_treasury_bps: u16,
+    /// Share of the paperhand tax kept in the curve's SOL reserve (in basis points)
+    pub tax_reserve_bps: u16,
+    /// Share of the paperhand tax paid to the pool's creator (in basis points)
+    pub tax_creator_bps: u16,
+    /// Share of the paperhand tax paid out to the pool's remaining holders (in basis points)
+    pub tax_reward_bps: u16,
+    /// How sales of tokens not bought through the curve are handled
+    pub untracked_token_policy: UntrackedTokenPolicy,
+    /// Whether a sell that empties a position closes it and refunds its rent
+    pub auto_close_positions: bool,
+    /// Share of a pool's treasury tax earmarked for buying back and burning
+    /// that pool's token (in basis points)
+    pub buyback_bps: u16,
+    /// Most lamports a single `buyback_and_burn` call may spend (0 = buybacks disabled)
+    pub buyback_max_sol_per_call: u64,
+    /// Minimum time between two buybacks of the same pool
+    pub buyback_cooldown_seconds: u32,
+    /// Share of the protocol fee paid to the trader's referrer (in basis points)
+    pub referral_fee_bps: u16,
+    /// Spare space so new fields don't require another realloc. The later
+    /// accounts keep a `reserved` tail for the same reason.
+    pub reserved: [u8; 143],
+}
+
+/// How the paperhand tax is computed on a loss-making sale
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TaxMode {
+    /// The tax rate applies to all of the sale proceeds
+    #[default]
+    FlatOnProceeds,
+    /// The tax rate applies to the realized loss (`cost_basis - sol_out`)
+    PercentOfLoss,
+    /// Each slice of the realized loss is taxed at the rate of its loss bracket
+    ProgressiveByLoss,
+}
+
+/// How the sell path treats tokens missing from the seller's position, e.g.
+/// received by airdrop, transfer or bought on another DEX
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UntrackedTokenPolicy {
+    /// Only tracked tokens can be sold
+    #[default]
+    Reject,
+    /// Untracked tokens cost nothing, so they are never taxed
+    ZeroCostBasis,
+    /// Untracked tokens are a total loss, so they are always taxed
+    FullLoss,
+}
+
+/// Tax rate for the part of a loss beyond `min_loss_bps` of the cost basis
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LossBracket {
+    pub min_loss_bps: u16,
+    pub tax_bps: u16,
+}
+
+/// Paperhand tax rate applied once tokens have been held for `min_hold_seconds`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TaxBracket {
+    pub min_hold_seconds: u32,
+    pub tax_bps: u16,
 }
 
 impl CurveConfiguration {
     pub const SEED: &'static str = "CurveConfiguration";
     pub const TREASURY_VAULT_SEED: &'static str = "treasury_vault";
+    pub const VERSION: u8 = 1;
 
-    // Discriminator (8) + f64 (8) + Pubkey (32) + u16 (2) + padding (6)
-    pub const ACCOUNT_SIZE: usize = 8 + 8 + 32 + 2 + 6;
+    // Discriminator (8) + u8 (1) + u16 (2) + Pubkey (32) + u16 (2) + Pubkey (32)
+    // + u8 (1) + u64 (8) + u8 (1) + TaxBracket (6) * MAX_TAX_BRACKETS + u16 (2) + u16 (2)
+    // + TaxMode (1) + u8 (1) + LossBracket (4) * MAX_LOSS_BRACKETS + u16 (2) * 4
+    // + UntrackedTokenPolicy (1) + bool (1) + u16 (2) + u64 (8) + u32 (4) + u16 (2)
+    // + reserved (143)
+    pub const ACCOUNT_SIZE: usize = 8 + 1 + 2 + 32 + 2 + 32 + 1 + 8 + 1 + 6 * MAX_TAX_BRACKETS
+        + 2 + 2 + 1 + 1 + 4 * MAX_LOSS_BRACKETS + 2 * 4 + 1 + 1 + 2 + 8 + 4 + 2 + 143;
 
-    pub fn new(fees: f64, treasury: Pubkey, paperhand_tax_bps: u16) -> Self {
+    // Legacy (v0) layout: Discriminator (8) + f64 (8) + Pubkey (32) + u16 (2), then
+    // optionally Pubkey (32) + bool (1) + u64 (8) for the admin council fields
+    const LEGACY_ADMIN_FIELDS_END: usize = 8 + 8 + 32 + 2 + 32 + 1 + 8;
+
+    pub fn new(fee_bps: u16, treasury: Pubkey, paperhand_tax_bps: u16) -> Self {
         Self { 
-            fees, 
+            version: CurveConfiguration::VERSION,
+            fee_bps, 
             treasury,
             paperhand_tax_bps,
+            admin_council: Pubkey::default(),
+            pause_flags: 0,
+            accrued_fees: 0,
+            tax_bracket_count: 0,
+            tax_brackets: [TaxBracket::default(); MAX_TAX_BRACKETS],
+            min_paperhand_tax_bps: 0,
+            max_paperhand_tax_bps: 10000,
+            tax_mode: TaxMode::FlatOnProceeds,
+            loss_bracket_count: 0,
+            loss_brackets: [LossBracket::default(); MAX_LOSS_BRACKETS],
+            tax_treasury_bps: 10000,
+            tax_reserve_bps: 0,
+            tax_creator_bps: 0,
+            tax_reward_bps: 0,
+            untracked_token_policy: UntrackedTokenPolicy::Reject,
+            auto_close_positions: false,
+            buyback_bps: 0,
+            buyback_max_sol_per_call: 0,
+            buyback_cooldown_seconds: 0,
+            referral_fee_bps: 0,
+            reserved: [0; 143],
+        }
+    }
+
+    /// Decode a pre-versioning account, where `fees` was an f64 percent
+    pub fn from_legacy(data: &[u8]) -> Result<Self> {
+        let mut body = data.get(8..).ok_or(CustomError::InvalidAccountData)?;
+
+        let fees = f64::deserialize(&mut body)?;
+        let treasury = Pubkey::deserialize(&mut body)?;
+        let paperhand_tax_bps = u16::deserialize(&mut body)?;
+
+        if !(0_f64..=100_f64).contains(&fees) {
+            return err!(CustomError::InvalidFee);
+        }
+
+        // Percent -> basis points, rounded to the nearest bps
+        let fee_bps = (fees * 100_f64 + 0.5_f64) as u16;
+
+        let mut config = Self::new(fee_bps, treasury, paperhand_tax_bps);
+
+        if data.len() >= CurveConfiguration::LEGACY_ADMIN_FIELDS_END {
+            config.admin_council = Pubkey::deserialize(&mut body)?;
+            // The legacy switch stopped everything
+            if bool::deserialize(&mut body)? {
+                config.pause_flags = PAUSE_ALL;
+            }
+            config.accrued_fees = u64::deserialize(&mut body)?;
+        }
+
+        Ok(config)
+    }
+
+    /// Whether admin changes need council approval
+    ///
+    /// Until a council is installed the direct admin setters are gated on the
+    /// upgrade authority. Afterwards they fail with `CouncilApprovalRequired`
+    /// and the same changes go through `propose_admin_action`.
+    pub fn has_council(&self) -> bool {
+        self.admin_council != Pubkey::default()
+    }
+
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.pause_flags & flag != 0
+    }
+
+    pub fn set_pause_flags(&mut self, pause_flags: u8) -> Result<()> {
+        if pause_flags & !PAUSE_ALL != 0 {
+            return err!(CustomError::InvalidPauseFlags);
+        }
+        self.pause_flags = pause_flags;
+        Ok(())
+    }
+
+    /// Point the PaperHand tax at the treasury vault; it is the only valid
+    /// treasury since taxes are held by the program
+    pub fn set_treasury(&mut self, treasury: Pubkey) -> Result<()> {
+        if treasury != TreasuryVault::address() {
+            return err!(CustomError::InvalidTreasury);
+        }
+        self.treasury = treasury;
+        Ok(())
+    }
+
+    pub fn set_fee_bps(&mut self, fee_bps: u16) -> Result<()> {
+        if fee_bps > 10000 {
+            return err!(CustomError::InvalidFee);
+        }
+        self.fee_bps = fee_bps;
+        Ok(())
+    }
+
+    /// Deduct `amount` from the accrued protocol fees before it leaves the
+    /// global vault
+    pub fn take_accrued_fees(&mut self, amount: u64) -> Result<()> {
+        if amount == 0 || amount > self.accrued_fees {
+            return err!(CustomError::InsufficientFunds);
+        }
+        self.accrued_fees -= amount;
+        Ok(())
+    }
+
+    /// Replace the holding-time tax schedule
+    ///
+    /// Brackets must be sorted by strictly increasing `min_hold_seconds`. Sales of
+    /// tokens held for less than the first bracket pay `paperhand_tax_bps`.
+    pub fn set_tax_schedule(&mut self, brackets: &[TaxBracket]) -> Result<()> {
+        if brackets.len() > MAX_TAX_BRACKETS
+            || brackets
+                .windows(2)
+                .any(|pair| pair[0].min_hold_seconds >= pair[1].min_hold_seconds)
+        {
+            return err!(CustomError::InvalidTaxSchedule);
+        }
+        if brackets.iter().any(|bracket| bracket.tax_bps > 10000) {
+            return err!(CustomError::InvalidTaxBps);
+        }
+
+        self.tax_brackets = [TaxBracket::default(); MAX_TAX_BRACKETS];
+        self.tax_brackets[..brackets.len()].copy_from_slice(brackets);
+        self.tax_bracket_count = brackets.len() as u8;
+        Ok(())
+    }
+
+    /// Paperhand tax rate for tokens held for `holding_seconds`, starting from
+    /// the pool's `base_tax_bps`
+    ///
+    /// The schedule only ever lowers the rate, so a pool with a lower rate than
+    /// a bracket keeps its own.
+    pub fn paperhand_tax_bps_for(&self, base_tax_bps: u16, holding_seconds: i64) -> u16 {
+        self.tax_brackets[..self.tax_bracket_count as usize]
+            .iter()
+            .rev()
+            .find(|bracket| holding_seconds >= bracket.min_hold_seconds as i64)
+            .map_or(base_tax_bps, |bracket| bracket.tax_bps.min(base_tax_bps))
+    }
+
+    pub fn set_tax_bounds(&mut self, min_bps: u16, max_bps: u16) -> Result<()> {
+        // A zero maximum would read as unset
+        if min_bps > max_bps || max_bps == 0 || max_bps > 10000 {
+            return err!(CustomError::InvalidTaxBps);
+        }
+        self.min_paperhand_tax_bps = min_bps;
+        self.max_paperhand_tax_bps = max_bps;
+        Ok(())
+    }
+
+    /// Configs versioned before the bounds existed read 0/0, which leaves
+    /// launch-time rates unrestricted
+    pub fn is_tax_within_bounds(&self, tax_bps: u16) -> bool {
+        if self.max_paperhand_tax_bps == 0 {
+            return tax_bps <= 10000;
+        }
+        (self.min_paperhand_tax_bps..=self.max_paperhand_tax_bps).contains(&tax_bps)
+    }
+
+    /// Switch the tax formula
+    ///
+    /// `loss_brackets` are only used by `TaxMode::ProgressiveByLoss`, which needs
+    /// at least one; they must be sorted by strictly increasing `min_loss_bps`.
+    pub fn set_tax_mode(&mut self, tax_mode: TaxMode, loss_brackets: &[LossBracket]) -> Result<()> {
+        if loss_brackets.len() > MAX_LOSS_BRACKETS
+            || (tax_mode == TaxMode::ProgressiveByLoss && loss_brackets.is_empty())
+            || loss_brackets
+                .windows(2)
+                .any(|pair| pair[0].min_loss_bps >= pair[1].min_loss_bps)
+        {
+            return err!(CustomError::InvalidTaxSchedule);
+        }
+        if loss_brackets
+            .iter()
+            .any(|bracket| bracket.min_loss_bps > 10000 || bracket.tax_bps > 10000)
+        {
+            return err!(CustomError::InvalidTaxBps);
+        }
+
+        self.tax_mode = tax_mode;
+        self.loss_brackets = [LossBracket::default(); MAX_LOSS_BRACKETS];
+        self.loss_brackets[..loss_brackets.len()].copy_from_slice(loss_brackets);
+        self.loss_bracket_count = loss_brackets.len() as u8;
+        Ok(())
+    }
+
+    pub fn set_tax_split(
+        &mut self,
+        treasury_bps: u16,
+        reserve_bps: u16,
+        creator_bps: u16,
+        reward_bps: u16,
+    ) -> Result<()> {
+        let total = treasury_bps as u32 + reserve_bps as u32 + creator_bps as u32 + reward_bps as u32;
+        if total != 10000 {
+            return err!(CustomError::InvalidTaxSplit);
+        }
+        self.tax_treasury_bps = treasury_bps;
+        self.tax_reserve_bps = reserve_bps;
+        self.tax_creator_bps = creator_bps;
+        self.tax_reward_bps = reward_bps;
+        Ok(())
+    }
+
+    pub fn set_buyback_config(
+        &mut self,
+        buyback_bps: u16,
+        max_sol_per_call: u64,
+        cooldown_seconds: u32,
+    ) -> Result<()> {
+        if buyback_bps > 10000 {
+            return err!(CustomError::InvalidBuybackConfig);
+        }
+        self.buyback_bps = buyback_bps;
+        self.buyback_max_sol_per_call = max_sol_per_call;
+        self.buyback_cooldown_seconds = cooldown_seconds;
+        Ok(())
+    }
+
+    pub fn set_referral_fee_bps(&mut self, referral_fee_bps: u16) -> Result<()> {
+        if referral_fee_bps > 10000 {
+            return err!(CustomError::InvalidReferralFee);
+        }
+        self.referral_fee_bps = referral_fee_bps;
+        Ok(())
+    }
+
+    /// Split a paperhand tax into its (treasury, reserve, creator, reward) shares
+    ///
+    /// Rounding dust goes to the treasury, as does the creator's share when the
+    /// pool has no creator. Configurations without a split send everything to
+    /// the treasury.
+    pub fn split_tax(&self, tax: u64, has_creator: bool) -> Result<(u64, u64, u64, u64)> {
+        let reserve = apply_bps(tax, self.tax_reserve_bps).ok_or(CustomError::MathOverflow)?;
+        let creator = if has_creator {
+            apply_bps(tax, self.tax_creator_bps).ok_or(CustomError::MathOverflow)?
+        } else {
+            0
+        };
+        let reward = apply_bps(tax, self.tax_reward_bps).ok_or(CustomError::MathOverflow)?;
+        let treasury = tax
+            .checked_sub(reserve)
+            .and_then(|rest| rest.checked_sub(creator))
+            .and_then(|rest| rest.checked_sub(reward))
+            .ok_or(CustomError::MathOverflow)?;
+        Ok((treasury, reserve, creator, reward))
+    }
+
+    /// PaperHand tax owed on a sale returning `sol_out` for tokens that cost
+    /// `cost_basis`, at an effective rate of `tax_bps`
+    ///
+    /// Progressive bracket rates are capped at `tax_bps`, so pool overrides and
+    /// holding-time decay still lower them. The tax never exceeds `sol_out`.
+    pub fn paperhand_tax(&self, tax_bps: u16, sol_out: u64, cost_basis: u64) -> Result<u64> {
+        let loss = cost_basis.saturating_sub(sol_out);
+        if loss == 0 {
+            return Ok(0);
+        }
+
+        self.tax_on_loss(tax_bps, sol_out, cost_basis, loss)
+    }
+
+    /// PaperHand tax owed on `sol_out` received for untracked tokens, at an
+    /// effective rate of `tax_bps`
+    ///
+    /// Under `UntrackedTokenPolicy::FullLoss` the whole proceeds count as the
+    /// loss, i.e. a 100% loss on a cost basis equal to the proceeds.
+    pub fn untracked_tax(&self, tax_bps: u16, sol_out: u64) -> Result<u64> {
+        match self.untracked_token_policy {
+            UntrackedTokenPolicy::Reject | UntrackedTokenPolicy::ZeroCostBasis => Ok(0),
+            UntrackedTokenPolicy::FullLoss if sol_out == 0 => Ok(0),
+            UntrackedTokenPolicy::FullLoss => self.tax_on_loss(tax_bps, sol_out, sol_out, sol_out),
+        }
+    }
+
+    fn tax_on_loss(&self, tax_bps: u16, sol_out: u64, cost_basis: u64, loss: u64) -> Result<u64> {
+        let tax = match self.tax_mode {
+            TaxMode::FlatOnProceeds => apply_bps(sol_out, tax_bps),
+            TaxMode::PercentOfLoss => apply_bps(loss, tax_bps),
+            TaxMode::ProgressiveByLoss => {
+                let brackets = &self.loss_brackets[..self.loss_bracket_count as usize];
+                let mut tax: u64 = 0;
+                for (index, bracket) in brackets.iter().enumerate() {
+                    let lower = apply_bps(cost_basis, bracket.min_loss_bps)
+                        .ok_or(CustomError::MathOverflow)?;
+                    if loss <= lower {
+                        break;
+                    }
+                    let upper = match brackets.get(index + 1) {
+                        Some(next) => apply_bps(cost_basis, next.min_loss_bps)
+                            .ok_or(CustomError::MathOverflow)?
+                            .min(loss),
+                        None => loss,
+                    };
+                    let slice_tax = apply_bps(upper - lower, bracket.tax_bps.min(tax_bps))
+                        .ok_or(CustomError::MathOverflow)?;
+                    tax = tax.checked_add(slice_tax).ok_or(CustomError::MathOverflow)?;
+                }
+                Some(tax)
+            }
+        }
+        .ok_or(CustomError::MathOverflow)?;
+
+        Ok(tax.min(sol_out))
+    }
+
+    /// Amount left after deducting the trading fee
+    pub fn amount_after_fee(&self, amount: u64) -> Result<u64> {
+        let fee = apply_bps(amount, self.fee_bps).ok_or(CustomError::MathOverflow)?;
+        amount
+            .checked_sub(fee)
+            .ok_or(CustomError::MathOverflow.into())
+    }
+}
+
+/// Program-owned vault holding the treasury's share of paperhand taxes
+#[account]
+pub struct TreasuryVault {
+    /// Most lamports that may be withdrawn per epoch (0 = no limit)
+    pub epoch_withdrawal_limit: u64,
+    /// Epoch of the latest withdrawal
+    pub current_epoch: u64,
+    /// Lamports withdrawn during `current_epoch`
+    pub withdrawn_this_epoch: u64,
+    /// Lamports withdrawn over the vault's lifetime
+    pub total_withdrawn: u64,
+    /// PDA bump seed
+    pub bump: u8,
+    /// Lamports earmarked for pool buybacks, which withdrawals can't touch
+    pub earmarked: u64,
+    pub reserved: [u8; 56],
+}
+
+impl TreasuryVault {
+    pub const SEED: &'static str = CurveConfiguration::TREASURY_VAULT_SEED;
+
+    // Discriminator (8) + u64 (8) * 4 + u8 (1) + u64 (8) + reserved (56)
+    pub const ACCOUNT_SIZE: usize = 8 + 8 * 4 + 1 + 8 + 56;
+
+    pub fn address() -> Pubkey {
+        Pubkey::find_program_address(&[TreasuryVault::SEED.as_bytes()], &crate::ID).0
+    }
+
+    /// Move `amount` lamports from the vault (`vault_info`) to `destination`
+    ///
+    /// Withdrawals are capped per epoch and can't dip into the vault's rent
+    /// reserve or the lamports earmarked for buybacks.
+    pub fn withdraw(
+        &mut self,
+        vault_info: &AccountInfo,
+        destination: &AccountInfo,
+        amount: u64,
+        epoch: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return err!(CustomError::InvalidAmount);
+        }
+
+        if epoch != self.current_epoch {
+            self.current_epoch = epoch;
+            self.withdrawn_this_epoch = 0;
+        }
+        let withdrawn = self.withdrawn_this_epoch
+            .checked_add(amount)
+            .ok_or(CustomError::MathOverflow)?;
+        if self.epoch_withdrawal_limit != 0 && withdrawn > self.epoch_withdrawal_limit {
+            return err!(CustomError::TreasuryLimitExceeded);
+        }
+
+        let available = Self::spendable(vault_info)?.saturating_sub(self.earmarked);
+        if amount > available {
+            return err!(CustomError::InsufficientFunds);
+        }
+        Self::move_lamports(vault_info, destination, amount)?;
+
+        self.withdrawn_this_epoch = withdrawn;
+        self.total_withdrawn = self.total_withdrawn
+            .checked_add(amount)
+            .ok_or(CustomError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Set aside `amount` lamports of incoming tax for a pool's buybacks
+    pub fn earmark(&mut self, pool: &mut LiquidityPool, amount: u64) -> Result<()> {
+        self.earmarked = self.earmarked
+            .checked_add(amount)
+            .ok_or(CustomError::MathOverflow)?;
+        pool.buyback_balance = pool.buyback_balance
+            .checked_add(amount)
+            .ok_or(CustomError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Pay `amount` lamports earmarked for `pool` to `destination`
+    pub fn spend_earmarked(
+        &mut self,
+        vault_info: &AccountInfo,
+        destination: &AccountInfo,
+        pool: &mut LiquidityPool,
+        amount: u64,
+    ) -> Result<()> {
+        if amount > pool.buyback_balance || amount > Self::spendable(vault_info)? {
+            return err!(CustomError::InsufficientFunds);
+        }
+        Self::move_lamports(vault_info, destination, amount)?;
+
+        pool.buyback_balance -= amount;
+        self.earmarked = self.earmarked.saturating_sub(amount);
+        Ok(())
+    }
+
+    /// Lamports held above the vault's rent reserve
+    fn spendable(vault_info: &AccountInfo) -> Result<u64> {
+        let rent_reserve = Rent::get()?.minimum_balance(vault_info.data_len());
+        Ok(vault_info.lamports().saturating_sub(rent_reserve))
+    }
+
+    fn move_lamports(vault_info: &AccountInfo, destination: &AccountInfo, amount: u64) -> Result<()> {
+        **vault_info.try_borrow_mut_lamports()? -= amount;
+        **destination.try_borrow_mut_lamports()? = destination
+            .lamports()
+            .checked_add(amount)
+            .ok_or(CustomError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// Native M-of-N multisig that approves admin actions on the CurveConfiguration
+#[account]
+pub struct AdminCouncil {
+    /// Council members; only the first `member_count` entries are used
+    pub members: [Pubkey; MAX_COUNCIL_MEMBERS],
+    /// Number of populated entries in `members`
+    pub member_count: u8,
+    /// Approvals required before a proposal can be executed
+    pub threshold: u8,
+    /// Number of proposals created so far (used to derive proposal PDAs)
+    pub proposal_count: u64,
+    /// PDA bump seed
+    pub bump: u8,
+    /// Proposals with a lower id were created under an earlier membership and
+    /// can no longer be approved or executed
+    pub first_valid_proposal: u64,
+}
+
+impl AdminCouncil {
+    pub const SEED: &'static str = "admin_council";
+    // Discriminator (8) + Pubkey (32) * MAX_COUNCIL_MEMBERS + u8 (1) + u8 (1) + u64 (8) + u8 (1)
+    // + u64 (8)
+    pub const ACCOUNT_SIZE: usize = 8 + 32 * MAX_COUNCIL_MEMBERS + 1 + 1 + 8 + 1 + 8;
+
+    pub fn new(members: &[Pubkey], threshold: u8, bump: u8) -> Result<Self> {
+        let mut council = Self {
+            members: [Pubkey::default(); MAX_COUNCIL_MEMBERS],
+            member_count: 0,
+            threshold: 0,
+            proposal_count: 0,
+            bump,
+            first_valid_proposal: 0,
+        };
+        council.set_members(members, threshold)?;
+        Ok(council)
+    }
+
+    /// Replace the members and threshold
+    ///
+    /// Approvals are recorded by member index, so every proposal created
+    /// before the change is invalidated.
+    pub fn set_members(&mut self, members: &[Pubkey], threshold: u8) -> Result<()> {
+        if members.is_empty()
+            || members.len() > MAX_COUNCIL_MEMBERS
+            || threshold == 0
+            || threshold as usize > members.len()
+        {
+            return err!(CustomError::InvalidCouncil);
+        }
+
+        self.members = [Pubkey::default(); MAX_COUNCIL_MEMBERS];
+        for (i, member) in members.iter().enumerate() {
+            // Reject empty keys and duplicates so every approval is a distinct signer
+            if *member == Pubkey::default() || members[..i].contains(member) {
+                return err!(CustomError::InvalidCouncil);
+            }
+            self.members[i] = *member;
+        }
+        self.member_count = members.len() as u8;
+        self.threshold = threshold;
+        self.first_valid_proposal = self.proposal_count;
+
+        Ok(())
+    }
+
+    /// Index of `key` in the member list, used as its approval bit
+    pub fn member_index(&self, key: &Pubkey) -> Option<usize> {
+        self.members[..self.member_count as usize]
+            .iter()
+            .position(|member| member == key)
+    }
+}
+
+/// Admin action carried by an AdminProposal and applied once approved
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum AdminAction {
+    /// Change the trading fee (in basis points)
+    SetFees { fee_bps: u16 },
+    /// Change the treasury that receives the PaperHand tax (must be the treasury vault)
+    SetTreasury { treasury: Pubkey },
+    /// Set the global pause switches
+    SetPauseFlags { pause_flags: u8 },
+    /// Set the pause switches of a single pool
+    SetPoolPauseFlags { pool: Pubkey, pause_flags: u8 },
+    /// Withdraw accrued protocol fees from the global vault
+    WithdrawFees { amount: u64, destination: Pubkey },
+    /// Replace the holding-time paperhand tax schedule
+    SetTaxSchedule { brackets: Vec<TaxBracket> },
+    /// Change how the paperhand tax is split between treasury, reserve, creator
+    /// and diamond-hand rewards
+    SetTaxSplit { treasury_bps: u16, reserve_bps: u16, creator_bps: u16, reward_bps: u16 },
+    /// Change the range of paperhand tax creators may choose at launch
+    SetTaxBounds { min_bps: u16, max_bps: u16 },
+    /// Switch the paperhand tax formula
+    SetTaxMode { tax_mode: TaxMode, loss_brackets: Vec<LossBracket> },
+    /// Change how sales of untracked tokens are handled
+    SetUntrackedTokenPolicy { policy: UntrackedTokenPolicy },
+    /// Turn closing of positions emptied by a sell on or off
+    SetAutoClosePositions { enabled: bool },
+    /// Pay lamports out of the treasury vault
+    WithdrawTreasury { amount: u64, destination: Pubkey },
+    /// Change how much the treasury vault may pay out per epoch
+    SetTreasuryWithdrawalLimit { limit: u64 },
+    /// Change the buyback share of the treasury tax and the crank's limits
+    SetBuybackConfig {
+        buyback_bps: u16,
+        max_sol_per_call: u64,
+        cooldown_seconds: u32,
+    },
+    /// Change the share of the protocol fee paid to referrers
+    SetReferralFeeBps { referral_fee_bps: u16 },
+    /// Appoint the moderator of the symbol registry
+    SetSymbolAuthority { authority: Pubkey },
+    /// Replace the council members and approval threshold
+    SetCouncil { members: Vec<Pubkey>, threshold: u8 },
+}
+
+/// A pending admin action awaiting council approvals
+#[account]
+pub struct AdminProposal {
+    /// The council this proposal belongs to
+    pub council: Pubkey,
+    /// Sequential proposal id within the council
+    pub id: u64,
+    /// Member that created the proposal
+    pub proposer: Pubkey,
+    /// The action to apply once the threshold is reached
+    pub action: AdminAction,
+    /// Bitmask of member indices that approved
+    pub approvals: u16,
+    /// Set once the action has been applied
+    pub executed: bool,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl AdminProposal {
+    pub const SEED_PREFIX: &'static str = "admin_proposal";
+
+    // Serialized AdminAction is capped so new variants fit without resizing;
+    // the largest, SetCouncil with a full member list, takes 326 bytes
+    pub const MAX_ACTION_SIZE: usize = 384;
+
+    // Discriminator (8) + Pubkey (32) + u64 (8) + Pubkey (32) + action (MAX_ACTION_SIZE)
+    // + u16 (2) + bool (1) + u8 (1)
+    pub const ACCOUNT_SIZE: usize = 8 + 32 + 8 + 32 + AdminProposal::MAX_ACTION_SIZE + 2 + 1 + 1;
+
+    pub fn approval_count(&self) -> u32 {
+        self.approvals.count_ones()
+    }
+
+    pub fn has_approved(&self, member_index: usize) -> bool {
+        self.approvals & (1 << member_index) != 0
+    }
+
+    pub fn approve(&mut self, member_index: usize) -> Result<()> {
+        if self.has_approved(member_index) {
+            return err!(CustomError::ProposalAlreadyApproved);
         }
+        self.approvals |= 1 << member_index;
+        Ok(())
     }
 }
 
@@ -48,13 +734,47 @@ pub struct UserPosition {
     pub total_sol: u64,
     /// PDA bump seed
     pub bump: u8,
+    /// Account layout version (see `UserPosition::VERSION`)
+    pub version: u8,
+    /// Token-weighted average unix timestamp of the buys making up the position
+    /// (0 = unknown, e.g. positions created before this was tracked)
+    pub entry_timestamp: i64,
+    /// Pool reward-per-token already accounted for in `pending_rewards`
+    pub reward_per_token_paid: u128,
+    /// Diamond-hand rewards earned but not yet claimed (in lamports)
+    pub pending_rewards: u64,
+    /// Whether `total_tokens` is counted in the pool's `reward_tokens`
+    pub rewards_enrolled: bool,
+    /// Lifetime gains of sales over their cost basis, before tax (in lamports)
+    pub realized_profit: u64,
+    /// Lifetime shortfall of sales under their cost basis, before tax (in lamports)
+    pub realized_loss: u64,
+    /// Lifetime paperhand tax paid (in lamports)
+    pub total_tax_paid: u64,
+    /// Number of buys through the curve
+    pub buy_count: u32,
+    /// Number of sells through the curve
+    pub sell_count: u32,
+    /// Unix timestamp of the first trade (0 = none yet)
+    pub first_trade_timestamp: i64,
+    /// Unix timestamp of the latest trade (0 = none yet)
+    pub last_trade_timestamp: i64,
+    /// Lifetime SOL spent on buys (in lamports)
+    pub total_sol_in: u64,
+    /// Lifetime SOL proceeds of sells, before tax, untracked tokens included (in lamports)
+    pub total_sol_out: u64,
+    pub reserved: [u8; 95],
 }
 
 impl UserPosition {
     pub const SEED_PREFIX: &'static str = "position";
+    pub const VERSION: u8 = 1;
 
     // Discriminator (8) + Pubkey (32) + Pubkey (32) + u64 (8) + u64 (8) + u8 (1)
-    pub const ACCOUNT_SIZE: usize = 8 + 32 + 32 + 8 + 8 + 1;
+    // + u8 (1) + i64 (8) + u128 (16) + u64 (8) + bool (1) + u64 (8) * 3 + u32 (4) * 2
+    // + i64 (8) * 2 + u64 (8) * 2 + reserved (95)
+    pub const ACCOUNT_SIZE: usize =
+        8 + 32 + 32 + 8 + 8 + 1 + 1 + 8 + 16 + 8 + 1 + 8 * 3 + 4 * 2 + 8 * 2 + 8 * 2 + 95;
 
     pub fn new(pool: Pubkey, owner: Pubkey, bump: u8) -> Self {
         Self {
@@ -63,12 +783,80 @@ impl UserPosition {
             total_tokens: 0,
             total_sol: 0,
             bump,
+            version: UserPosition::VERSION,
+            entry_timestamp: 0,
+            reward_per_token_paid: 0,
+            pending_rewards: 0,
+            rewards_enrolled: false,
+            realized_profit: 0,
+            realized_loss: 0,
+            total_tax_paid: 0,
+            buy_count: 0,
+            sell_count: 0,
+            first_trade_timestamp: 0,
+            last_trade_timestamp: 0,
+            total_sol_in: 0,
+            total_sol_out: 0,
+            reserved: [0; 95],
         }
     }
 
+    /// Credit rewards earned since the last settlement at the pool's current
+    /// reward-per-token
+    ///
+    /// Must run before `total_tokens` changes so tokens only earn from tax
+    /// events that happened while they were held. Positions opened before
+    /// rewards existed join here without any past rewards.
+    pub fn settle_rewards(&mut self, pool: &mut LiquidityPool) -> Result<()> {
+        if self.rewards_enrolled {
+            let earned = (self.total_tokens as u128)
+                .checked_mul(
+                    pool.reward_per_token
+                        .checked_sub(self.reward_per_token_paid)
+                        .ok_or(CustomError::MathOverflow)?,
+                )
+                .ok_or(CustomError::MathOverflow)?
+                / REWARD_PRECISION;
+            self.pending_rewards = self.pending_rewards
+                .checked_add(u64::try_from(earned).map_err(|_| CustomError::MathOverflow)?)
+                .ok_or(CustomError::MathOverflow)?;
+        } else {
+            pool.reward_tokens = pool.reward_tokens
+                .checked_add(self.total_tokens)
+                .ok_or(CustomError::MathOverflow)?;
+            self.rewards_enrolled = true;
+        }
+        self.reward_per_token_paid = pool.reward_per_token;
+        Ok(())
+    }
+
+    /// Seconds the position has been held, weighted by buy size
+    ///
+    /// An unknown entry time counts as just bought, so legacy positions pay the
+    /// highest rate of the schedule until their next buy.
+    pub fn holding_seconds(&self, now: i64) -> i64 {
+        UserPosition::holding_seconds_since(self.entry_timestamp, now)
+    }
+
+    pub fn holding_seconds_since(entry_timestamp: i64, now: i64) -> i64 {
+        if entry_timestamp == 0 {
+            return 0;
+        }
+        now.saturating_sub(entry_timestamp).max(0)
+    }
+
     /// Calculate cost basis for a given token amount using u128 for overflow safety
-    /// Returns the proportional SOL cost for the tokens being sold
-    pub fn calculate_cost_basis_for_sale(&self, token_amount: u64) -> Result<u64> {
+    /// Returns the proportional SOL cost for the tokens being sold: from the
+    /// oldest lots first when `lots` is given (FIFO pools), at average cost otherwise
+    pub fn calculate_cost_basis_for_sale(
+        &self,
+        token_amount: u64,
+        lots: Option<&PositionLots>,
+    ) -> Result<u64> {
+        if let Some(lots) = lots {
+            return Ok(lots.preview_sale(token_amount)?.0);
+        }
+
         if self.total_tokens == 0 {
             return Ok(0);
         }
@@ -89,7 +877,48 @@ impl UserPosition {
     }
 
     /// Update position after a buy
-    pub fn record_buy(&mut self, tokens_received: u64, sol_spent: u64) -> Result<()> {
+    pub fn record_buy(
+        &mut self,
+        pool: &mut LiquidityPool,
+        tokens_received: u64,
+        sol_spent: u64,
+        now: i64,
+    ) -> Result<()> {
+        self.add_tokens(pool, tokens_received, sol_spent, now)?;
+
+        self.buy_count = self.buy_count.saturating_add(1);
+        self.total_sol_in = self.total_sol_in
+            .checked_add(sol_spent)
+            .ok_or(CustomError::MathOverflow)?;
+        self.record_trade_time(now);
+        Ok(())
+    }
+
+    /// Credit tokens and their cost basis, after a buy or a received transfer
+    /// Moves the entry timestamp towards `now` (the tokens' entry time) in
+    /// proportion to the tokens received
+    pub fn add_tokens(
+        &mut self,
+        pool: &mut LiquidityPool,
+        tokens_received: u64,
+        sol_spent: u64,
+        now: i64,
+    ) -> Result<()> {
+        self.settle_rewards(pool)?;
+        pool.reward_tokens = pool.reward_tokens
+            .checked_add(tokens_received)
+            .ok_or(CustomError::MathOverflow)?;
+
+        // Tokens bought before entry times were tracked count as bought now
+        let previous_entry = if self.entry_timestamp == 0 { now } else { self.entry_timestamp };
+        let total_tokens = (self.total_tokens as i128) + (tokens_received as i128);
+        if total_tokens > 0 {
+            let held = (previous_entry as i128) * (self.total_tokens as i128);
+            let bought = (now as i128) * (tokens_received as i128);
+            let weighted = held.checked_add(bought).ok_or(CustomError::MathOverflow)?;
+            self.entry_timestamp = (weighted / total_tokens) as i64;
+        }
+
         self.total_tokens = self.total_tokens
             .checked_add(tokens_received)
             .ok_or(CustomError::MathOverflow)?;
@@ -99,9 +928,74 @@ impl UserPosition {
         Ok(())
     }
 
-    /// Update position after a sell
+    /// Whether the position holds nothing worth keeping the account for
+    pub fn is_closable(&self) -> bool {
+        self.total_tokens == 0 && self.pending_rewards == 0
+    }
+
+    /// Update position after a sell of `token_amount` tracked tokens returning
+    /// `sol_out` before tax
+    ///
+    /// Only the tracked tokens have a cost basis, so only `sol_out` counts
+    /// towards the realized profit or loss. `untracked_sol_out`, the proceeds
+    /// of untracked tokens sold alongside, is only added to `total_sol_out`.
+    pub fn record_sell(
+        &mut self,
+        pool: &mut LiquidityPool,
+        token_amount: u64,
+        cost_basis: u64,
+        sol_out: u64,
+        untracked_sol_out: u64,
+        now: i64,
+    ) -> Result<()> {
+        self.remove_tokens(pool, token_amount, cost_basis)?;
+
+        if sol_out >= cost_basis {
+            self.realized_profit = self.realized_profit
+                .checked_add(sol_out - cost_basis)
+                .ok_or(CustomError::MathOverflow)?;
+        } else {
+            self.realized_loss = self.realized_loss
+                .checked_add(cost_basis - sol_out)
+                .ok_or(CustomError::MathOverflow)?;
+        }
+        self.sell_count = self.sell_count.saturating_add(1);
+        self.total_sol_out = self.total_sol_out
+            .checked_add(sol_out)
+            .and_then(|total| total.checked_add(untracked_sol_out))
+            .ok_or(CustomError::MathOverflow)?;
+        self.record_trade_time(now);
+        Ok(())
+    }
+
+    /// Add paperhand tax charged on a sell to the lifetime total
+    pub fn record_tax_paid(&mut self, tax: u64) -> Result<()> {
+        self.total_tax_paid = self.total_tax_paid
+            .checked_add(tax)
+            .ok_or(CustomError::MathOverflow)?;
+        Ok(())
+    }
+
+    fn record_trade_time(&mut self, now: i64) {
+        if self.first_trade_timestamp == 0 {
+            self.first_trade_timestamp = now;
+        }
+        self.last_trade_timestamp = now;
+    }
+
+    /// Debit tokens and their cost basis, after a sell or a sent transfer
     /// Reduces total_tokens by token_amount and total_sol by cost_basis proportionally
-    pub fn record_sell(&mut self, token_amount: u64, cost_basis: u64) -> Result<()> {
+    pub fn remove_tokens(
+        &mut self,
+        pool: &mut LiquidityPool,
+        token_amount: u64,
+        cost_basis: u64,
+    ) -> Result<()> {
+        self.settle_rewards(pool)?;
+        pool.reward_tokens = pool.reward_tokens
+            .checked_sub(token_amount)
+            .ok_or(CustomError::MathOverflow)?;
+
         self.total_tokens = self.total_tokens
             .checked_sub(token_amount)
             .ok_or(CustomError::InsufficientPosition)?;
@@ -112,12 +1006,658 @@ impl UserPosition {
         // Clean up dust when position is empty
         if self.total_tokens == 0 {
             self.total_sol = 0;
+            self.entry_timestamp = 0;
         }
         
         Ok(())
     }
 }
 
+/// How a pool computes the cost basis of a sale
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CostBasisMode {
+    /// Every token costs the position's average price
+    #[default]
+    AverageCost,
+    /// Sales consume the position's oldest lots first (see `PositionLots`)
+    Fifo,
+}
+
+/// Tokens and SOL of a single buy
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Lot {
+    pub tokens: u64,
+    pub sol: u64,
+    pub timestamp: i64,
+}
+
+/// Ring of buy lots for a position in a `CostBasisMode::Fifo` pool
+#[account]
+pub struct PositionLots {
+    /// The UserPosition these lots belong to
+    pub position: Pubkey,
+    /// Index of the oldest lot
+    pub head: u8,
+    /// Number of open lots
+    pub count: u8,
+    pub lots: [Lot; MAX_POSITION_LOTS],
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl PositionLots {
+    pub const SEED_PREFIX: &'static str = "position_lots";
+
+    // Discriminator (8) + Pubkey (32) + u8 (1) + u8 (1) + Lot (24) * MAX_POSITION_LOTS + u8 (1)
+    pub const ACCOUNT_SIZE: usize = 8 + 32 + 1 + 1 + 24 * MAX_POSITION_LOTS + 1;
+
+    fn slot(&self, offset: usize) -> usize {
+        (self.head as usize + offset) % MAX_POSITION_LOTS
+    }
+
+    /// Record a buy as the newest lot
+    ///
+    /// Once the ring is full the buy is merged into the newest lot, averaging
+    /// its price and timestamp.
+    pub fn push(&mut self, tokens: u64, sol: u64, timestamp: i64) -> Result<()> {
+        if (self.count as usize) < MAX_POSITION_LOTS {
+            let slot = self.slot(self.count as usize);
+            self.lots[slot] = Lot { tokens, sol, timestamp };
+            self.count += 1;
+            return Ok(());
+        }
+
+        let newest = &mut self.lots[self.slot(MAX_POSITION_LOTS - 1)];
+        let total_tokens = (newest.tokens as i128) + (tokens as i128);
+        if total_tokens > 0 {
+            let weighted = (newest.timestamp as i128) * (newest.tokens as i128)
+                + (timestamp as i128) * (tokens as i128);
+            newest.timestamp = (weighted / total_tokens) as i64;
+        }
+        newest.tokens = newest.tokens.checked_add(tokens).ok_or(CustomError::MathOverflow)?;
+        newest.sol = newest.sol.checked_add(sol).ok_or(CustomError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Cost basis and token-weighted entry timestamp of selling `token_amount`
+    /// from the oldest lots, without consuming them
+    pub fn preview_sale(&self, token_amount: u64) -> Result<(u64, i64)> {
+        let mut remaining = token_amount;
+        let mut cost: u128 = 0;
+        let mut weighted_timestamp: i128 = 0;
+
+        for offset in 0..self.count as usize {
+            if remaining == 0 {
+                break;
+            }
+            let lot = &self.lots[self.slot(offset)];
+            let taken = remaining.min(lot.tokens);
+            cost += lot_cost(lot, taken)? as u128;
+            weighted_timestamp += (lot.timestamp as i128) * (taken as i128);
+            remaining -= taken;
+        }
+        if remaining > 0 {
+            return err!(CustomError::InsufficientPosition);
+        }
+
+        let cost = u64::try_from(cost).map_err(|_| CustomError::MathOverflow)?;
+        let entry_timestamp = if token_amount == 0 {
+            0
+        } else {
+            (weighted_timestamp / token_amount as i128) as i64
+        };
+        Ok((cost, entry_timestamp))
+    }
+
+    /// Remove `token_amount` from the oldest lots, closing lots as they empty
+    pub fn consume(&mut self, token_amount: u64) -> Result<()> {
+        let mut remaining = token_amount;
+        while remaining > 0 {
+            if self.count == 0 {
+                return err!(CustomError::InsufficientPosition);
+            }
+            let head = self.head as usize;
+            let lot = &mut self.lots[head];
+            let taken = remaining.min(lot.tokens);
+            let cost = lot_cost(lot, taken)?;
+            lot.tokens -= taken;
+            lot.sol -= cost;
+            remaining -= taken;
+
+            if lot.tokens == 0 {
+                *lot = Lot::default();
+                self.head = ((head + 1) % MAX_POSITION_LOTS) as u8;
+                self.count -= 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Cumulative trading statistics of a pool, maintained by `swap` and the
+/// position transfers
+#[account]
+pub struct PoolStats {
+    /// The pool these statistics belong to
+    pub pool: Pubkey,
+    /// SOL spent on buys, fees included (in lamports)
+    pub buy_volume_sol: u64,
+    /// SOL proceeds of sells, before tax (in lamports)
+    pub sell_volume_sol: u64,
+    /// Tokens bought (in smallest units)
+    pub buy_volume_tokens: u64,
+    /// Tokens sold (in smallest units)
+    pub sell_volume_tokens: u64,
+    /// Number of buys and sells
+    pub trade_count: u64,
+    /// Number of times a position went from empty to holding tokens
+    pub holder_count: u64,
+    /// Trading fees collected (in lamports)
+    pub total_fees: u64,
+    /// Paperhand tax collected (in lamports)
+    pub total_tax: u64,
+    /// Highest price reached, in lamports per token unit scaled by PRICE_PRECISION
+    pub ath_price: u128,
+    /// Slot at which `ath_price` was reached
+    pub ath_slot: u64,
+    /// PDA bump seed
+    pub bump: u8,
+    pub reserved: [u8; 64],
+}
+
+impl PoolStats {
+    pub const SEED_PREFIX: &'static str = "pool_stats";
+
+    // Discriminator (8) + Pubkey (32) + u64 (8) * 8 + u128 (16) + u64 (8) + u8 (1)
+    // + reserved (64)
+    pub const ACCOUNT_SIZE: usize = 8 + 32 + 8 * 8 + 16 + 8 + 1 + 64;
+
+    pub fn record_buy(
+        &mut self,
+        sol_spent: u64,
+        tokens_received: u64,
+        fee: u64,
+        new_holder: bool,
+    ) -> Result<()> {
+        self.buy_volume_sol = self.buy_volume_sol
+            .checked_add(sol_spent)
+            .ok_or(CustomError::MathOverflow)?;
+        self.buy_volume_tokens = self.buy_volume_tokens
+            .checked_add(tokens_received)
+            .ok_or(CustomError::MathOverflow)?;
+        self.total_fees = self.total_fees
+            .checked_add(fee)
+            .ok_or(CustomError::MathOverflow)?;
+        if new_holder {
+            self.record_new_holder();
+        }
+        self.trade_count = self.trade_count.saturating_add(1);
+        Ok(())
+    }
+
+    /// Count a position that went from empty to holding tokens
+    pub fn record_new_holder(&mut self) {
+        self.holder_count = self.holder_count.saturating_add(1);
+    }
+
+    pub fn record_sell(&mut self, sol_out: u64, tokens_sold: u64, tax: u64) -> Result<()> {
+        self.sell_volume_sol = self.sell_volume_sol
+            .checked_add(sol_out)
+            .ok_or(CustomError::MathOverflow)?;
+        self.sell_volume_tokens = self.sell_volume_tokens
+            .checked_add(tokens_sold)
+            .ok_or(CustomError::MathOverflow)?;
+        self.total_tax = self.total_tax
+            .checked_add(tax)
+            .ok_or(CustomError::MathOverflow)?;
+        self.trade_count = self.trade_count.saturating_add(1);
+        Ok(())
+    }
+
+    /// Record the pool's current price if it is a new all-time high
+    pub fn update_ath(&mut self, pool: &LiquidityPool, slot: u64) {
+        let price = pool.spot_price();
+        if price > self.ath_price {
+            self.ath_price = price;
+            self.ath_slot = slot;
+        }
+    }
+}
+
+/// A pool in the king-of-the-hill ranking
+#[zero_copy]
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct RankedPool {
+    pub mint: Pubkey,
+    /// Market cap at the pool's last trade (in lamports)
+    pub market_cap: u64,
+}
+
+/// Protocol-wide statistics and the king-of-the-hill ranking, maintained by
+/// `launch` and `swap` so a homepage can render from a single fetch
+#[account(zero_copy)]
+pub struct GlobalStats {
+    /// Tokens launched
+    pub total_launches: u64,
+    /// SOL traded through the curves: buys with fees, sells before tax (in lamports)
+    pub total_volume: u64,
+    /// Paperhand tax collected (in lamports)
+    pub total_tax: u64,
+    /// Pools that left the bonding curve (no graduation path yet)
+    pub graduated_pools: u64,
+    /// Pools with the highest market caps, highest first
+    pub ranked_pools: [RankedPool; MAX_RANKED_POOLS],
+    /// Number of populated entries in `ranked_pools`
+    pub ranked_count: u8,
+    /// PDA bump seed
+    pub bump: u8,
+    pub padding: [u8; 6],
+    pub reserved: [u8; 64],
+}
+
+impl GlobalStats {
+    pub const SEED: &'static str = "global_stats";
+
+    // Discriminator (8) + u64 (8) * 4 + RankedPool (40) * MAX_RANKED_POOLS + u8 (1) + u8 (1)
+    // + padding (6) + reserved (64)
+    pub const ACCOUNT_SIZE: usize = 8 + 8 * 4 + 40 * MAX_RANKED_POOLS + 1 + 1 + 6 + 64;
+
+    pub fn record_trade(&mut self, volume: u64, tax: u64) -> Result<()> {
+        self.total_volume = self.total_volume
+            .checked_add(volume)
+            .ok_or(CustomError::MathOverflow)?;
+        self.total_tax = self.total_tax
+            .checked_add(tax)
+            .ok_or(CustomError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Update a pool's market cap in the ranking
+    ///
+    /// A pool outside the ranking only enters it by beating the lowest entry
+    /// once the ranking is full; ranked pools move both ways.
+    pub fn update_ranking(&mut self, mint: Pubkey, market_cap: u64) {
+        let count = self.ranked_count as usize;
+        let index = match self.ranked_pools[..count].iter().position(|entry| entry.mint == mint) {
+            Some(index) => index,
+            None if count < MAX_RANKED_POOLS => {
+                self.ranked_count += 1;
+                count
+            }
+            None if market_cap > self.ranked_pools[count - 1].market_cap => count - 1,
+            None => return,
+        };
+        self.ranked_pools[index] = RankedPool { mint, market_cap };
+
+        // Restore the descending order around the updated entry
+        let ranked = &mut self.ranked_pools[..self.ranked_count as usize];
+        let mut index = index;
+        while index > 0 && ranked[index].market_cap > ranked[index - 1].market_cap {
+            ranked.swap(index, index - 1);
+            index -= 1;
+        }
+        while index + 1 < ranked.len() && ranked[index].market_cap < ranked[index + 1].market_cap {
+            ranked.swap(index, index + 1);
+            index += 1;
+        }
+    }
+}
+
+/// SOL cost of `tokens` out of `lot`, proportional to the lot's price
+fn lot_cost(lot: &Lot, tokens: u64) -> Result<u64> {
+    if tokens == lot.tokens {
+        return Ok(lot.sol);
+    }
+    let cost = (lot.sol as u128)
+        .checked_mul(tokens as u128)
+        .ok_or(CustomError::MathOverflow)?
+        / (lot.tokens as u128);
+    Ok(cost as u64)
+}
+
+/// Per-creator launch counter; mints are derived from the creator and this
+/// counter, so symbols don't need to be unique
+#[account]
+pub struct CreatorProfile {
+    /// Wallet that launches the tokens
+    pub creator: Pubkey,
+    /// Tokens launched so far, i.e. the index of the next launch
+    pub launch_count: u64,
+    /// PDA bump seed
+    pub bump: u8,
+    pub reserved: [u8; 32],
+}
+
+impl CreatorProfile {
+    pub const SEED_PREFIX: &'static str = "creator";
+
+    // Discriminator (8) + Pubkey (32) + u64 (8) + u8 (1) + reserved (32)
+    pub const ACCOUNT_SIZE: usize = 8 + 32 + 8 + 1 + 32;
+}
+
+/// Reverse index from a mint to its launch, seeded by the mint
+#[account]
+pub struct LaunchRecord {
+    pub mint: Pubkey,
+    pub pool: Pubkey,
+    pub creator: Pubkey,
+    /// The creator's launch counter the mint was derived from
+    pub launch_index: u64,
+    /// Bump of the mint PDA `["mint", creator, launch_index]`
+    pub mint_bump: u8,
+    pub symbol: String,
+    pub timestamp: i64,
+    pub slot: u64,
+    /// PDA bump seed
+    pub bump: u8,
+    pub reserved: [u8; 32],
+}
+
+impl LaunchRecord {
+    pub const SEED_PREFIX: &'static str = "launch";
+    pub const MINT_SEED_PREFIX: &'static str = "mint";
+
+    // Discriminator (8) + Pubkey (32) * 3 + u64 (8) + u8 (1) + String (4 + MAX_SYMBOL_LEN)
+    // + i64 (8) + u64 (8) + u8 (1) + reserved (32)
+    pub const ACCOUNT_SIZE: usize = 8 + 32 * 3 + 8 + 1 + 4 + MAX_SYMBOL_LEN + 8 + 8 + 1 + 32;
+}
+
+/// Settings of the symbol registry consulted by `launch`
+#[account]
+pub struct SymbolRegistry {
+    /// Moderator that blocks and reserves symbols and tunes the auctions
+    pub authority: Pubkey,
+    /// Symbols up to this length are premium and only launchable by an
+    /// auction winner (0 = no premium symbols)
+    pub premium_max_len: u8,
+    /// How long an auction runs after its first bid
+    pub auction_duration_seconds: u32,
+    /// Smallest opening bid (in lamports)
+    pub min_bid: u64,
+    /// How much a bid must beat the highest bid by (in basis points)
+    pub min_bid_increment_bps: u16,
+    /// PDA bump seed
+    pub bump: u8,
+    pub reserved: [u8; 64],
+}
+
+impl SymbolRegistry {
+    pub const SEED: &'static str = "symbol_registry";
+
+    // Discriminator (8) + Pubkey (32) + u8 (1) + u32 (4) + u64 (8) + u16 (2) + u8 (1)
+    // + reserved (64)
+    pub const ACCOUNT_SIZE: usize = 8 + 32 + 1 + 4 + 8 + 2 + 1 + 64;
+
+    pub fn is_premium(&self, symbol: &str) -> bool {
+        symbol.len() <= self.premium_max_len as usize
+    }
+
+    /// Smallest bid accepted against `entry`'s current auction
+    pub fn next_min_bid(&self, entry: &SymbolEntry) -> Result<u64> {
+        if entry.highest_bid == 0 {
+            return Ok(self.min_bid);
+        }
+        let increment = apply_bps(entry.highest_bid, self.min_bid_increment_bps)
+            .ok_or(CustomError::MathOverflow)?;
+        entry.highest_bid
+            .checked_add(increment.max(1))
+            .ok_or(CustomError::MathOverflow.into())
+    }
+}
+
+/// Moderation and allocation state of one symbol, seeded by the symbol in
+/// upper case
+///
+/// Lamports bid in a running auction are escrowed on this account.
+#[account]
+pub struct SymbolEntry {
+    pub symbol: String,
+    /// Blocked symbols can't be launched, reserved or auctioned
+    pub blocked: bool,
+    /// Only this wallet may launch the symbol (default = not reserved)
+    pub reserved_for: Pubkey,
+    /// End of the reservation (0 = no expiry)
+    pub reservation_expires_at: i64,
+    /// End of the auction started by the first bid (0 = never auctioned)
+    pub auction_end_time: i64,
+    /// Highest bid, escrowed until the auction is settled (in lamports)
+    pub highest_bid: u64,
+    pub highest_bidder: Pubkey,
+    /// Whether the winning bid went to the treasury and the winner got the reservation
+    pub auction_settled: bool,
+    /// PDA bump seed
+    pub bump: u8,
+    pub reserved: [u8; 32],
+}
+
+impl SymbolEntry {
+    pub const SEED_PREFIX: &'static str = "symbol";
+
+    // Discriminator (8) + String (4 + MAX_SYMBOL_LEN) + bool (1) + Pubkey (32) + i64 (8) * 2
+    // + u64 (8) + Pubkey (32) + bool (1) + u8 (1) + reserved (32)
+    pub const ACCOUNT_SIZE: usize =
+        8 + 4 + MAX_SYMBOL_LEN + 1 + 32 + 8 * 2 + 8 + 32 + 1 + 1 + 32;
+
+    /// Registry form of a symbol; lookups ignore case so "doge" can't
+    /// impersonate "DOGE"
+    pub fn normalize(symbol: &str) -> String {
+        symbol.to_ascii_uppercase()
+    }
+
+    /// Require `symbol` to be a valid symbol in registry form
+    ///
+    /// Only A-Z and 0-9 are allowed, so look-alikes such as a trailing space
+    /// or a Cyrillic letter can't slip past moderation as distinct symbols.
+    pub fn validate(symbol: &str) -> Result<()> {
+        if symbol.is_empty()
+            || symbol.len() > MAX_SYMBOL_LEN
+            || !symbol.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+        {
+            return err!(CustomError::InvalidSymbol);
+        }
+        Ok(())
+    }
+
+    pub fn address(symbol: &str) -> Pubkey {
+        Pubkey::find_program_address(
+            &[SymbolEntry::SEED_PREFIX.as_bytes(), symbol.as_bytes()],
+            &crate::ID,
+        )
+        .0
+    }
+
+    /// Record the symbol and bump of a freshly created entry
+    pub fn init_if_new(&mut self, symbol: &str, bump: u8) {
+        if self.symbol.is_empty() {
+            self.symbol = symbol.to_string();
+            self.bump = bump;
+        }
+    }
+
+    pub fn is_reserved(&self, now: i64) -> bool {
+        self.reserved_for != Pubkey::default()
+            && (self.reservation_expires_at == 0 || now < self.reservation_expires_at)
+    }
+
+    pub fn has_auction(&self) -> bool {
+        self.auction_end_time != 0
+    }
+
+    /// Whether `creator` may launch a token under this symbol
+    pub fn check_launch(&self, creator: &Pubkey, now: i64, premium: bool) -> Result<()> {
+        if self.blocked {
+            return err!(CustomError::SymbolBlocked);
+        }
+        if self.has_auction() && !self.auction_settled {
+            return err!(CustomError::SymbolInAuction);
+        }
+        if self.is_reserved(now) {
+            if self.reserved_for != *creator {
+                return err!(CustomError::SymbolReserved);
+            }
+            return Ok(());
+        }
+        if premium {
+            return err!(CustomError::SymbolRequiresAuction);
+        }
+        Ok(())
+    }
+}
+
+/// Creator allocation requested at launch, vesting from the launch time
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VestingParams {
+    /// Tokens carved out of the initial supply (in base units)
+    pub amount: u64,
+    /// Nothing unlocks before this many seconds after the launch
+    pub cliff_seconds: u32,
+    /// Everything is unlocked this many seconds after the launch
+    pub duration_seconds: u32,
+}
+
+impl VestingParams {
+    /// The allocation must leave tokens in the pool and the cliff must fall
+    /// within the vesting period
+    pub fn is_valid(&self, initial_supply: u64) -> bool {
+        self.amount > 0
+            && self.amount < initial_supply
+            && self.duration_seconds > 0
+            && self.cliff_seconds <= self.duration_seconds
+    }
+}
+
+/// Escrow of a creator allocation, seeded by the mint; its associated token
+/// account holds the locked tokens
+///
+/// The tokens unlock linearly from `start_timestamp` to `end_timestamp`, but
+/// none can be claimed before `cliff_timestamp`.
+#[account]
+pub struct VestingEscrow {
+    pub mint: Pubkey,
+    pub pool: Pubkey,
+    /// Wallet that claims the unlocked tokens (the creator)
+    pub beneficiary: Pubkey,
+    /// Tokens allocated at launch (in base units)
+    pub total_amount: u64,
+    /// Tokens claimed so far (in base units)
+    pub claimed_amount: u64,
+    pub start_timestamp: i64,
+    pub cliff_timestamp: i64,
+    pub end_timestamp: i64,
+    /// PDA bump seed
+    pub bump: u8,
+    pub reserved: [u8; 32],
+}
+
+impl VestingEscrow {
+    pub const SEED_PREFIX: &'static str = "vesting";
+
+    // Discriminator (8) + Pubkey (32) * 3 + u64 (8) * 2 + i64 (8) * 3 + u8 (1) + reserved (32)
+    pub const ACCOUNT_SIZE: usize = 8 + 32 * 3 + 8 * 2 + 8 * 3 + 1 + 32;
+
+    /// Tokens unlocked at `now`, claimed or not
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        if now < self.cliff_timestamp {
+            return 0;
+        }
+        if now >= self.end_timestamp {
+            return self.total_amount;
+        }
+        let elapsed = (now - self.start_timestamp) as u128;
+        let duration = (self.end_timestamp - self.start_timestamp) as u128;
+        (self.total_amount as u128 * elapsed / duration) as u64
+    }
+
+    /// Tokens unlocked at `now` and not yet claimed
+    pub fn claimable_amount(&self, now: i64) -> u64 {
+        self.vested_amount(now).saturating_sub(self.claimed_amount)
+    }
+}
+
+/// A wallet that refers traders and earns a share of their protocol fees
+#[account]
+pub struct Referrer {
+    /// Wallet that registered the referrer and claims its fees
+    pub owner: Pubkey,
+    /// Earned fees not yet claimed, held in the global vault (in lamports)
+    pub accrued_fees: u64,
+    /// Lifetime fees earned (in lamports)
+    pub total_earned: u64,
+    /// Wallets bound to this referrer, counted once per pool
+    pub referred_positions: u64,
+    /// Trades made by referred positions
+    pub trade_count: u64,
+    /// SOL volume of those trades (in lamports)
+    pub referred_volume: u64,
+    /// PDA bump seed
+    pub bump: u8,
+    pub reserved: [u8; 64],
+}
+
+impl Referrer {
+    pub const SEED_PREFIX: &'static str = "referrer";
+
+    // Discriminator (8) + Pubkey (32) + u64 (8) * 5 + u8 (1) + reserved (64)
+    pub const ACCOUNT_SIZE: usize = 8 + 32 + 8 * 5 + 1 + 64;
+
+    /// Record a referred trade of `volume` lamports earning `fee`
+    pub fn record_trade(&mut self, volume: u64, fee: u64) -> Result<()> {
+        self.trade_count = self.trade_count
+            .checked_add(1)
+            .ok_or(CustomError::MathOverflow)?;
+        self.referred_volume = self.referred_volume
+            .checked_add(volume)
+            .ok_or(CustomError::MathOverflow)?;
+        self.accrued_fees = self.accrued_fees
+            .checked_add(fee)
+            .ok_or(CustomError::MathOverflow)?;
+        self.total_earned = self.total_earned
+            .checked_add(fee)
+            .ok_or(CustomError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// A wallet's referral in a pool, bound on its first trade
+///
+/// Kept apart from the position so closing an emptied position doesn't
+/// release the binding.
+#[account]
+pub struct Referral {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    /// Referrer account bound on the first trade (default = no referrer)
+    pub referrer: Pubkey,
+    /// PDA bump seed
+    pub bump: u8,
+    pub reserved: [u8; 32],
+}
+
+impl Referral {
+    pub const SEED_PREFIX: &'static str = "referral";
+
+    // Discriminator (8) + Pubkey (32) * 3 + u8 (1) + reserved (32)
+    pub const ACCOUNT_SIZE: usize = 8 + 32 * 3 + 1 + 32;
+
+    /// Check `referrer` against the wallet's referral, binding it on the
+    /// first trade
+    ///
+    /// Returns whether the referral was bound by this call. Once bound, every
+    /// trade must pass the same referrer; wallets that traded without one
+    /// stay unreferred.
+    pub fn bind(&mut self, referrer: Option<Pubkey>, first_trade: bool) -> Result<bool> {
+        match referrer {
+            Some(referrer) if self.referrer == referrer => Ok(false),
+            Some(referrer) if self.referrer == Pubkey::default() && first_trade => {
+                self.referrer = referrer;
+                Ok(true)
+            }
+            None if self.referrer == Pubkey::default() => Ok(false),
+            _ => err!(CustomError::InvalidReferrer),
+        }
+    }
+}
+
 #[account]
 pub struct LiquidityProvider {
     pub shares: u64, // The number of shares this provider holds in the liquidity pool ( didnt add to contract now )
@@ -138,14 +1678,31 @@ pub struct LiquidityPool {
     pub reserve_one: u64,  // Reserve amount of token_one in the pool
     pub reserve_two: u64,  // Reserve amount of token_two in the pool
     pub bump: u8,          // Nonce for the program-derived address
+    pub version: u8,       // Account layout version (see `LiquidityPool::VERSION`)
+    pub pause_flags: u8,   // Per-pool pause switches (see `PAUSE_*` in consts)
+    pub has_tax_override: bool, // Whether the creator chose this pool's paperhand tax at launch
+    pub tax_override_bps: u16, // Creator-chosen paperhand tax (in basis points)
+    pub creator: Pubkey,   // Launcher of the pool, paid the creator share of the tax (default = none)
+    pub reward_per_token: u128, // Accumulated diamond-hand reward per position token, scaled by REWARD_PRECISION
+    pub reward_tokens: u64, // Position tokens earning diamond-hand rewards
+    pub reward_balance: u64, // Distributed but unclaimed rewards held in the global vault (in lamports)
+    pub cost_basis_mode: CostBasisMode, // Average cost or FIFO lots, chosen at launch
+    pub buyback_balance: u64, // Treasury lamports earmarked for buying back this pool's token
+    pub last_buyback_timestamp: i64, // Time of the latest buyback (0 = never)
+    pub reserved: [u8; 43],
 }
 
 impl LiquidityPool {
     pub const POOL_SEED_PREFIX: &'static str = "liquidity_pool";
+    pub const VERSION: u8 = 1;
 
     // Discriminator (8) + Pubkey (32) + Pubkey (32) + totalsupply (8)
-    // + reserve one (8) + reserve two (8) + Bump (1)
-    pub const ACCOUNT_SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 1;
+    // + reserve one (8) + reserve two (8) + Bump (1) + version (1) + pause flags (1)
+    // + tax override (1 + 2) + creator (32) + reward per token (16) + reward tokens (8)
+    // + reward balance (8) + cost basis mode (1) + buyback balance (8)
+    // + last buyback (8) + reserved (43)
+    pub const ACCOUNT_SIZE: usize =
+        8 + 32 + 32 + 8 + 8 + 8 + 1 + 1 + 1 + 1 + 2 + 32 + 16 + 8 + 8 + 1 + 8 + 8 + 43;
 
     // Helper function to generate a seed for PDAs based on token public keys
     // pub fn generate_seed(token_one: Pubkey, token_two: Pubkey) -> String {
@@ -165,8 +1722,105 @@ impl LiquidityPool {
             reserve_one: 0_u64,
             reserve_two: 0_u64,
             bump: bump,
+            version: LiquidityPool::VERSION,
+            pause_flags: 0,
+            has_tax_override: false,
+            tax_override_bps: 0,
+            creator: Pubkey::default(),
+            reward_per_token: 0,
+            reward_tokens: 0,
+            reward_balance: 0,
+            cost_basis_mode: CostBasisMode::AverageCost,
+            buyback_balance: 0,
+            last_buyback_timestamp: 0,
+            reserved: [0; 43],
         }
     }
+
+    /// Spread `amount` lamports of rewards over the tokens currently earning them
+    ///
+    /// Returns the amount actually distributed: nothing when no position holds
+    /// tokens, and rounding dust is left out.
+    pub fn distribute_rewards(&mut self, amount: u64) -> Result<u64> {
+        if amount == 0 || self.reward_tokens == 0 {
+            return Ok(0);
+        }
+        let per_token = (amount as u128)
+            .checked_mul(REWARD_PRECISION)
+            .ok_or(CustomError::MathOverflow)?
+            / (self.reward_tokens as u128);
+        let distributed = per_token
+            .checked_mul(self.reward_tokens as u128)
+            .ok_or(CustomError::MathOverflow)?
+            / REWARD_PRECISION;
+        let distributed = u64::try_from(distributed).map_err(|_| CustomError::MathOverflow)?;
+
+        self.reward_per_token = self.reward_per_token
+            .checked_add(per_token)
+            .ok_or(CustomError::MathOverflow)?;
+        self.reward_balance = self.reward_balance
+            .checked_add(distributed)
+            .ok_or(CustomError::MathOverflow)?;
+        Ok(distributed)
+    }
+
+    /// Tokens bought from the curve with `sol_in` lamports (after fees)
+    pub fn buy_output(&self, sol_in: u64, decimals: u8) -> Result<u64> {
+        let denominator_sum = self.reserve_two
+            .checked_add(sol_in)
+            .ok_or(CustomError::OverflowOrUnderflowOccurred)?;
+
+        let div_amt = convert_to_float(denominator_sum, decimals)
+            .div(convert_to_float(sol_in, decimals));
+
+        let amount_out_in_float = convert_to_float(self.reserve_one, 9_u8).div(div_amt);
+        Ok(convert_from_float(amount_out_in_float, 9_u8))
+    }
+
+    /// Value of the total supply at the current curve price (in lamports)
+    pub fn market_cap(&self) -> u64 {
+        if self.reserve_one == 0 {
+            return 0;
+        }
+        let market_cap =
+            (self.reserve_two as u128) * (self.total_supply as u128) / (self.reserve_one as u128);
+        market_cap.min(u64::MAX as u128) as u64
+    }
+
+    /// Current curve price in lamports per token unit, scaled by PRICE_PRECISION
+    pub fn spot_price(&self) -> u128 {
+        if self.reserve_one == 0 {
+            return 0;
+        }
+        (self.reserve_two as u128) * PRICE_PRECISION / (self.reserve_one as u128)
+    }
+
+    pub fn has_creator(&self) -> bool {
+        self.creator != Pubkey::default()
+    }
+
+    /// Paperhand tax before holding-time decay: the creator's choice, or the
+    /// global rate for pools launched without one
+    pub fn base_tax_bps(&self, config: &CurveConfiguration) -> u16 {
+        if self.has_tax_override {
+            self.tax_override_bps
+        } else {
+            config.paperhand_tax_bps
+        }
+    }
+
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.pause_flags & flag != 0
+    }
+
+    pub fn set_pause_flags(&mut self, pause_flags: u8) -> Result<()> {
+        // Launching is a global switch only
+        if pause_flags & !(PAUSE_ALL & !PAUSE_LAUNCH) != 0 {
+            return err!(CustomError::InvalidPauseFlags);
+        }
+        self.pause_flags = pause_flags;
+        Ok(())
+    }
 }
 
 pub trait LiquidityPoolAccount<'info> {
@@ -507,12 +2161,7 @@ impl<'info> LiquidityPoolAccount<'info> for Account<'info, LiquidityPool> {
         // dy = yx + ydx - xy / (x + dx)
         // formula => dy = ydx / (x + dx)
 
-        let adjusted_amount_in_float = convert_to_float(amount, token_one_accounts.0.decimals)
-            .div(100_f64)
-            .mul(100_f64.sub(_bonding_configuration_account.fees));
-
-        let adjusted_amount =
-            convert_from_float(adjusted_amount_in_float, token_one_accounts.0.decimals);
+        let adjusted_amount = _bonding_configuration_account.amount_after_fee(amount)?;
 
         if style == 1 {
             let denominator_sum = self
@@ -770,4 +2419,137 @@ pub fn transfer_sol_to_pool<'info>(
         amount,
     )?;
     Ok(())
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn legacy_config(fees: f64, admin_fields: Option<(Pubkey, bool, u64)>) -> Vec<u8> {
+        let mut data = CurveConfiguration::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&fees.to_le_bytes());
+        Pubkey::new_unique().serialize(&mut data).unwrap();
+        4000_u16.serialize(&mut data).unwrap();
+        if let Some((council, paused, accrued_fees)) = admin_fields {
+            council.serialize(&mut data).unwrap();
+            paused.serialize(&mut data).unwrap();
+            accrued_fees.serialize(&mut data).unwrap();
+        }
+        data
+    }
+
+    #[test]
+    fn legacy_fee_percent_converts_to_rounded_bps() {
+        let config = CurveConfiguration::from_legacy(&legacy_config(1.0, None)).unwrap();
+        assert_eq!(config.version, CurveConfiguration::VERSION);
+        assert_eq!(config.fee_bps, 100);
+        assert_eq!(config.paperhand_tax_bps, 4000);
+        assert_eq!(config.max_paperhand_tax_bps, 10000);
+
+        let config = CurveConfiguration::from_legacy(&legacy_config(0.255, None)).unwrap();
+        assert_eq!(config.fee_bps, 26);
+        let config = CurveConfiguration::from_legacy(&legacy_config(100.0, None)).unwrap();
+        assert_eq!(config.fee_bps, 10000);
+    }
+
+    #[test]
+    fn legacy_admin_fields_are_kept() {
+        let council = Pubkey::new_unique();
+        let data = legacy_config(2.5, Some((council, true, 42)));
+        let config = CurveConfiguration::from_legacy(&data).unwrap();
+        assert_eq!(config.fee_bps, 250);
+        assert_eq!(config.admin_council, council);
+        assert_eq!(config.pause_flags, PAUSE_ALL);
+        assert_eq!(config.accrued_fees, 42);
+    }
+
+    #[test]
+    fn legacy_fee_out_of_range_or_nan_is_rejected() {
+        for fees in [-0.5, 100.01, f64::INFINITY] {
+            assert_eq!(
+                CurveConfiguration::from_legacy(&legacy_config(fees, None)).err(),
+                Some(CustomError::InvalidFee.into())
+            );
+        }
+        // Borsh refuses to decode NaN before the range check
+        assert!(CurveConfiguration::from_legacy(&legacy_config(f64::NAN, None)).is_err());
+    }
+
+    #[test]
+    fn truncated_legacy_config_is_rejected() {
+        let data = legacy_config(1.0, None);
+        assert!(CurveConfiguration::from_legacy(&data[..20]).is_err());
+    }
+
+    #[test]
+    fn untracked_proceeds_are_not_realized_profit() {
+        let mut pool = LiquidityPool::new(Pubkey::default(), 255);
+        let mut position = UserPosition::new(Pubkey::default(), Pubkey::default(), 255);
+        position.record_buy(&mut pool, 1_000, 500, 1).unwrap();
+
+        // Tracked tokens sold under their basis, plus untracked tokens for 300
+        position.record_sell(&mut pool, 1_000, 500, 400, 300, 2).unwrap();
+        assert_eq!(position.realized_profit, 0);
+        assert_eq!(position.realized_loss, 100);
+        assert_eq!(position.total_sol_out, 700);
+    }
+
+    #[test]
+    fn symbols_are_upper_case_ascii_letters_and_digits() {
+        assert!(SymbolEntry::validate("PEPE").is_ok());
+        assert!(SymbolEntry::validate("PEPE2").is_ok());
+        for symbol in ["", "pepe", "PEPE ", "PE-PE", "PEP\u{0415}", "ABCDEFGHIJK"] {
+            assert!(SymbolEntry::validate(symbol).is_err(), "{symbol:?}");
+        }
+    }
+
+    #[test]
+    fn vesting_params_must_leave_supply_and_end_after_the_cliff() {
+        let params = |amount, cliff_seconds, duration_seconds| VestingParams {
+            amount,
+            cliff_seconds,
+            duration_seconds,
+        };
+        assert!(params(100, 10, 100).is_valid(1_000));
+        assert!(params(100, 100, 100).is_valid(1_000));
+        assert!(!params(1_000, 10, 100).is_valid(1_000));
+        assert!(!params(1_001, 10, 100).is_valid(1_000));
+        assert!(!params(100, 101, 100).is_valid(1_000));
+        assert!(!params(0, 10, 100).is_valid(1_000));
+        assert!(!params(100, 0, 0).is_valid(1_000));
+    }
+
+    #[test]
+    fn vesting_unlocks_linearly_after_the_cliff() {
+        let escrow = VestingEscrow {
+            mint: Pubkey::default(),
+            pool: Pubkey::default(),
+            beneficiary: Pubkey::default(),
+            total_amount: 1_000,
+            claimed_amount: 100,
+            start_timestamp: 1_000,
+            cliff_timestamp: 1_250,
+            end_timestamp: 2_000,
+            bump: 255,
+            reserved: [0; 32],
+        };
+        assert_eq!(escrow.claimable_amount(1_249), 0);
+        assert_eq!(escrow.vested_amount(1_250), 250);
+        assert_eq!(escrow.claimable_amount(1_500), 400);
+        assert_eq!(escrow.claimable_amount(5_000), 900);
+    }
+
+    #[test]
+    fn unset_tax_bounds_allow_any_rate() {
+        let mut config = CurveConfiguration::new(100, Pubkey::default(), 5000);
+        config.min_paperhand_tax_bps = 0;
+        config.max_paperhand_tax_bps = 0;
+        assert!(config.is_tax_within_bounds(0));
+        assert!(config.is_tax_within_bounds(7500));
+        assert!(!config.is_tax_within_bounds(10001));
+
+        config.set_tax_bounds(1000, 7500).unwrap();
+        assert!(!config.is_tax_within_bounds(500));
+        assert!(config.is_tax_within_bounds(7500));
+        assert!(config.set_tax_bounds(0, 0).is_err());
+    }
+}
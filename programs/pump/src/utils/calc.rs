@@ -7,3 +7,11 @@ pub fn convert_to_float(value: u64, decimals: u8) -> f64 {
 pub fn convert_from_float(value: f64, decimals: u8) -> u64 {
     value.mul(f64::powf(10.0, decimals as f64)) as u64
 }
+
+/// `amount * bps / 10000`, computed in u128 to avoid overflow
+pub fn apply_bps(amount: u64, bps: u16) -> Option<u64> {
+    let value = (amount as u128)
+        .checked_mul(bps as u128)?
+        .checked_div(10000_u128)?;
+    u64::try_from(value).ok()
+}
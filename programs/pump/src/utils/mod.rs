@@ -1,2 +1,6 @@
 pub mod calc;
+pub mod realloc;
+pub mod token;
 pub use calc::*;
+pub use realloc::*;
+pub use token::*;
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Grow a program-owned account to `new_size`, charging any missing rent to `payer`
+pub fn realloc_with_rent_topup<'info>(
+    account: &AccountInfo<'info>,
+    new_size: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let required_lamports = Rent::get()?.minimum_balance(new_size);
+    let current_lamports = account.lamports();
+
+    if required_lamports > current_lamports {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            required_lamports - current_lamports,
+        )?;
+    }
+
+    account.resize(new_size)?;
+    Ok(())
+}
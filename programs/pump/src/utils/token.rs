@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022;
+
+/// Transfer tokens with `transfer_checked` through either token program
+///
+/// `hook_accounts` are the extra accounts of mints with a transfer hook: the
+/// hook program, its validation account and the accounts it lists. They are
+/// ignored for other mints, so instructions pass their remaining accounts.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_tokens<'info>(
+    token_program: &AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    hook_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    decimals: u8,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    spl_token_2022::onchain::invoke_transfer_checked(
+        token_program.key,
+        from,
+        mint,
+        to,
+        authority,
+        hook_accounts,
+        amount,
+        decimals,
+        signer_seeds,
+    )?;
+    Ok(())
+}
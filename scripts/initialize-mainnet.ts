@@ -11,7 +11,7 @@ const RPC_URL = process.env.RPC_URL || "https://api.mainnet-beta.solana.com";
 const WALLET_PATH = "./mainnet-deploy.json"; // Path to your wallet keypair
 
 // Initialization parameters
-const FEE_PERCENT = 1.0; // 1% trading fee
+const FEE_BPS = 100; // 1% trading fee (100 bps)
 const PAPERHAND_TAX_BPS = 5000; // 50% tax (5000 bps)
 
 async function main() {
@@ -53,16 +53,23 @@ async function main() {
         program.programId
     );
 
+    // initialize must be signed by the program upgrade authority
+    const [programData] = PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
+        new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+
     // 4. Initialize
     console.log("Initializing contract...");
 
     try {
         const tx = await program.methods
-            .initialize(FEE_PERCENT, PAPERHAND_TAX_BPS)
+            .initialize(FEE_BPS, PAPERHAND_TAX_BPS)
             .accounts({
                 dexConfigurationAccount: curveConfig,
                 globalAccount: globalAccount,
                 treasuryVault: treasuryVault,
+                programData: programData,
                 admin: wallet.publicKey,
                 rent: anchor.web3.SYSVAR_RENT_PUBKEY,
                 systemProgram: anchor.web3.SystemProgram.programId,
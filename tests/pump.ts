@@ -1,8 +1,8 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Pump } from "../target/types/pump"
-import { Connection, PublicKey, Keypair, SystemProgram, Transaction, sendAndConfirmTransaction, ComputeBudgetProgram, SYSVAR_RENT_PUBKEY, LAMPORTS_PER_SOL } from "@solana/web3.js"
-import { createMint, getOrCreateAssociatedTokenAccount, mintTo, getAssociatedTokenAddress } from "@solana/spl-token"
+import { Connection, PublicKey, Keypair, SystemProgram, Transaction, sendAndConfirmTransaction, ComputeBudgetProgram, SYSVAR_RENT_PUBKEY, SYSVAR_INSTRUCTIONS_PUBKEY, LAMPORTS_PER_SOL } from "@solana/web3.js"
+import { createMint, getOrCreateAssociatedTokenAccount, mintTo, getAssociatedTokenAddress, getAssociatedTokenAddressSync, transfer, createAssociatedTokenAccountIdempotent, createTransferCheckedWithTransferHookInstruction, getMint, getTransferHook, TOKEN_2022_PROGRAM_ID } from "@solana/spl-token"
 import { expect } from "chai";
 import { BN } from "bn.js";
 const keys = require('../keys/users.json');
@@ -15,9 +15,16 @@ const POOL_SEED_PREFIX = "liquidity_pool"
 const LP_SEED_PREFIX = "LiqudityProvider"
 const TREASURY_VAULT_SEED = "treasury_vault"
 const POSITION_SEED = "position"
+const METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s")
+const TRANSFER_HOOK_PROGRAM_ID = new PublicKey("8jL4kHLoeevdgiBs2qQMgyQ9mm5PUua1Qkkei4rm5xue")
 
+// Default trading fee: 1% = 100 bps
+const DEFAULT_FEE_BPS = 100;
 // Default paperhand tax: 50% = 5000 bps
 const DEFAULT_PAPERHAND_TAX_BPS = 5000;
+// Pause switches (PAUSE_* in consts.rs)
+const PAUSE_BUY = 1 << 1;
+const PAUSE_SELL = 1 << 2;
 
 function sleep(ms: number) {
   return new Promise(resolve => setTimeout(resolve, ms));
@@ -30,6 +37,8 @@ describe("PaperHandBitchTax", () => {
 
   // Test users
   const admin = Keypair.fromSecretKey(new Uint8Array(keys))
+  // The provider wallet deploys the program and is its upgrade authority
+  const upgradeAuthority = (anchor.AnchorProvider.env().wallet as anchor.Wallet).payer
   const user2 = Keypair.fromSecretKey(new Uint8Array(key2))
   const tokenDecimal = 6
   const initialTokenSupply = new BN(1_000_000_000).mul(new BN(10 ** tokenDecimal))
@@ -40,6 +49,16 @@ describe("PaperHandBitchTax", () => {
   let poolPda: PublicKey
   let globalAccount: PublicKey
   let treasuryVault: PublicKey
+  let poolStats: PublicKey
+  const [globalStats] = PublicKey.findProgramAddressSync(
+    [Buffer.from("global_stats")],
+    program.programId
+  )
+
+  const [programData] = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+  )
 
   console.log("Admin's wallet address is:", admin.publicKey.toBase58())
   console.log("User2's wallet address is:", user2.publicKey.toBase58())
@@ -74,11 +93,188 @@ describe("PaperHandBitchTax", () => {
     return position;
   };
 
+  // A launched token and its pool
+  type Market = { mint: PublicKey; pool: PublicKey; creator?: PublicKey; fifo?: boolean };
+
+  const getPositionLotsPDA = (position: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from("position_lots"), position.toBuffer()], program.programId)[0];
+
+  const getPoolStatsPDA = (pool: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from("pool_stats"), pool.toBuffer()], program.programId)[0];
+
+  // Referral binding of a wallet in a pool, kept when its position is closed
+  const getReferralPDA = (pool: PublicKey, user: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from("referral"), pool.toBuffer(), user.toBuffer()], program.programId)[0];
+
+  // Helper to buy (style 2) or sell (style 1) through the bonding curve of `market`
+  // FIFO markets pass the position's lots unless `positionLots` says otherwise
+  const swapOn = async (
+    market: Market,
+    user: Keypair,
+    amount: BN,
+    style: number,
+    referrer: PublicKey | null = null,
+    positionLots?: PublicKey | null
+  ) => {
+    const poolTokenOne = await getAssociatedTokenAddress(market.mint, globalAccount, true);
+    const userTokenAta = await getAssociatedTokenAddress(market.mint, user.publicKey);
+    const userPosition = getUserPositionPDA(market.pool, user.publicKey);
+    const tx = new Transaction()
+      .add(
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }),
+        ComputeBudgetProgram.setComputeUnitPrice({ microLamports: 200_000 }),
+        await program.methods
+          .swap(amount, new BN(style))
+          .accounts({
+            dexConfigurationAccount: curveConfig,
+            pool: market.pool,
+            globalAccount: globalAccount,
+            treasuryVault: treasuryVault,
+            creator: market.creator ?? null,
+            referrer: referrer,
+            referral: getReferralPDA(market.pool, user.publicKey),
+            userPosition: userPosition,
+            positionLots: positionLots !== undefined
+              ? positionLots
+              : market.fifo ? getPositionLotsPDA(userPosition) : null,
+            poolStats: getPoolStatsPDA(market.pool),
+            globalStats: globalStats,
+            mintTokenOne: market.mint,
+            poolTokenAccountOne: poolTokenOne,
+            userTokenAccountOne: userTokenAta,
+            user: user.publicKey,
+            rent: SYSVAR_RENT_PUBKEY,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_PROGRAM_ID
+          })
+          .instruction()
+      );
+    tx.feePayer = user.publicKey;
+    tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+    return sendAndConfirmTransaction(connection, tx, [user], { skipPreflight: true });
+  };
+
+  // Helper to trade on the pool created in the setup
+  const swap = (user: Keypair, amount: BN, style: number, referrer: PublicKey | null = null) =>
+    swapOn({ mint: mint1, pool: poolPda }, user, amount, style, referrer);
+
   // Helper to get treasury balance
   const getTreasuryBalance = async () => {
     return await connection.getBalance(treasuryVault);
   };
 
+  // Helper to decode the events emitted by a confirmed transaction
+  const getEvents = async (signature: string) => {
+    const tx = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0
+    });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    return Array.from(parser.parseLogs(tx.meta.logMessages));
+  };
+
+  const getEvent = async (signature: string, name: string) =>
+    (await getEvents(signature)).find(event => event.name === name).data as any;
+
+  // Helper to airdrop SOL to a fresh wallet and open its mint1 token account
+  const fundWallet = async (wallet: Keypair, sol = 5) => {
+    const sig = await connection.requestAirdrop(wallet.publicKey, sol * LAMPORTS_PER_SOL);
+    const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
+    await connection.confirmTransaction({ blockhash, lastValidBlockHeight, signature: sig }, 'finalized');
+    await getOrCreateAssociatedTokenAccount(connection, wallet, mint1, wallet.publicKey);
+  };
+
+  type LaunchOptions = {
+    paperhandTaxBps?: number;
+    costBasisMode?: any;
+    initialBuySol?: BN;
+    creatorVesting?: { amount: BN; cliffSeconds: number; durationSeconds: number };
+  };
+
+  const getCreatorProfilePDA = (creator: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from("creator"), creator.toBuffer()], program.programId)[0];
+
+  const getVestingEscrowPDA = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from("vesting"), mint.toBuffer()], program.programId)[0];
+
+  // Helper to launch a token through `launch`, returning its market and the
+  // transaction signature; the creator pays a 1 SOL initial reserve
+  const launchToken = async (creator: Keypair, symbol: string, options: LaunchOptions = {}) => {
+    const profile = await program.account.creatorProfile.fetchNullable(getCreatorProfilePDA(creator.publicKey));
+    const launchIndex = profile ? profile.launchCount : new BN(0);
+    const [mint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint"), creator.publicKey.toBuffer(), launchIndex.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [pool] = PublicKey.findProgramAddressSync(
+      [Buffer.from(POOL_SEED_PREFIX), mint.toBuffer()],
+      program.programId
+    );
+    const [metadata] = PublicKey.findProgramAddressSync(
+      [Buffer.from("metadata"), METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+      METADATA_PROGRAM_ID
+    );
+    const costBasisMode = options.costBasisMode ?? { averageCost: {} };
+    const initialBuy = options.initialBuySol !== undefined;
+    const vesting = options.creatorVesting !== undefined;
+    const creatorPosition = getUserPositionPDA(pool, creator.publicKey);
+    const vestingEscrow = getVestingEscrowPDA(mint);
+
+    const tx = new Transaction()
+      .add(
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 600_000 }),
+        await program.methods
+          .launch(
+            `${symbol} token`,
+            symbol,
+            "https://example.com/token.json",
+            tokenDecimal,
+            initialTokenSupply,
+            new BN(LAMPORTS_PER_SOL),
+            options.paperhandTaxBps ?? null,
+            costBasisMode,
+            options.initialBuySol ?? null,
+            options.creatorVesting ?? null
+          )
+          .accounts({
+            dexConfigurationAccount: curveConfig,
+            creatorProfile: getCreatorProfilePDA(creator.publicKey),
+            mint: mint,
+            metadata: metadata,
+            pool: pool,
+            launchRecord: PublicKey.findProgramAddressSync([Buffer.from("launch"), mint.toBuffer()], program.programId)[0],
+            globalStats: globalStats,
+            symbolRegistry: PublicKey.findProgramAddressSync([Buffer.from("symbol_registry")], program.programId)[0],
+            symbolEntry: PublicKey.findProgramAddressSync(
+              [Buffer.from("symbol"), Buffer.from(symbol.toUpperCase())],
+              program.programId
+            )[0],
+            globalAccount: globalAccount,
+            poolTokenAccount: getAssociatedTokenAddressSync(mint, globalAccount, true),
+            creatorPosition: initialBuy ? creatorPosition : null,
+            creatorPositionLots: initialBuy && costBasisMode.fifo ? getPositionLotsPDA(creatorPosition) : null,
+            poolStats: initialBuy ? getPoolStatsPDA(pool) : null,
+            creatorTokenAccount: initialBuy ? getAssociatedTokenAddressSync(mint, creator.publicKey) : null,
+            vestingEscrow: vesting ? vestingEscrow : null,
+            vestingTokenAccount: vesting ? getAssociatedTokenAddressSync(mint, vestingEscrow, true) : null,
+            creator: creator.publicKey,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+            metadataProgram: METADATA_PROGRAM_ID,
+            rent: SYSVAR_RENT_PUBKEY
+          })
+          .instruction()
+      );
+    tx.feePayer = creator.publicKey;
+    tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+    const signature = await sendAndConfirmTransaction(connection, tx, [creator], { skipPreflight: true });
+
+    const market: Market = { mint, pool, creator: creator.publicKey, fifo: costBasisMode.fifo !== undefined };
+    return { market, signature, launchIndex };
+  };
+
   it("Airdrop SOL to admin wallet", async () => {
     console.log(`Requesting airdrop to admin: ${admin.publicKey.toBase58()}`);
     const signature = await connection.requestAirdrop(admin.publicKey, 10 * LAMPORTS_PER_SOL);
@@ -113,45 +309,90 @@ describe("PaperHandBitchTax", () => {
     poolPda = pdas.pool;
     globalAccount = pdas.global;
     treasuryVault = pdas.treasury;
+    poolStats = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool_stats"), poolPda.toBuffer()],
+      program.programId
+    )[0];
+  });
+
+  it("Should fail: Initialize from a wallet that is not the upgrade authority", async () => {
+    try {
+      await program.methods
+        .initialize(DEFAULT_FEE_BPS, DEFAULT_PAPERHAND_TAX_BPS)
+        .accounts({
+          dexConfigurationAccount: curveConfig,
+          globalAccount: globalAccount,
+          treasuryVault: treasuryVault,
+          programData: programData,
+          admin: user2.publicKey,
+          rent: SYSVAR_RENT_PUBKEY,
+          systemProgram: SystemProgram.programId
+        })
+        .signers([user2])
+        .rpc();
+      expect.fail("Should have thrown Unauthorized error");
+    } catch (error: any) {
+      expect(error.message).to.include("Unauthorized");
+    }
   });
 
   it("Initialize the contract with PaperHandBitchTax (50%)", async () => {
     try {
       const tx = new Transaction()
         .add(
-          ComputeBudgetProgram.setComputeUnitLimit({ units: 50_000 }),
+          ComputeBudgetProgram.setComputeUnitLimit({ units: 100_000 }),
           ComputeBudgetProgram.setComputeUnitPrice({ microLamports: 1200_000 }),
           await program.methods
-            .initialize(1, DEFAULT_PAPERHAND_TAX_BPS) // 1% trading fee, 50% paperhand tax
+            .initialize(DEFAULT_FEE_BPS, DEFAULT_PAPERHAND_TAX_BPS) // 1% trading fee, 50% paperhand tax
             .accounts({
               dexConfigurationAccount: curveConfig,
               globalAccount: globalAccount,
               treasuryVault: treasuryVault,
-              admin: admin.publicKey,
+              programData: programData,
+              admin: upgradeAuthority.publicKey,
               rent: SYSVAR_RENT_PUBKEY,
               systemProgram: SystemProgram.programId
             })
             .instruction()
         );
-      tx.feePayer = admin.publicKey;
+      tx.feePayer = upgradeAuthority.publicKey;
       tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
-      const sig = await sendAndConfirmTransaction(connection, tx, [admin], { skipPreflight: true });
+      const sig = await sendAndConfirmTransaction(connection, tx, [upgradeAuthority], { skipPreflight: true });
       console.log("Successfully initialized with PaperHandBitchTax:", sig);
 
       const configAccount = await program.account.curveConfiguration.fetch(curveConfig);
       console.log("Config state:", {
-        fees: configAccount.fees,
+        version: configAccount.version,
+        feeBps: configAccount.feeBps,
         treasury: configAccount.treasury.toBase58(),
         paperhandTaxBps: configAccount.paperhandTaxBps
       });
 
       expect(configAccount.paperhandTaxBps).to.equal(DEFAULT_PAPERHAND_TAX_BPS);
+      expect(configAccount.feeBps).to.equal(DEFAULT_FEE_BPS);
+      expect(configAccount.treasury.toBase58()).to.equal(treasuryVault.toBase58());
     } catch (error) {
       console.log("Error in initialization:", error);
       throw error;
     }
   });
 
+  it("Initialize global statistics", async () => {
+    await program.methods
+      .initializeGlobalStats()
+      .accounts({
+        globalStats: globalStats,
+        payer: admin.publicKey,
+        systemProgram: SystemProgram.programId
+      })
+      .signers([admin])
+      .rpc();
+
+    const stats = await program.account.globalStats.fetch(globalStats);
+    expect(stats.totalVolume.toNumber()).to.equal(0);
+    expect(stats.rankedCount).to.equal(0);
+  });
+
   it("Add liquidity to pool", async () => {
     try {
       const [liquidityProviderAccount] = PublicKey.findProgramAddressSync(
@@ -168,6 +409,7 @@ describe("PaperHandBitchTax", () => {
           await program.methods
             .addLiquidity(new BN(1_000_000_000_000_000), new BN(30 * LAMPORTS_PER_SOL)) // Large token reserve, 30 SOL
             .accounts({
+              dexConfigurationAccount: curveConfig,
               pool: poolPda,
               globalAccount: globalAccount,
               mintTokenOne: mint1,
@@ -227,7 +469,13 @@ describe("PaperHandBitchTax", () => {
               pool: poolPda,
               globalAccount: globalAccount,
               treasuryVault: treasuryVault,
+              creator: null,
+              referrer: null,
+              referral: getReferralPDA(poolPda, user2.publicKey),
               userPosition: userPositionPDA,
+              positionLots: null,
+              poolStats: poolStats,
+              globalStats: globalStats,
               mintTokenOne: mint1,
               poolTokenAccountOne: poolTokenOne,
               userTokenAccountOne: user2TokenAta,
@@ -257,6 +505,9 @@ describe("PaperHandBitchTax", () => {
 
       expect(position.totalTokens.toNumber()).to.be.greaterThan(0);
       expect(position.totalSol.toNumber()).to.equal(buyAmount.toNumber());
+      expect(position.version).to.equal(1);
+      expect(position.owner.toBase58()).to.equal(user2.publicKey.toBase58());
+      expect(position.pool.toBase58()).to.equal(poolPda.toBase58());
     });
 
     it("Simulate price drop: Admin sells heavily to move price down", async () => {
@@ -278,7 +529,13 @@ describe("PaperHandBitchTax", () => {
               pool: poolPda,
               globalAccount: globalAccount,
               treasuryVault: treasuryVault,
+              creator: null,
+              referrer: null,
+              referral: getReferralPDA(poolPda, admin.publicKey),
               userPosition: adminPositionPDA,
+              positionLots: null,
+              poolStats: poolStats,
+              globalStats: globalStats,
               mintTokenOne: mint1,
               poolTokenAccountOne: poolTokenOne,
               userTokenAccountOne: adminTokenAta,
@@ -309,7 +566,13 @@ describe("PaperHandBitchTax", () => {
               pool: poolPda,
               globalAccount: globalAccount,
               treasuryVault: treasuryVault,
+              creator: null,
+              referrer: null,
+              referral: getReferralPDA(poolPda, admin.publicKey),
               userPosition: adminPositionPDA,
+              positionLots: null,
+              poolStats: poolStats,
+              globalStats: globalStats,
               mintTokenOne: mint1,
               poolTokenAccountOne: poolTokenOne,
               userTokenAccountOne: adminTokenAta,
@@ -357,7 +620,13 @@ describe("PaperHandBitchTax", () => {
               pool: poolPda,
               globalAccount: globalAccount,
               treasuryVault: treasuryVault,
+              creator: null,
+              referrer: null,
+              referral: getReferralPDA(poolPda, user2.publicKey),
               userPosition: userPositionPDA,
+              positionLots: null,
+              poolStats: poolStats,
+              globalStats: globalStats,
               mintTokenOne: mint1,
               poolTokenAccountOne: poolTokenOne,
               userTokenAccountOne: user2TokenAta,
@@ -423,7 +692,13 @@ describe("PaperHandBitchTax", () => {
               pool: poolPda,
               globalAccount: globalAccount,
               treasuryVault: treasuryVault,
+              creator: null,
+              referrer: null,
+              referral: getReferralPDA(poolPda, user2.publicKey),
               userPosition: userPositionPDA,
+              positionLots: null,
+              poolStats: poolStats,
+              globalStats: globalStats,
               mintTokenOne: mint1,
               poolTokenAccountOne: poolTokenOne,
               userTokenAccountOne: user2TokenAta,
@@ -459,7 +734,13 @@ describe("PaperHandBitchTax", () => {
               pool: poolPda,
               globalAccount: globalAccount,
               treasuryVault: treasuryVault,
+              creator: null,
+              referrer: null,
+              referral: getReferralPDA(poolPda, admin.publicKey),
               userPosition: adminPositionPDA,
+              positionLots: null,
+              poolStats: poolStats,
+              globalStats: globalStats,
               mintTokenOne: mint1,
               poolTokenAccountOne: poolTokenOne,
               userTokenAccountOne: adminTokenAta,
@@ -505,7 +786,13 @@ describe("PaperHandBitchTax", () => {
               pool: poolPda,
               globalAccount: globalAccount,
               treasuryVault: treasuryVault,
+              creator: null,
+              referrer: null,
+              referral: getReferralPDA(poolPda, user2.publicKey),
               userPosition: userPositionPDA,
+              positionLots: null,
+              poolStats: poolStats,
+              globalStats: globalStats,
               mintTokenOne: mint1,
               poolTokenAccountOne: poolTokenOne,
               userTokenAccountOne: user2TokenAta,
@@ -563,7 +850,13 @@ describe("PaperHandBitchTax", () => {
                 pool: poolPda,
                 globalAccount: globalAccount,
                 treasuryVault: treasuryVault,
+                creator: null,
+                referrer: null,
+                referral: getReferralPDA(poolPda, newUser.publicKey),
                 userPosition: userPositionPDA,
+                positionLots: null,
+                poolStats: poolStats,
+                globalStats: globalStats,
                 mintTokenOne: mint1,
                 poolTokenAccountOne: poolTokenOne,
                 userTokenAccountOne: newUserTokenAta,
@@ -607,7 +900,13 @@ describe("PaperHandBitchTax", () => {
                 pool: poolPda,
                 globalAccount: globalAccount,
                 treasuryVault: treasuryVault,
+                creator: null,
+                referrer: null,
+                referral: getReferralPDA(poolPda, user2.publicKey),
                 userPosition: userPositionPDA,
+                positionLots: null,
+                poolStats: poolStats,
+                globalStats: globalStats,
                 mintTokenOne: mint1,
                 poolTokenAccountOne: poolTokenOne,
                 userTokenAccountOne: user2TokenAta,
@@ -653,7 +952,13 @@ describe("PaperHandBitchTax", () => {
               pool: poolPda,
               globalAccount: globalAccount,
               treasuryVault: treasuryVault,
+              creator: null,
+              referrer: null,
+              referral: getReferralPDA(poolPda, newUser.publicKey),
               userPosition: userPositionPDA,
+              positionLots: null,
+              poolStats: poolStats,
+              globalStats: globalStats,
               mintTokenOne: mint1,
               poolTokenAccountOne: poolTokenOne,
               userTokenAccountOne: newUserTokenAta,
@@ -686,7 +991,13 @@ describe("PaperHandBitchTax", () => {
               pool: poolPda,
               globalAccount: globalAccount,
               treasuryVault: treasuryVault,
+              creator: null,
+              referrer: null,
+              referral: getReferralPDA(poolPda, newUser.publicKey),
               userPosition: userPositionPDA,
+              positionLots: null,
+              poolStats: poolStats,
+              globalStats: globalStats,
               mintTokenOne: mint1,
               poolTokenAccountOne: poolTokenOne,
               userTokenAccountOne: newUserTokenAta,
@@ -723,7 +1034,13 @@ describe("PaperHandBitchTax", () => {
               pool: poolPda,
               globalAccount: globalAccount,
               treasuryVault: treasuryVault,
+              creator: null,
+              referrer: null,
+              referral: getReferralPDA(poolPda, newUser.publicKey),
               userPosition: userPositionPDA,
+              positionLots: null,
+              poolStats: poolStats,
+              globalStats: globalStats,
               mintTokenOne: mint1,
               poolTokenAccountOne: poolTokenOne,
               userTokenAccountOne: newUserTokenAta,
@@ -772,7 +1089,13 @@ describe("PaperHandBitchTax", () => {
               pool: poolPda,
               globalAccount: globalAccount,
               treasuryVault: treasuryVault,
+              creator: null,
+              referrer: null,
+              referral: getReferralPDA(poolPda, user2.publicKey),
               userPosition: userPositionPDA,
+              positionLots: null,
+              poolStats: poolStats,
+              globalStats: globalStats,
               mintTokenOne: mint1,
               poolTokenAccountOne: poolTokenOne,
               userTokenAccountOne: user2TokenAta,
@@ -805,7 +1128,13 @@ describe("PaperHandBitchTax", () => {
               pool: poolPda,
               globalAccount: globalAccount,
               treasuryVault: treasuryVault,
+              creator: null,
+              referrer: null,
+              referral: getReferralPDA(poolPda, user2.publicKey),
               userPosition: userPositionPDA,
+              positionLots: null,
+              poolStats: poolStats,
+              globalStats: globalStats,
               mintTokenOne: mint1,
               poolTokenAccountOne: poolTokenOne,
               userTokenAccountOne: user2TokenAta,
@@ -831,4 +1160,2156 @@ describe("PaperHandBitchTax", () => {
       expect(positionAfterSellAll.totalSol.toNumber()).to.equal(0);
     });
   });
+
+  describe("Protocol fees", () => {
+    it("Buys price the curve on the SOL after fees and accrue the fee separately", async () => {
+      const amount = new BN(0.1 * LAMPORTS_PER_SOL);
+      const fee = amount.muln(DEFAULT_FEE_BPS).divn(10000);
+      const adjusted = amount.sub(fee);
+      const poolBefore = await program.account.liquidityPool.fetch(poolPda);
+      const configBefore = await program.account.curveConfiguration.fetch(curveConfig);
+
+      await swap(user2, amount, 2);
+
+      const poolAfter = await program.account.liquidityPool.fetch(poolPda);
+      const configAfter = await program.account.curveConfiguration.fetch(curveConfig);
+      // Only the SOL after fees enters the reserve; the fee is withdrawable
+      expect(poolAfter.reserveTwo.sub(poolBefore.reserveTwo).toString()).to.equal(adjusted.toString());
+      expect(configAfter.accruedFees.sub(configBefore.accruedFees).toString()).to.equal(fee.toString());
+
+      // Tokens out follow the curve for the SOL after fees
+      const tokensOut = poolBefore.reserveOne.sub(poolAfter.reserveOne);
+      const expected = poolBefore.reserveOne.mul(adjusted).div(poolBefore.reserveTwo.add(adjusted));
+      expect(tokensOut.sub(expected).abs().toNumber()).to.be.at.most(1);
+      const priceIfFeeEntered = poolBefore.reserveTwo.add(amount).muln(1_000_000)
+        .div(poolBefore.reserveOne.sub(tokensOut));
+      const price = poolAfter.reserveTwo.muln(1_000_000).div(poolAfter.reserveOne);
+      expect(price.lt(priceIfFeeEntered)).to.equal(true);
+
+      const position = await program.account.userPosition.fetch(getUserPositionPDA(poolPda, user2.publicKey));
+      await swap(user2, position.totalTokens, 1);
+    });
+  });
+
+  describe("Pause switches", () => {
+    const setPause = (pauseFlags: number, pool: PublicKey | null) =>
+      program.methods
+        .setPause(pauseFlags)
+        .accounts({
+          dexConfigurationAccount: curveConfig,
+          pool: pool,
+          programData: programData,
+          adminCouncil: null,
+          admin: upgradeAuthority.publicKey
+        })
+        .signers([upgradeAuthority])
+        .rpc();
+
+    it("Halting buys still lets holders sell", async () => {
+      await swap(user2, new BN(0.1 * LAMPORTS_PER_SOL), 2);
+
+      await setPause(PAUSE_BUY, null);
+      const configAccount = await program.account.curveConfiguration.fetch(curveConfig);
+      expect(configAccount.pauseFlags).to.equal(PAUSE_BUY);
+
+      try {
+        await swap(user2, new BN(0.1 * LAMPORTS_PER_SOL), 2);
+        expect.fail("Should have thrown ProgramPaused error");
+      } catch (error: any) {
+        expect(error.message).to.include("ProgramPaused");
+      }
+
+      const position = await program.account.userPosition.fetch(getUserPositionPDA(poolPda, user2.publicKey));
+      await swap(user2, position.totalTokens, 1);
+
+      await setPause(0, null);
+    });
+
+    it("Per-pool switches only affect that pool", async () => {
+      await swap(user2, new BN(0.1 * LAMPORTS_PER_SOL), 2);
+
+      await setPause(PAUSE_SELL, poolPda);
+      const poolAccount = await program.account.liquidityPool.fetch(poolPda);
+      expect(poolAccount.pauseFlags).to.equal(PAUSE_SELL);
+
+      const position = await program.account.userPosition.fetch(getUserPositionPDA(poolPda, user2.publicKey));
+      try {
+        await swap(user2, position.totalTokens, 1);
+        expect.fail("Should have thrown PoolPaused error");
+      } catch (error: any) {
+        expect(error.message).to.include("PoolPaused");
+      }
+
+      await setPause(0, poolPda);
+      await swap(user2, position.totalTokens, 1);
+    });
+  });
+
+  describe("Paperhand tax settings", () => {
+    const setTaxSchedule = (brackets: { minHoldSeconds: number; taxBps: number }[]) =>
+      program.methods
+        .setTaxSchedule(brackets)
+        .accounts({
+          dexConfigurationAccount: curveConfig,
+          programData: programData,
+          admin: upgradeAuthority.publicKey
+        })
+        .signers([upgradeAuthority])
+        .rpc();
+
+    const decayingSchedule = [
+      { minHoldSeconds: 3_600, taxBps: 4000 },
+      { minHoldSeconds: 86_400, taxBps: 2500 },
+      { minHoldSeconds: 604_800, taxBps: 1000 },
+    ];
+    const holder = Keypair.generate();
+
+    it("Positions track a weighted entry timestamp", async () => {
+      await fundWallet(holder);
+
+      const first = await getEvent(await swap(holder, new BN(0.1 * LAMPORTS_PER_SOL), 2), "tradeExecuted");
+      await sleep(2000);
+      const second = await getEvent(await swap(holder, new BN(0.3 * LAMPORTS_PER_SOL), 2), "tradeExecuted");
+      expect(second.timestamp.toNumber()).to.be.greaterThan(first.timestamp.toNumber());
+
+      // Each buy's time weighted by the tokens it bought
+      const expected = first.timestamp.mul(first.tokenAmount)
+        .add(second.timestamp.mul(second.tokenAmount))
+        .div(first.tokenAmount.add(second.tokenAmount));
+
+      const position = await program.account.userPosition.fetch(getUserPositionPDA(poolPda, holder.publicKey));
+      expect(position.totalTokens.toString()).to.equal(first.tokenAmount.add(second.tokenAmount).toString());
+      expect(position.entryTimestamp.toString()).to.equal(expected.toString());
+    });
+
+    it("Should fail: Brackets out of holding-time order", async () => {
+      try {
+        await setTaxSchedule([
+          { minHoldSeconds: 86_400, taxBps: 2500 },
+          { minHoldSeconds: 3_600, taxBps: 4000 },
+        ]);
+        expect.fail("Should have thrown InvalidTaxSchedule error");
+      } catch (error: any) {
+        expect(error.message).to.include("InvalidTaxSchedule");
+      }
+    });
+
+    it("Upgrade authority sets a decaying schedule", async () => {
+      await setTaxSchedule(decayingSchedule);
+
+      const configAccount = await program.account.curveConfiguration.fetch(curveConfig);
+      expect(configAccount.taxBracketCount).to.equal(3);
+      expect(configAccount.taxBrackets[0].minHoldSeconds).to.equal(3_600);
+      expect(configAccount.taxBrackets[2].taxBps).to.equal(1000);
+      // Sales held under an hour still pay the base rate
+      expect(configAccount.paperhandTaxBps).to.equal(DEFAULT_PAPERHAND_TAX_BPS);
+    });
+
+    it("A longer hold sells at a lower bracket rate", async () => {
+      await setTaxSchedule([{ minHoldSeconds: 5, taxBps: 1000 }]);
+
+      const trader = Keypair.generate();
+      await fundWallet(trader);
+      await swap(trader, new BN(0.2 * LAMPORTS_PER_SOL), 2);
+      const positionPda = getUserPositionPDA(poolPda, trader.publicKey);
+      const { totalTokens } = await program.account.userPosition.fetch(positionPda);
+
+      // Sold right away, under the first bracket: the base rate
+      const quick = await getEvent(await swap(trader, totalTokens.divn(2), 1), "paperhandTaxApplied");
+      expect(quick.holdingSeconds.toNumber()).to.be.lessThan(5);
+      expect(quick.taxBps).to.equal(DEFAULT_PAPERHAND_TAX_BPS);
+
+      await sleep(6000);
+
+      const remaining = (await program.account.userPosition.fetch(positionPda)).totalTokens;
+      const held = await getEvent(await swap(trader, remaining, 1), "paperhandTaxApplied");
+      expect(held.holdingSeconds.toNumber()).to.be.at.least(5);
+      expect(held.taxBps).to.equal(1000);
+      expect(held.taxBps).to.be.lessThan(quick.taxBps);
+
+      // Restore the decaying schedule for the remaining tests
+      await setTaxSchedule(decayingSchedule);
+    });
+
+    it("Upgrade authority sets the launch-time tax bounds", async () => {
+      const setTaxBounds = (minBps: number, maxBps: number) =>
+        program.methods
+          .setTaxBounds(minBps, maxBps)
+          .accounts({
+            dexConfigurationAccount: curveConfig,
+            programData: programData,
+            admin: upgradeAuthority.publicKey
+          })
+          .signers([upgradeAuthority])
+          .rpc();
+
+      try {
+        await setTaxBounds(6000, 2000);
+        expect.fail("Should have thrown InvalidTaxBps error");
+      } catch (error: any) {
+        expect(error.message).to.include("InvalidTaxBps");
+      }
+
+      await setTaxBounds(1000, 7500);
+
+      const configAccount = await program.account.curveConfiguration.fetch(curveConfig);
+      expect(configAccount.minPaperhandTaxBps).to.equal(1000);
+      expect(configAccount.maxPaperhandTaxBps).to.equal(7500);
+    });
+
+    it("Upgrade authority switches the tax formula", async () => {
+      const setTaxMode = (taxMode: any, lossBrackets: { minLossBps: number; taxBps: number }[]) =>
+        program.methods
+          .setTaxMode(taxMode, lossBrackets)
+          .accounts({
+            dexConfigurationAccount: curveConfig,
+            programData: programData,
+            admin: upgradeAuthority.publicKey
+          })
+          .signers([upgradeAuthority])
+          .rpc();
+
+      try {
+        await setTaxMode({ progressiveByLoss: {} }, []);
+        expect.fail("Should have thrown InvalidTaxSchedule error");
+      } catch (error: any) {
+        expect(error.message).to.include("InvalidTaxSchedule");
+      }
+
+      await setTaxMode({ progressiveByLoss: {} }, [
+        { minLossBps: 500, taxBps: 1000 },
+        { minLossBps: 2000, taxBps: 3000 },
+        { minLossBps: 5000, taxBps: 5000 },
+      ]);
+
+      let configAccount = await program.account.curveConfiguration.fetch(curveConfig);
+      expect(configAccount.taxMode).to.deep.equal({ progressiveByLoss: {} });
+      expect(configAccount.lossBracketCount).to.equal(3);
+      expect(configAccount.lossBrackets[1].minLossBps).to.equal(2000);
+
+      // Restore today's behavior for the remaining tests
+      await setTaxMode({ flatOnProceeds: {} }, []);
+      configAccount = await program.account.curveConfiguration.fetch(curveConfig);
+      expect(configAccount.taxMode).to.deep.equal({ flatOnProceeds: {} });
+    });
+
+    it("Upgrade authority splits the tax between treasury, reserve and creator", async () => {
+      const setTaxSplit = (treasuryBps: number, reserveBps: number, creatorBps: number) =>
+        program.methods
+          .setTaxSplit(treasuryBps, reserveBps, creatorBps, 0)
+          .accounts({
+            dexConfigurationAccount: curveConfig,
+            programData: programData,
+            admin: upgradeAuthority.publicKey
+          })
+          .signers([upgradeAuthority])
+          .rpc();
+
+      try {
+        await setTaxSplit(5000, 3000, 1000);
+        expect.fail("Should have thrown InvalidTaxSplit error");
+      } catch (error: any) {
+        expect(error.message).to.include("InvalidTaxSplit");
+      }
+
+      await setTaxSplit(5000, 3000, 2000);
+
+      const configAccount = await program.account.curveConfiguration.fetch(curveConfig);
+      expect(configAccount.taxTreasuryBps).to.equal(5000);
+      expect(configAccount.taxReserveBps).to.equal(3000);
+      expect(configAccount.taxCreatorBps).to.equal(2000);
+
+      // Restore the treasury-only split for the remaining tests
+      await setTaxSplit(10000, 0, 0);
+    });
+
+    it("Pools launched without an override use the global rate", async () => {
+      const poolAccount = await program.account.liquidityPool.fetch(poolPda);
+      expect(poolAccount.hasTaxOverride).to.equal(false);
+    });
+
+    it("Pools track average cost basis unless FIFO lots are chosen", async () => {
+      const poolAccount = await program.account.liquidityPool.fetch(poolPda);
+      expect(poolAccount.costBasisMode).to.deep.equal({ averageCost: {} });
+    });
+  });
+
+  describe("Diamond-hand rewards", () => {
+    const whale = Keypair.generate();
+    const holder = Keypair.generate();
+
+    const setTaxSplit = (treasuryBps: number, reserveBps: number, creatorBps: number, rewardBps: number) =>
+      program.methods
+        .setTaxSplit(treasuryBps, reserveBps, creatorBps, rewardBps)
+        .accounts({
+          dexConfigurationAccount: curveConfig,
+          programData: programData,
+          admin: upgradeAuthority.publicKey
+        })
+        .signers([upgradeAuthority])
+        .rpc();
+
+    const claim = (user: Keypair) =>
+      program.methods
+        .claimDiamondRewards()
+        .accounts({
+          pool: poolPda,
+          userPosition: getUserPositionPDA(poolPda, user.publicKey),
+          globalAccount: globalAccount,
+          user: user.publicKey,
+          systemProgram: SystemProgram.programId
+        })
+        .signers([user])
+        .rpc();
+
+    it("Setup: Fund whale and holder wallets", async () => {
+      for (const wallet of [whale, holder]) {
+        const sig = await connection.requestAirdrop(wallet.publicKey, 5 * LAMPORTS_PER_SOL);
+        const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
+        await connection.confirmTransaction({ blockhash, lastValidBlockHeight, signature: sig }, 'finalized');
+        await getOrCreateAssociatedTokenAccount(connection, wallet, mint1, wallet.publicKey);
+      }
+
+      await setTaxSplit(5000, 0, 0, 5000);
+    });
+
+    it("Holders earn a share of paperhand taxes paid after they bought", async () => {
+      await swap(holder, new BN(0.5 * LAMPORTS_PER_SOL), 2);
+      await swap(user2, new BN(0.5 * LAMPORTS_PER_SOL), 2);
+      await swap(whale, new BN(2 * LAMPORTS_PER_SOL), 2);
+
+      const holderPosition = await program.account.userPosition.fetch(getUserPositionPDA(poolPda, holder.publicKey));
+      expect(holderPosition.rewardsEnrolled).to.equal(true);
+
+      // Whale dumps, then user2 sells at a loss and pays the tax
+      const whalePosition = await program.account.userPosition.fetch(getUserPositionPDA(poolPda, whale.publicKey));
+      await swap(whale, whalePosition.totalTokens, 1);
+      const poolBefore = await program.account.liquidityPool.fetch(poolPda);
+      const user2Position = await program.account.userPosition.fetch(getUserPositionPDA(poolPda, user2.publicKey));
+      await swap(user2, user2Position.totalTokens, 1);
+
+      const poolAfter = await program.account.liquidityPool.fetch(poolPda);
+      expect(poolAfter.rewardPerToken.gt(poolBefore.rewardPerToken)).to.equal(true);
+      expect(poolAfter.rewardBalance.gt(poolBefore.rewardBalance)).to.equal(true);
+
+      await claim(holder);
+      const poolAfterClaim = await program.account.liquidityPool.fetch(poolPda);
+      expect(poolAfterClaim.rewardBalance.lt(poolAfter.rewardBalance)).to.equal(true);
+
+      const claimed = await program.account.userPosition.fetch(getUserPositionPDA(poolPda, holder.publicKey));
+      expect(claimed.pendingRewards.toNumber()).to.equal(0);
+    });
+
+    it("Should fail: Claim with nothing earned", async () => {
+      try {
+        await claim(holder);
+        expect.fail("Should have thrown NothingToClaim error");
+      } catch (error: any) {
+        expect(error.message).to.include("NothingToClaim");
+      }
+
+      await setTaxSplit(10000, 0, 0, 0);
+    });
+  });
+
+  describe("Position transfers", () => {
+    const sender = Keypair.generate();
+    const recipient = Keypair.generate();
+
+    const transferPosition = (owner: Keypair, to: PublicKey, amount: BN) =>
+      program.methods
+        .transferPosition(amount)
+        .accounts({
+          pool: poolPda,
+          mintTokenOne: mint1,
+          sourcePosition: getUserPositionPDA(poolPda, owner.publicKey),
+          destinationPosition: getUserPositionPDA(poolPda, to),
+          poolStats,
+          sourceLots: null,
+          destinationLots: null,
+          ownerTokenAccount: getAssociatedTokenAddressSync(mint1, owner.publicKey),
+          recipientTokenAccount: getAssociatedTokenAddressSync(mint1, to),
+          recipient: to,
+          owner: owner.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_PROGRAM_ID
+        })
+        .signers([owner])
+        .rpc();
+
+    it("Setup: Fund sender and buy through the curve", async () => {
+      const sig = await connection.requestAirdrop(sender.publicKey, 3 * LAMPORTS_PER_SOL);
+      const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
+      await connection.confirmTransaction({ blockhash, lastValidBlockHeight, signature: sig }, 'finalized');
+      await getOrCreateAssociatedTokenAccount(connection, sender, mint1, sender.publicKey);
+
+      await swap(sender, new BN(1 * LAMPORTS_PER_SOL), 2);
+    });
+
+    it("Moves tokens and proportional cost basis to a new wallet", async () => {
+      const senderPDA = getUserPositionPDA(poolPda, sender.publicKey);
+      const before = await program.account.userPosition.fetch(senderPDA);
+      const half = before.totalTokens.div(new BN(2));
+      const statsBefore = await program.account.poolStats.fetch(poolStats);
+
+      await transferPosition(sender, recipient.publicKey, half);
+
+      const senderAfter = await program.account.userPosition.fetch(senderPDA);
+      const received = await program.account.userPosition.fetch(getUserPositionPDA(poolPda, recipient.publicKey));
+      expect(senderAfter.totalTokens.toString()).to.equal(before.totalTokens.sub(half).toString());
+      expect(received.owner.toBase58()).to.equal(recipient.publicKey.toBase58());
+      expect(received.totalTokens.toString()).to.equal(half.toString());
+      // The basis moves with the tokens: nothing is created or lost
+      expect(senderAfter.totalSol.add(received.totalSol).toString()).to.equal(before.totalSol.toString());
+      // The recipient inherits the sender's entry time
+      expect(received.entryTimestamp.toString()).to.equal(before.entryTimestamp.toString());
+      // A transfer into an empty position counts a new holder, like a buy
+      const statsAfter = await program.account.poolStats.fetch(poolStats);
+      expect(statsAfter.holderCount.toNumber()).to.equal(statsBefore.holderCount.toNumber() + 1);
+
+      const balance = await connection.getTokenAccountBalance(
+        getAssociatedTokenAddressSync(mint1, recipient.publicKey)
+      );
+      expect(balance.value.amount).to.equal(half.toString());
+    });
+
+    it("Should fail: Transfer more than the tracked position", async () => {
+      const position = await program.account.userPosition.fetch(getUserPositionPDA(poolPda, sender.publicKey));
+      try {
+        await transferPosition(sender, recipient.publicKey, position.totalTokens.add(new BN(1)));
+        expect.fail("Should have thrown InsufficientPosition error");
+      } catch (error: any) {
+        expect(error.message).to.include("InsufficientPosition");
+      }
+    });
+
+    it("Should fail: Transfer to the same wallet", async () => {
+      try {
+        await transferPosition(sender, sender.publicKey, new BN(1));
+        expect.fail("Should have thrown InvalidRecipient error");
+      } catch (error: any) {
+        expect(error.message).to.include("InvalidRecipient");
+      }
+    });
+  });
+
+  describe("Untracked tokens", () => {
+    const buyer = Keypair.generate();
+    const holder = Keypair.generate();
+
+    const setPolicy = (policy: any) =>
+      program.methods
+        .setUntrackedTokenPolicy(policy)
+        .accounts({
+          dexConfigurationAccount: curveConfig,
+          programData: programData,
+          admin: upgradeAuthority.publicKey
+        })
+        .signers([upgradeAuthority])
+        .rpc();
+
+    const sellHalfOfBalance = async () => {
+      const balance = await connection.getTokenAccountBalance(
+        getAssociatedTokenAddressSync(mint1, holder.publicKey)
+      );
+      await swap(holder, new BN(balance.value.amount).div(new BN(2)), 1);
+    };
+
+    it("Setup: Move curve-bought tokens to a wallet without a position", async () => {
+      for (const wallet of [buyer, holder]) {
+        const sig = await connection.requestAirdrop(wallet.publicKey, 2 * LAMPORTS_PER_SOL);
+        const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
+        await connection.confirmTransaction({ blockhash, lastValidBlockHeight, signature: sig }, 'finalized');
+      }
+      const buyerAta = (await getOrCreateAssociatedTokenAccount(connection, buyer, mint1, buyer.publicKey)).address;
+      const holderAta = (await getOrCreateAssociatedTokenAccount(connection, holder, mint1, holder.publicKey)).address;
+
+      await swap(buyer, new BN(0.5 * LAMPORTS_PER_SOL), 2);
+      const balance = await connection.getTokenAccountBalance(buyerAta);
+      // A plain SPL transfer leaves the cost basis behind
+      await transfer(connection, buyer, buyerAta, holderAta, buyer, BigInt(balance.value.amount));
+    });
+
+    it("Should fail: Sell untracked tokens under the default reject policy", async () => {
+      try {
+        await sellHalfOfBalance();
+        expect.fail("Should have thrown InsufficientPosition error");
+      } catch (error: any) {
+        expect(error.message).to.include("InsufficientPosition");
+      }
+    });
+
+    it("Untracked tokens sell untaxed with a zero cost basis", async () => {
+      await setPolicy({ zeroCostBasis: {} });
+      const configAccount = await program.account.curveConfiguration.fetch(curveConfig);
+      expect(configAccount.untrackedTokenPolicy).to.deep.equal({ zeroCostBasis: {} });
+
+      const treasuryBefore = await getTreasuryBalance();
+      await sellHalfOfBalance();
+      expect(await getTreasuryBalance()).to.equal(treasuryBefore);
+
+      // Their proceeds count towards lifetime SOL out, not realized profit
+      const position = await program.account.userPosition.fetch(getUserPositionPDA(poolPda, holder.publicKey));
+      expect(position.totalSolOut.toNumber()).to.be.greaterThan(0);
+      expect(position.realizedProfit.toNumber()).to.equal(0);
+      expect(position.realizedLoss.toNumber()).to.equal(0);
+    });
+
+    it("Untracked tokens are taxed as a full loss", async () => {
+      await setPolicy({ fullLoss: {} });
+
+      const treasuryBefore = await getTreasuryBalance();
+      await sellHalfOfBalance();
+      expect(await getTreasuryBalance()).to.be.greaterThan(treasuryBefore);
+
+      await setPolicy({ reject: {} });
+    });
+  });
+
+  describe("Closing positions", () => {
+    const trader = Keypair.generate();
+
+    const closePosition = (user: Keypair) =>
+      program.methods
+        .closePosition()
+        .accounts({
+          pool: poolPda,
+          userPosition: getUserPositionPDA(poolPda, user.publicKey),
+          positionLots: null,
+          user: user.publicKey
+        })
+        .signers([user])
+        .rpc();
+
+    const setAutoClose = (enabled: boolean) =>
+      program.methods
+        .setAutoClosePositions(enabled)
+        .accounts({
+          dexConfigurationAccount: curveConfig,
+          programData: programData,
+          admin: upgradeAuthority.publicKey
+        })
+        .signers([upgradeAuthority])
+        .rpc();
+
+    const sellAll = async (user: Keypair) => {
+      const position = await program.account.userPosition.fetch(getUserPositionPDA(poolPda, user.publicKey));
+      await swap(user, position.totalTokens, 1);
+    };
+
+    it("Setup: Fund trader and buy through the curve", async () => {
+      const sig = await connection.requestAirdrop(trader.publicKey, 3 * LAMPORTS_PER_SOL);
+      const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
+      await connection.confirmTransaction({ blockhash, lastValidBlockHeight, signature: sig }, 'finalized');
+      await getOrCreateAssociatedTokenAccount(connection, trader, mint1, trader.publicKey);
+
+      await swap(trader, new BN(0.5 * LAMPORTS_PER_SOL), 2);
+    });
+
+    it("Tracks lifetime trade history on the position", async () => {
+      const position = await program.account.userPosition.fetch(getUserPositionPDA(poolPda, trader.publicKey));
+      expect(position.buyCount).to.equal(1);
+      expect(position.sellCount).to.equal(0);
+      expect(position.totalSolIn.toNumber()).to.equal(0.5 * LAMPORTS_PER_SOL);
+      expect(position.firstTradeTimestamp.toNumber()).to.be.greaterThan(0);
+      expect(position.lastTradeTimestamp.toString()).to.equal(position.firstTradeTimestamp.toString());
+    });
+
+    it("Should fail: Close a position that still holds tokens", async () => {
+      try {
+        await closePosition(trader);
+        expect.fail("Should have thrown PositionNotEmpty error");
+      } catch (error: any) {
+        expect(error.message).to.include("PositionNotEmpty");
+      }
+    });
+
+    it("Closes an emptied position and refunds its rent", async () => {
+      await sellAll(trader);
+      const positionPDA = getUserPositionPDA(poolPda, trader.publicKey);
+      const sold = await program.account.userPosition.fetch(positionPDA);
+      expect(sold.sellCount).to.equal(1);
+      // Selling right after buying loses the fee and the price impact
+      expect(sold.realizedLoss.toNumber()).to.be.greaterThan(0);
+      expect(sold.totalSolOut.sub(sold.totalSolIn).toString()).to.equal(sold.realizedLoss.neg().toString());
+      const rent = await connection.getBalance(positionPDA);
+      expect(rent).to.be.greaterThan(0);
+
+      const balanceBefore = await connection.getBalance(trader.publicKey);
+      await closePosition(trader);
+
+      expect(await connection.getAccountInfo(positionPDA)).to.equal(null);
+      expect(await connection.getBalance(trader.publicKey)).to.be.greaterThan(balanceBefore);
+    });
+
+    it("Closes positions emptied by a sell when auto-close is on", async () => {
+      await setAutoClose(true);
+      const configAccount = await program.account.curveConfiguration.fetch(curveConfig);
+      expect(configAccount.autoClosePositions).to.equal(true);
+
+      await swap(trader, new BN(0.5 * LAMPORTS_PER_SOL), 2);
+      await sellAll(trader);
+
+      expect(await connection.getAccountInfo(getUserPositionPDA(poolPda, trader.publicKey))).to.equal(null);
+
+      await setAutoClose(false);
+    });
+  });
+
+  describe("Pool statistics", () => {
+    it("Tracks cumulative volume, trades and the all-time high", async () => {
+      const stats = await program.account.poolStats.fetch(poolStats);
+      expect(stats.pool.toBase58()).to.equal(poolPda.toBase58());
+      expect(stats.tradeCount.toNumber()).to.be.greaterThan(0);
+      expect(stats.buyVolumeSol.toNumber()).to.be.greaterThan(0);
+      expect(stats.sellVolumeTokens.toNumber()).to.be.greaterThan(0);
+      expect(stats.holderCount.toNumber()).to.be.greaterThan(0);
+      expect(stats.totalFees.toNumber()).to.be.greaterThan(0);
+      expect(stats.totalTax.toNumber()).to.be.greaterThan(0);
+      expect(stats.athSlot.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("Counts a buy into an empty position as a new holder", async () => {
+      const newcomer = Keypair.generate();
+      const sig = await connection.requestAirdrop(newcomer.publicKey, LAMPORTS_PER_SOL);
+      const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
+      await connection.confirmTransaction({ blockhash, lastValidBlockHeight, signature: sig }, 'finalized');
+      await getOrCreateAssociatedTokenAccount(connection, newcomer, mint1, newcomer.publicKey);
+
+      const before = await program.account.poolStats.fetch(poolStats);
+      await swap(newcomer, new BN(0.1 * LAMPORTS_PER_SOL), 2);
+      const after = await program.account.poolStats.fetch(poolStats);
+
+      expect(after.holderCount.toNumber()).to.equal(before.holderCount.toNumber() + 1);
+      expect(after.tradeCount.toNumber()).to.equal(before.tradeCount.toNumber() + 1);
+      expect(after.buyVolumeSol.sub(before.buyVolumeSol).toNumber()).to.equal(0.1 * LAMPORTS_PER_SOL);
+      // Buying pushes the price up
+      expect(after.athPrice.gte(before.athPrice)).to.equal(true);
+    });
+  });
+
+  describe("Global statistics", () => {
+    it("Tracks protocol volume, tax and the king of the hill", async () => {
+      const stats = await program.account.globalStats.fetch(globalStats);
+      const poolStatsAccount = await program.account.poolStats.fetch(poolStats);
+      // Only one pool has traded so far
+      expect(stats.totalVolume.toString()).to.equal(
+        poolStatsAccount.buyVolumeSol.add(poolStatsAccount.sellVolumeSol).toString()
+      );
+      expect(stats.totalTax.toString()).to.equal(poolStatsAccount.totalTax.toString());
+
+      expect(stats.rankedCount).to.equal(1);
+      expect(stats.rankedPools[0].mint.toBase58()).to.equal(mint1.toBase58());
+      expect(stats.rankedPools[0].marketCap.toNumber()).to.be.greaterThan(0);
+    });
+  });
+
+  describe("Treasury vault", () => {
+    const recipient = Keypair.generate();
+
+    const withdraw = (amount: BN, authority: Keypair = upgradeAuthority) =>
+      program.methods
+        .withdrawTreasury(amount)
+        .accounts({
+          dexConfigurationAccount: curveConfig,
+          treasuryVault: treasuryVault,
+          destination: recipient.publicKey,
+          programData: programData,
+          admin: authority.publicKey
+        })
+        .signers([authority])
+        .rpc();
+
+    const setLimit = (limit: BN) =>
+      program.methods
+        .setTreasuryWithdrawalLimit(limit)
+        .accounts({
+          dexConfigurationAccount: curveConfig,
+          treasuryVault: treasuryVault,
+          programData: programData,
+          admin: upgradeAuthority.publicKey
+        })
+        .signers([upgradeAuthority])
+        .rpc();
+
+    it("Holds the collected paperhand tax in a program-owned account", async () => {
+      const info = await connection.getAccountInfo(treasuryVault);
+      expect(info.owner.toBase58()).to.equal(program.programId.toBase58());
+      const rentReserve = await connection.getMinimumBalanceForRentExemption(info.data.length);
+      expect(info.lamports).to.be.greaterThan(rentReserve);
+    });
+
+    it("Should fail: Withdraw from a wallet that is not the upgrade authority", async () => {
+      try {
+        await withdraw(new BN(1000), user2);
+        expect.fail("Should have thrown Unauthorized error");
+      } catch (error: any) {
+        expect(error.message).to.include("Unauthorized");
+      }
+    });
+
+    it("Withdraws within the per-epoch limit", async () => {
+      await setLimit(new BN(1_000_000));
+      await withdraw(new BN(600_000));
+
+      expect(await connection.getBalance(recipient.publicKey)).to.equal(600_000);
+      const vault = await program.account.treasuryVault.fetch(treasuryVault);
+      expect(vault.withdrawnThisEpoch.toNumber()).to.equal(600_000);
+      expect(vault.totalWithdrawn.toNumber()).to.equal(600_000);
+    });
+
+    it("Should fail: Withdraw beyond the per-epoch limit", async () => {
+      try {
+        await withdraw(new BN(600_000));
+        expect.fail("Should have thrown TreasuryLimitExceeded error");
+      } catch (error: any) {
+        expect(error.message).to.include("TreasuryLimitExceeded");
+      }
+    });
+
+    it("Should fail: Withdraw into the vault's rent reserve", async () => {
+      await setLimit(new BN(0));
+      const balance = await connection.getBalance(treasuryVault);
+      try {
+        await withdraw(new BN(balance));
+        expect.fail("Should have thrown InsufficientFunds error");
+      } catch (error: any) {
+        expect(error.message).to.include("InsufficientFunds");
+      }
+    });
+
+    it("Should fail: Point the treasury at another account", async () => {
+      try {
+        await program.methods
+          .updateConfiguration(user2.publicKey, null)
+          .accounts({
+            dexConfigurationAccount: curveConfig,
+            programData: programData,
+            admin: upgradeAuthority.publicKey
+          })
+          .signers([upgradeAuthority])
+          .rpc();
+        expect.fail("Should have thrown InvalidTreasury error");
+      } catch (error: any) {
+        expect(error.message).to.include("InvalidTreasury");
+      }
+    });
+  });
+
+  describe("Protocol fees", () => {
+    const recipient = Keypair.generate();
+
+    const withdraw = (amount: BN, authority: Keypair = upgradeAuthority) =>
+      program.methods
+        .withdrawFees(amount)
+        .accounts({
+          dexConfigurationAccount: curveConfig,
+          globalAccount: globalAccount,
+          destination: recipient.publicKey,
+          programData: programData,
+          admin: authority.publicKey,
+          systemProgram: SystemProgram.programId
+        })
+        .signers([authority])
+        .rpc();
+
+    it("Should fail: Withdraw fees from a wallet that is not the upgrade authority", async () => {
+      try {
+        await withdraw(new BN(1_000_000), user2);
+        expect.fail("Should have thrown Unauthorized error");
+      } catch (error: any) {
+        expect(error.message).to.include("Unauthorized");
+      }
+    });
+
+    it("Should fail: Withdraw more than the accrued fees", async () => {
+      const { accruedFees } = await program.account.curveConfiguration.fetch(curveConfig);
+      try {
+        await withdraw(accruedFees.addn(1));
+        expect.fail("Should have thrown InsufficientFunds error");
+      } catch (error: any) {
+        expect(error.message).to.include("InsufficientFunds");
+      }
+    });
+
+    it("Upgrade authority withdraws accrued fees without a council", async () => {
+      const configBefore = await program.account.curveConfiguration.fetch(curveConfig);
+      const amount = configBefore.accruedFees.divn(2);
+      expect(amount.toNumber()).to.be.greaterThan(0);
+
+      await withdraw(amount);
+
+      expect(await connection.getBalance(recipient.publicKey)).to.equal(amount.toNumber());
+      const configAfter = await program.account.curveConfiguration.fetch(curveConfig);
+      expect(configAfter.accruedFees.toString()).to.equal(configBefore.accruedFees.sub(amount).toString());
+    });
+  });
+
+  describe("Buyback and burn", () => {
+    const seller = Keypair.generate();
+
+    const setBuybackConfig = (buybackBps: number, maxSolPerCall: BN, cooldownSeconds: number) =>
+      program.methods
+        .setBuybackConfig(buybackBps, maxSolPerCall, cooldownSeconds)
+        .accounts({
+          dexConfigurationAccount: curveConfig,
+          programData: programData,
+          admin: upgradeAuthority.publicKey
+        })
+        .signers([upgradeAuthority])
+        .rpc();
+
+    const buyback = async (maxSol: BN) =>
+      program.methods
+        .buybackAndBurn(maxSol)
+        .accounts({
+          dexConfigurationAccount: curveConfig,
+          pool: poolPda,
+          globalAccount: globalAccount,
+          treasuryVault: treasuryVault,
+          mintTokenOne: mint1,
+          poolTokenAccountOne: await getAssociatedTokenAddress(mint1, globalAccount, true),
+          cranker: user2.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_PROGRAM_ID
+        })
+        .signers([user2])
+        .rpc();
+
+    it("Should fail: Buy back while buybacks are disabled", async () => {
+      try {
+        await buyback(new BN(LAMPORTS_PER_SOL));
+        expect.fail("Should have thrown BuybacksDisabled error");
+      } catch (error: any) {
+        expect(error.message).to.include("BuybacksDisabled");
+      }
+    });
+
+    it("Should fail: Earmark more than all of the treasury's share", async () => {
+      try {
+        await setBuybackConfig(10001, new BN(LAMPORTS_PER_SOL), 3600);
+        expect.fail("Should have thrown InvalidBuybackConfig error");
+      } catch (error: any) {
+        expect(error.message).to.include("InvalidBuybackConfig");
+      }
+    });
+
+    it("Earmarks part of the treasury's tax for the pool", async () => {
+      await setBuybackConfig(5000, new BN(0.001 * LAMPORTS_PER_SOL), 3600);
+
+      const sig = await connection.requestAirdrop(seller.publicKey, LAMPORTS_PER_SOL);
+      const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
+      await connection.confirmTransaction({ blockhash, lastValidBlockHeight, signature: sig }, 'finalized');
+      const sellerAta = await getOrCreateAssociatedTokenAccount(connection, seller, mint1, seller.publicKey);
+
+      const poolBefore = await program.account.liquidityPool.fetch(poolPda);
+      const vaultBefore = await program.account.treasuryVault.fetch(treasuryVault);
+
+      // Selling straight after buying is a loss, so it pays the paperhand tax
+      await swap(seller, new BN(0.1 * LAMPORTS_PER_SOL), 2);
+      const tokens = (await connection.getTokenAccountBalance(sellerAta.address)).value.amount;
+      await swap(seller, new BN(tokens), 1);
+
+      const poolAfter = await program.account.liquidityPool.fetch(poolPda);
+      const vaultAfter = await program.account.treasuryVault.fetch(treasuryVault);
+      const earmarked = poolAfter.buybackBalance.sub(poolBefore.buybackBalance);
+      expect(earmarked.toNumber()).to.be.greaterThan(0);
+      expect(vaultAfter.earmarked.sub(vaultBefore.earmarked).toString()).to.equal(earmarked.toString());
+    });
+
+    it("Buys the token back along the curve and burns it", async () => {
+      const poolBefore = await program.account.liquidityPool.fetch(poolPda);
+      const vaultBalanceBefore = await getTreasuryBalance();
+      const spent = BN.min(poolBefore.buybackBalance, new BN(0.001 * LAMPORTS_PER_SOL));
+      const supplyBefore = (await connection.getTokenSupply(mint1)).value.amount;
+
+      await buyback(new BN(LAMPORTS_PER_SOL));
+
+      const poolAfter = await program.account.liquidityPool.fetch(poolPda);
+      const burned = poolBefore.reserveOne.sub(poolAfter.reserveOne);
+      expect(burned.toNumber()).to.be.greaterThan(0);
+      expect(poolBefore.totalSupply.sub(poolAfter.totalSupply).toString()).to.equal(burned.toString());
+      expect(poolAfter.reserveTwo.sub(poolBefore.reserveTwo).toString()).to.equal(spent.toString());
+      expect(poolBefore.buybackBalance.sub(poolAfter.buybackBalance).toString()).to.equal(spent.toString());
+      expect(vaultBalanceBefore - (await getTreasuryBalance())).to.equal(spent.toNumber());
+
+      const supplyAfter = (await connection.getTokenSupply(mint1)).value.amount;
+      expect(new BN(supplyBefore).sub(new BN(supplyAfter)).toString()).to.equal(burned.toString());
+    });
+
+    it("Should fail: Buy back again during the cooldown", async () => {
+      try {
+        await buyback(new BN(LAMPORTS_PER_SOL));
+        expect.fail("Should have thrown BuybackCooldownActive error");
+      } catch (error: any) {
+        expect(error.message).to.include("BuybackCooldownActive");
+      }
+    });
+  });
+
+  describe("Referrals", () => {
+    const trader = Keypair.generate();
+    const otherReferrer = Keypair.generate();
+    const getReferrerPDA = (wallet: PublicKey) =>
+      PublicKey.findProgramAddressSync([Buffer.from("referrer"), wallet.toBuffer()], program.programId)[0];
+    const referrer = getReferrerPDA(user2.publicKey);
+
+    const registerReferrer = (owner: Keypair) =>
+      program.methods
+        .registerReferrer()
+        .accounts({
+          referrer: getReferrerPDA(owner.publicKey),
+          owner: owner.publicKey,
+          systemProgram: SystemProgram.programId
+        })
+        .signers([owner])
+        .rpc();
+
+    before(async () => {
+      for (const wallet of [trader, otherReferrer]) {
+        const sig = await connection.requestAirdrop(wallet.publicKey, LAMPORTS_PER_SOL);
+        const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
+        await connection.confirmTransaction({ blockhash, lastValidBlockHeight, signature: sig }, 'finalized');
+        await getOrCreateAssociatedTokenAccount(connection, wallet, mint1, wallet.publicKey);
+      }
+      await registerReferrer(user2);
+      await registerReferrer(otherReferrer);
+
+      await program.methods
+        .setReferralFeeBps(2000)
+        .accounts({
+          dexConfigurationAccount: curveConfig,
+          programData: programData,
+          admin: upgradeAuthority.publicKey
+        })
+        .signers([upgradeAuthority])
+        .rpc();
+    });
+
+    it("Binds the referrer on the first trade and pays it a share of the fee", async () => {
+      const amount = new BN(0.1 * LAMPORTS_PER_SOL);
+      const configBefore = await program.account.curveConfiguration.fetch(curveConfig);
+      await swap(trader, amount, 2, referrer);
+
+      const referral = await program.account.referral.fetch(getReferralPDA(poolPda, trader.publicKey));
+      expect(referral.referrer.toBase58()).to.equal(referrer.toBase58());
+
+      // 1% fee, of which 20% goes to the referrer
+      const fee = amount.muln(DEFAULT_FEE_BPS).divn(10000);
+      const referralFee = fee.muln(2000).divn(10000);
+      const account = await program.account.referrer.fetch(referrer);
+      expect(account.referredPositions.toNumber()).to.equal(1);
+      expect(account.tradeCount.toNumber()).to.equal(1);
+      expect(account.referredVolume.toString()).to.equal(amount.toString());
+      expect(account.accruedFees.toString()).to.equal(referralFee.toString());
+
+      const configAfter = await program.account.curveConfiguration.fetch(curveConfig);
+      expect(configAfter.accruedFees.sub(configBefore.accruedFees).toString())
+        .to.equal(fee.sub(referralFee).toString());
+    });
+
+    it("Should fail: Switch to another referrer", async () => {
+      try {
+        await swap(trader, new BN(0.01 * LAMPORTS_PER_SOL), 2, getReferrerPDA(otherReferrer.publicKey));
+        expect.fail("Should have thrown InvalidReferrer error");
+      } catch (error: any) {
+        expect(error.message).to.include("InvalidReferrer");
+      }
+    });
+
+    it("Should fail: Trade without the bound referrer", async () => {
+      try {
+        await swap(trader, new BN(0.01 * LAMPORTS_PER_SOL), 2);
+        expect.fail("Should have thrown InvalidReferrer error");
+      } catch (error: any) {
+        expect(error.message).to.include("InvalidReferrer");
+      }
+    });
+
+    it("Should fail: Refer yourself", async () => {
+      try {
+        await swap(otherReferrer, new BN(0.01 * LAMPORTS_PER_SOL), 2, getReferrerPDA(otherReferrer.publicKey));
+        expect.fail("Should have thrown InvalidReferrer error");
+      } catch (error: any) {
+        expect(error.message).to.include("InvalidReferrer");
+      }
+    });
+
+    it("Closing an emptied position keeps its referrer binding", async () => {
+      const positionPDA = getUserPositionPDA(poolPda, trader.publicKey);
+      const { totalTokens } = await program.account.userPosition.fetch(positionPDA);
+      await swap(trader, totalTokens, 1, referrer);
+
+      await program.methods
+        .closePosition()
+        .accounts({
+          pool: poolPda,
+          userPosition: positionPDA,
+          positionLots: null,
+          user: trader.publicKey
+        })
+        .signers([trader])
+        .rpc();
+      expect(await connection.getAccountInfo(positionPDA)).to.equal(null);
+
+      // The binding outlives the position, so a reopened one can't switch referrers
+      const referral = await program.account.referral.fetch(getReferralPDA(poolPda, trader.publicKey));
+      expect(referral.referrer.toBase58()).to.equal(referrer.toBase58());
+      try {
+        await swap(trader, new BN(0.01 * LAMPORTS_PER_SOL), 2, getReferrerPDA(otherReferrer.publicKey));
+        expect.fail("Should have thrown InvalidReferrer error");
+      } catch (error: any) {
+        expect(error.message).to.include("InvalidReferrer");
+      }
+
+      await swap(trader, new BN(0.01 * LAMPORTS_PER_SOL), 2, referrer);
+      const position = await program.account.userPosition.fetch(positionPDA);
+      expect(position.totalTokens.toNumber()).to.be.greaterThan(0);
+      expect((await program.account.referrer.fetch(referrer)).referredPositions.toNumber()).to.equal(1);
+    });
+
+    it("Referrer claims its fees", async () => {
+      const accrued = (await program.account.referrer.fetch(referrer)).accruedFees;
+      const balanceBefore = await connection.getBalance(user2.publicKey);
+
+      await program.methods
+        .claimReferralFees()
+        .accounts({
+          referrer: referrer,
+          globalAccount: globalAccount,
+          owner: user2.publicKey,
+          systemProgram: SystemProgram.programId
+        })
+        .signers([user2])
+        .rpc();
+
+      const account = await program.account.referrer.fetch(referrer);
+      expect(account.accruedFees.toNumber()).to.equal(0);
+      expect(account.totalEarned.toString()).to.equal(accrued.toString());
+      // The claimant pays the transaction fee
+      expect(await connection.getBalance(user2.publicKey)).to.be.greaterThan(balanceBefore + accrued.toNumber() - 10_000);
+    });
+  });
+
+  describe("Symbol registry", () => {
+    const [symbolRegistry] = PublicKey.findProgramAddressSync(
+      [Buffer.from("symbol_registry")],
+      program.programId
+    );
+    const getSymbolEntryPDA = (symbol: string) =>
+      PublicKey.findProgramAddressSync([Buffer.from("symbol"), Buffer.from(symbol)], program.programId)[0];
+    const bidder = Keypair.generate();
+
+    const setBlocked = (symbol: string, blocked: boolean, authority: Keypair = upgradeAuthority) =>
+      program.methods
+        .setSymbolBlocked(symbol, blocked)
+        .accounts({
+          symbolRegistry: symbolRegistry,
+          symbolEntry: getSymbolEntryPDA(symbol),
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId
+        })
+        .signers([authority])
+        .rpc();
+
+    const bid = (symbol: string, amount: number, wallet: Keypair, previousBidder: PublicKey | null) =>
+      program.methods
+        .bidSymbol(symbol, new BN(amount))
+        .accounts({
+          symbolRegistry: symbolRegistry,
+          symbolEntry: getSymbolEntryPDA(symbol),
+          previousBidder: previousBidder,
+          bidder: wallet.publicKey,
+          systemProgram: SystemProgram.programId
+        })
+        .signers([wallet])
+        .rpc();
+
+    const settle = (symbol: string) =>
+      program.methods
+        .settleSymbolAuction(symbol)
+        .accounts({
+          dexConfigurationAccount: curveConfig,
+          symbolEntry: getSymbolEntryPDA(symbol),
+          treasuryVault: treasuryVault,
+          cranker: user2.publicKey
+        })
+        .signers([user2])
+        .rpc();
+
+    before(async () => {
+      const sig = await connection.requestAirdrop(bidder.publicKey, LAMPORTS_PER_SOL);
+      const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
+      await connection.confirmTransaction({ blockhash, lastValidBlockHeight, signature: sig }, 'finalized');
+    });
+
+    it("Initializes with the upgrade authority as moderator", async () => {
+      await program.methods
+        .initializeSymbolRegistry()
+        .accounts({
+          dexConfigurationAccount: curveConfig,
+          symbolRegistry: symbolRegistry,
+          programData: programData,
+          payer: admin.publicKey,
+          systemProgram: SystemProgram.programId
+        })
+        .signers([admin])
+        .rpc();
+
+      const registry = await program.account.symbolRegistry.fetch(symbolRegistry);
+      expect(registry.authority.toBase58()).to.equal(upgradeAuthority.publicKey.toBase58());
+      expect(registry.premiumMaxLen).to.equal(0);
+    });
+
+    it("Should fail: Block a symbol without being the moderator", async () => {
+      try {
+        await setBlocked("SCAM", true, user2);
+        expect.fail("Should have thrown Unauthorized error");
+      } catch (error: any) {
+        expect(error.message).to.include("Unauthorized");
+      }
+    });
+
+    it("Should fail: Block a symbol that is not in upper case", async () => {
+      try {
+        await setBlocked("scam", true);
+        expect.fail("Should have thrown InvalidSymbol error");
+      } catch (error: any) {
+        expect(error.message).to.include("InvalidSymbol");
+      }
+    });
+
+    it("Moderator blocks and reserves symbols", async () => {
+      await setBlocked("SCAM", true);
+      expect((await program.account.symbolEntry.fetch(getSymbolEntryPDA("SCAM"))).blocked).to.equal(true);
+
+      const expiresAt = Math.floor(Date.now() / 1000) + 3600;
+      await program.methods
+        .reserveSymbol("ACME", user2.publicKey, new BN(expiresAt))
+        .accounts({
+          symbolRegistry: symbolRegistry,
+          symbolEntry: getSymbolEntryPDA("ACME"),
+          authority: upgradeAuthority.publicKey,
+          systemProgram: SystemProgram.programId
+        })
+        .signers([upgradeAuthority])
+        .rpc();
+
+      const entry = await program.account.symbolEntry.fetch(getSymbolEntryPDA("ACME"));
+      expect(entry.reservedFor.toBase58()).to.equal(user2.publicKey.toBase58());
+      expect(entry.reservationExpiresAt.toNumber()).to.equal(expiresAt);
+    });
+
+    it("Should fail: Auction a symbol that is not premium", async () => {
+      await program.methods
+        .setSymbolRegistryConfig(3, 2, new BN(0.01 * LAMPORTS_PER_SOL), 1000)
+        .accounts({
+          symbolRegistry: symbolRegistry,
+          authority: upgradeAuthority.publicKey
+        })
+        .signers([upgradeAuthority])
+        .rpc();
+
+      try {
+        await bid("LONGER", 0.01 * LAMPORTS_PER_SOL, bidder, null);
+        expect.fail("Should have thrown SymbolNotAuctionable error");
+      } catch (error: any) {
+        expect(error.message).to.include("SymbolNotAuctionable");
+      }
+    });
+
+    it("Escrows bids and refunds the outbid bidder", async () => {
+      await bid("PHT", 0.01 * LAMPORTS_PER_SOL, user2, null);
+
+      try {
+        await bid("PHT", 0.0105 * LAMPORTS_PER_SOL, bidder, user2.publicKey);
+        expect.fail("Should have thrown BidTooLow error");
+      } catch (error: any) {
+        expect(error.message).to.include("BidTooLow");
+      }
+
+      const user2Before = await connection.getBalance(user2.publicKey);
+      await bid("PHT", 0.02 * LAMPORTS_PER_SOL, bidder, user2.publicKey);
+      expect(await connection.getBalance(user2.publicKey)).to.equal(user2Before + 0.01 * LAMPORTS_PER_SOL);
+
+      const entry = await program.account.symbolEntry.fetch(getSymbolEntryPDA("PHT"));
+      expect(entry.highestBidder.toBase58()).to.equal(bidder.publicKey.toBase58());
+      expect(entry.highestBid.toNumber()).to.equal(0.02 * LAMPORTS_PER_SOL);
+    });
+
+    it("Should fail: Settle a running auction", async () => {
+      try {
+        await settle("PHT");
+        expect.fail("Should have thrown AuctionNotEnded error");
+      } catch (error: any) {
+        expect(error.message).to.include("AuctionNotEnded");
+      }
+    });
+
+    it("Settles an ended auction into a reservation for the winner", async () => {
+      await sleep(4000);
+      const vaultBefore = await getTreasuryBalance();
+      await settle("PHT");
+
+      expect((await getTreasuryBalance()) - vaultBefore).to.equal(0.02 * LAMPORTS_PER_SOL);
+      const entry = await program.account.symbolEntry.fetch(getSymbolEntryPDA("PHT"));
+      expect(entry.auctionSettled).to.equal(true);
+      expect(entry.reservedFor.toBase58()).to.equal(bidder.publicKey.toBase58());
+      expect(entry.reservationExpiresAt.toNumber()).to.equal(0);
+
+      try {
+        await bid("PHT", LAMPORTS_PER_SOL, user2, bidder.publicKey);
+        expect.fail("Should have thrown SymbolReserved error");
+      } catch (error: any) {
+        expect(error.message).to.include("SymbolReserved");
+      }
+    });
+
+    describe("Launch gating", () => {
+      const launcher = Keypair.generate();
+
+      const expectLaunchToFail = async (symbol: string, errorName: string) => {
+        try {
+          await launchToken(launcher, symbol);
+          expect.fail(`Should have thrown ${errorName} error`);
+        } catch (error: any) {
+          expect(error.message).to.include(errorName);
+        }
+      };
+
+      before(async () => {
+        await fundWallet(launcher);
+        await fundWallet(bidder);
+      });
+
+      it("Should fail: Launch a blocked symbol", async () => {
+        await expectLaunchToFail("SCAM", "SymbolBlocked");
+        // Compared in upper case
+        await expectLaunchToFail("scam", "SymbolBlocked");
+      });
+
+      it("Should fail: Launch a symbol reserved for another wallet", async () => {
+        await expectLaunchToFail("ACME", "SymbolReserved");
+      });
+
+      it("Should fail: Launch a premium symbol that was never auctioned", async () => {
+        await expectLaunchToFail("XYZ", "SymbolRequiresAuction");
+      });
+
+      it("Should fail: Launch a look-alike of a blocked symbol", async () => {
+        await expectLaunchToFail("SCAM ", "InvalidSymbol");
+        // Cyrillic capital Es in place of the C
+        await expectLaunchToFail("S\u0421AM", "InvalidSymbol");
+      });
+
+      it("Auction winner launches its symbol", async () => {
+        const { market } = await launchToken(bidder, "PHT");
+
+        const record = await program.account.launchRecord.fetch(
+          PublicKey.findProgramAddressSync([Buffer.from("launch"), market.mint.toBuffer()], program.programId)[0]
+        );
+        expect(record.symbol).to.equal("PHT");
+        expect(record.creator.toBase58()).to.equal(bidder.publicKey.toBase58());
+      });
+    });
+  });
+
+  describe("Token-2022 launches", () => {
+    const creator = Keypair.generate();
+    const trader = Keypair.generate();
+    const receiver = Keypair.generate();
+    const stranger = Keypair.generate();
+    let mint: PublicKey;
+    let pool: PublicKey;
+
+    const ata = (owner: PublicKey) => getAssociatedTokenAddressSync(mint, owner, true, TOKEN_2022_PROGRAM_ID);
+    const positionOf = (wallet: Keypair) =>
+      program.account.userPosition.fetch(getUserPositionPDA(pool, wallet.publicKey));
+    const extraAccountMetaList = (mint: PublicKey) =>
+      PublicKey.findProgramAddressSync([Buffer.from("extra-account-metas"), mint.toBuffer()], TRANSFER_HOOK_PROGRAM_ID)[0];
+
+    // Remaining accounts pump passes on to the transfer hook when it moves
+    // tokens from `sourceOwner` to `destinationOwner`
+    const getHookAccounts = (sourceOwner: PublicKey, destinationOwner: PublicKey) => {
+      const account = (pubkey: PublicKey, isWritable = false) => ({ pubkey, isWritable, isSigner: false });
+      return [
+        account(TRANSFER_HOOK_PROGRAM_ID),
+        account(extraAccountMetaList(mint)),
+        account(SYSVAR_INSTRUCTIONS_PUBKEY),
+        account(program.programId),
+        account(pool, true),
+        account(getUserPositionPDA(pool, sourceOwner), true),
+        account(getUserPositionPDA(pool, destinationOwner), true),
+        account(PublicKey.findProgramAddressSync([Buffer.from("hook_authority")], TRANSFER_HOOK_PROGRAM_ID)[0]),
+        account(getPoolStatsPDA(pool), true)
+      ];
+    };
+
+    const launchHooked = async (symbol: string, costBasisMode: any) => {
+      const [creatorProfile] = PublicKey.findProgramAddressSync(
+        [Buffer.from("creator"), creator.publicKey.toBuffer()],
+        program.programId
+      );
+      const profile = await program.account.creatorProfile.fetchNullable(creatorProfile);
+      const launchIndex = profile ? profile.launchCount : new BN(0);
+      const [launchMint] = PublicKey.findProgramAddressSync(
+        [Buffer.from("mint"), creator.publicKey.toBuffer(), launchIndex.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [launchPool] = PublicKey.findProgramAddressSync(
+        [Buffer.from(POOL_SEED_PREFIX), launchMint.toBuffer()],
+        program.programId
+      );
+      const tx = new Transaction().add(
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 600_000 }),
+        await program.methods
+          .launch(
+            `${symbol} token`,
+            symbol,
+            "https://example.com/token.json",
+            tokenDecimal,
+            initialTokenSupply,
+            new BN(LAMPORTS_PER_SOL),
+            null,
+            costBasisMode,
+            null,
+            null
+          )
+          .accounts({
+            dexConfigurationAccount: curveConfig,
+            creatorProfile: creatorProfile,
+            mint: launchMint,
+            metadata: PublicKey.findProgramAddressSync(
+              [Buffer.from("metadata"), METADATA_PROGRAM_ID.toBuffer(), launchMint.toBuffer()],
+              METADATA_PROGRAM_ID
+            )[0],
+            pool: launchPool,
+            launchRecord: PublicKey.findProgramAddressSync([Buffer.from("launch"), launchMint.toBuffer()], program.programId)[0],
+            globalStats: globalStats,
+            symbolRegistry: PublicKey.findProgramAddressSync([Buffer.from("symbol_registry")], program.programId)[0],
+            symbolEntry: PublicKey.findProgramAddressSync([Buffer.from("symbol"), Buffer.from(symbol)], program.programId)[0],
+            globalAccount: globalAccount,
+            poolTokenAccount: getAssociatedTokenAddressSync(launchMint, globalAccount, true, TOKEN_2022_PROGRAM_ID),
+            creatorPosition: null,
+            creatorPositionLots: null,
+            poolStats: null,
+            creatorTokenAccount: null,
+            vestingEscrow: null,
+            vestingTokenAccount: null,
+            extraAccountMetaList: extraAccountMetaList(launchMint),
+            transferHookProgram: TRANSFER_HOOK_PROGRAM_ID,
+            creator: creator.publicKey,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+            metadataProgram: METADATA_PROGRAM_ID,
+            rent: SYSVAR_RENT_PUBKEY
+          })
+          .instruction()
+      );
+      await sendAndConfirmTransaction(connection, tx, [creator]);
+      return { launchMint, launchPool };
+    };
+
+    // Buy (style 2) or sell (style 1) through the hooked pool's curve
+    const swapHooked = async (user: Keypair, amount: BN, style: number) => {
+      const tx = new Transaction().add(
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }),
+        await program.methods
+          .swap(amount, new BN(style))
+          .accounts({
+            dexConfigurationAccount: curveConfig,
+            pool: pool,
+            globalAccount: globalAccount,
+            treasuryVault: treasuryVault,
+            creator: null,
+            referrer: null,
+            referral: getReferralPDA(pool, user.publicKey),
+            userPosition: getUserPositionPDA(pool, user.publicKey),
+            positionLots: null,
+            poolStats: PublicKey.findProgramAddressSync([Buffer.from("pool_stats"), pool.toBuffer()], program.programId)[0],
+            globalStats: globalStats,
+            mintTokenOne: mint,
+            poolTokenAccountOne: ata(globalAccount),
+            userTokenAccountOne: ata(user.publicKey),
+            user: user.publicKey,
+            rent: SYSVAR_RENT_PUBKEY,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_PROGRAM_ID
+          })
+          // Sells move tokens from the user to the vault, buys the other way
+          .remainingAccounts(style === 1
+            ? getHookAccounts(user.publicKey, globalAccount)
+            : getHookAccounts(globalAccount, user.publicKey))
+          .instruction()
+      );
+      return sendAndConfirmTransaction(connection, tx, [user]);
+    };
+
+    // A plain Token-2022 transfer, outside pump; the client resolves the hook's accounts
+    const transferDirectly = async (from: Keypair, to: Keypair, amount: BN) => {
+      const tx = new Transaction().add(
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }),
+        await createTransferCheckedWithTransferHookInstruction(
+          connection,
+          ata(from.publicKey),
+          mint,
+          ata(to.publicKey),
+          from.publicKey,
+          BigInt(amount.toString()),
+          tokenDecimal,
+          [],
+          "confirmed",
+          TOKEN_2022_PROGRAM_ID
+        )
+      );
+      return sendAndConfirmTransaction(connection, tx, [from]);
+    };
+
+    it("Setup: Fund the wallets", async () => {
+      for (const wallet of [creator, trader, receiver, stranger]) {
+        const sig = await connection.requestAirdrop(wallet.publicKey, 3 * LAMPORTS_PER_SOL);
+        const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
+        await connection.confirmTransaction({ blockhash, lastValidBlockHeight, signature: sig }, 'finalized');
+      }
+    });
+
+    it("Creates a Token-2022 mint whose transfers run the pump hook", async () => {
+      ({ launchMint: mint, launchPool: pool } = await launchHooked("HOOK", { averageCost: {} }));
+
+      const mintAccount = await getMint(connection, mint, "confirmed", TOKEN_2022_PROGRAM_ID);
+      expect(mintAccount.mintAuthority.toBase58()).to.equal(globalAccount.toBase58());
+      const hook = getTransferHook(mintAccount);
+      expect(hook.programId.toBase58()).to.equal(TRANSFER_HOOK_PROGRAM_ID.toBase58());
+      expect(hook.authority.toBase58()).to.equal(globalAccount.toBase58());
+
+      const metas = await connection.getAccountInfo(extraAccountMetaList(mint));
+      expect(metas.owner.toBase58()).to.equal(TRANSFER_HOOK_PROGRAM_ID.toBase58());
+    });
+
+    it("Should fail: Launch a Token-2022 mint with FIFO cost basis", async () => {
+      try {
+        await launchHooked("HOOKFIFO", { fifo: {} });
+        expect.fail("Should have thrown FifoWithTransferHook error");
+      } catch (error: any) {
+        expect(error.message).to.include("FifoWithTransferHook");
+      }
+    });
+
+    it("Trades through the curve without double counting", async () => {
+      for (const wallet of [trader, receiver]) {
+        await createAssociatedTokenAccountIdempotent(
+          connection, wallet, mint, wallet.publicKey, {}, TOKEN_2022_PROGRAM_ID
+        );
+        await swapHooked(wallet, new BN(0.5 * LAMPORTS_PER_SOL), 2);
+      }
+
+      const position = await positionOf(trader);
+      const balance = await connection.getTokenAccountBalance(ata(trader.publicKey));
+      expect(position.totalTokens.toString()).to.equal(balance.value.amount);
+      expect(position.totalSol.toString()).to.equal(new BN(0.5 * LAMPORTS_PER_SOL).toString());
+
+      // Selling part of it back goes through the hook as well
+      const sold = position.totalTokens.divn(4);
+      await swapHooked(trader, sold, 1);
+      const after = await positionOf(trader);
+      expect(after.totalTokens.toString()).to.equal(position.totalTokens.sub(sold).toString());
+    });
+
+    it("Plain transfers move proportional cost basis to the recipient's position", async () => {
+      const senderBefore = await positionOf(trader);
+      const receiverBefore = await positionOf(receiver);
+      const amount = senderBefore.totalTokens.divn(2);
+      const basis = senderBefore.totalSol.mul(amount).div(senderBefore.totalTokens);
+
+      await transferDirectly(trader, receiver, amount);
+
+      const senderAfter = await positionOf(trader);
+      const receiverAfter = await positionOf(receiver);
+      expect(senderAfter.totalTokens.toString()).to.equal(senderBefore.totalTokens.sub(amount).toString());
+      expect(senderBefore.totalSol.sub(senderAfter.totalSol).toString()).to.equal(basis.toString());
+      expect(receiverAfter.totalTokens.toString()).to.equal(receiverBefore.totalTokens.add(amount).toString());
+      expect(receiverAfter.totalSol.sub(receiverBefore.totalSol).toString()).to.equal(basis.toString());
+    });
+
+    it("Should fail: Send tracked tokens to a wallet without a position", async () => {
+      await createAssociatedTokenAccountIdempotent(
+        connection, stranger, mint, stranger.publicKey, {}, TOKEN_2022_PROGRAM_ID
+      );
+      const senderBefore = await positionOf(receiver);
+      const amount = senderBefore.totalTokens.divn(3);
+
+      try {
+        await transferDirectly(receiver, stranger, amount);
+        expect.fail("Should have thrown RecipientPositionRequired error");
+      } catch (error: any) {
+        expect(error.message).to.include("RecipientPositionRequired");
+      }
+
+      // The basis stays with the sender instead of being dropped
+      const senderAfter = await positionOf(receiver);
+      expect(senderAfter.totalTokens.toString()).to.equal(senderBefore.totalTokens.toString());
+      expect(senderAfter.totalSol.toString()).to.equal(senderBefore.totalSol.toString());
+      expect(await connection.getAccountInfo(getUserPositionPDA(pool, stranger.publicKey))).to.equal(null);
+
+      const balance = await connection.getTokenAccountBalance(ata(stranger.publicKey));
+      expect(balance.value.amount).to.equal("0");
+    });
+
+    it("Counts a plain transfer into an empty position as a new holder", async () => {
+      // The stranger's position exists but holds nothing once sold out
+      await swapHooked(stranger, new BN(0.1 * LAMPORTS_PER_SOL), 2);
+      await swapHooked(stranger, (await positionOf(stranger)).totalTokens, 1);
+      expect((await positionOf(stranger)).totalTokens.toNumber()).to.equal(0);
+
+      const before = await program.account.poolStats.fetch(getPoolStatsPDA(pool));
+      await transferDirectly(trader, stranger, (await positionOf(trader)).totalTokens.divn(4));
+      const after = await program.account.poolStats.fetch(getPoolStatsPDA(pool));
+
+      expect(after.holderCount.toNumber()).to.equal(before.holderCount.toNumber() + 1);
+      expect(after.tradeCount.toString()).to.equal(before.tradeCount.toString());
+    });
+  });
+
+  describe("Launch", () => {
+    const creator = Keypair.generate();
+    const trader = Keypair.generate();
+
+    it("Setup: Fund creator and trader wallets", async () => {
+      await fundWallet(creator, 20);
+      await fundWallet(trader, 20);
+    });
+
+    it("Sells on a pool launched with a tax override pay the pool rate", async () => {
+      const { market } = await launchToken(creator, "LOWTAX", { paperhandTaxBps: 2000 });
+      const poolAccount = await program.account.liquidityPool.fetch(market.pool);
+      expect(poolAccount.hasTaxOverride).to.equal(true);
+      expect(poolAccount.taxOverrideBps).to.equal(2000);
+
+      await getOrCreateAssociatedTokenAccount(connection, trader, market.mint, trader.publicKey);
+      await swapOn(market, trader, new BN(0.5 * LAMPORTS_PER_SOL), 2);
+      const { totalTokens } = await program.account.userPosition.fetch(getUserPositionPDA(market.pool, trader.publicKey));
+
+      // Sold at a loss right away, so the flat rate applies to the proceeds
+      const tax = await getEvent(await swapOn(market, trader, totalTokens, 1), "paperhandTaxApplied");
+      expect(tax.taxBps).to.equal(2000);
+      expect(tax.taxBps).to.not.equal(DEFAULT_PAPERHAND_TAX_BPS);
+      expect(tax.tax.toString()).to.equal(tax.solOutBeforeTax.muln(2000).divn(10000).toString());
+    });
+
+    it("Launches from one creator derive their mints from its launch counter", async () => {
+      const serial = Keypair.generate();
+      await fundWallet(serial, 10);
+
+      // The same symbol twice: mints no longer depend on it
+      const launches = [await launchToken(serial, "TWIN"), await launchToken(serial, "TWIN")];
+      expect(launches[0].market.mint.toBase58()).to.not.equal(launches[1].market.mint.toBase58());
+
+      for (const [index, { market, signature, launchIndex }] of launches.entries()) {
+        expect(launchIndex.toNumber()).to.equal(index);
+        const [mint, mintBump] = PublicKey.findProgramAddressSync(
+          [Buffer.from("mint"), serial.publicKey.toBuffer(), new BN(index).toArrayLike(Buffer, "le", 8)],
+          program.programId
+        );
+        expect(market.mint.toBase58()).to.equal(mint.toBase58());
+
+        const record = await program.account.launchRecord.fetch(
+          PublicKey.findProgramAddressSync([Buffer.from("launch"), mint.toBuffer()], program.programId)[0]
+        );
+        expect(record.mint.toBase58()).to.equal(mint.toBase58());
+        expect(record.pool.toBase58()).to.equal(market.pool.toBase58());
+        expect(record.creator.toBase58()).to.equal(serial.publicKey.toBase58());
+        expect(record.launchIndex.toNumber()).to.equal(index);
+        expect(record.mintBump).to.equal(mintBump);
+        expect(record.symbol).to.equal("TWIN");
+
+        const launched = await getEvent(signature, "tokenLaunched");
+        expect(launched.mint.toBase58()).to.equal(mint.toBase58());
+        expect(launched.launchIndex.toNumber()).to.equal(index);
+        expect(launched.mintBump).to.equal(mintBump);
+      }
+
+      const profile = await program.account.creatorProfile.fetch(getCreatorProfilePDA(serial.publicKey));
+      expect(profile.launchCount.toNumber()).to.equal(2);
+    });
+
+    const launchWithInitialBuy = async (symbol: string, costBasisMode: any) => {
+      const amount = new BN(0.5 * LAMPORTS_PER_SOL);
+      const configBefore = await program.account.curveConfiguration.fetch(curveConfig);
+      const { market, signature } = await launchToken(creator, symbol, { costBasisMode, initialBuySol: amount });
+
+      const fee = amount.muln(configBefore.feeBps).divn(10000);
+      const trade = await getEvent(signature, "tradeExecuted");
+      expect(trade.user.toBase58()).to.equal(creator.publicKey.toBase58());
+      expect(trade.pool.toBase58()).to.equal(market.pool.toBase58());
+      expect(trade.side).to.equal("buy");
+      expect(trade.solAmount.toString()).to.equal(amount.toString());
+      const tokensOut: BN = trade.tokenAmount;
+      expect(tokensOut.toNumber()).to.be.greaterThan(0);
+
+      // Same curve and fee as swap: only the SOL after fees enters the reserve
+      const pool = await program.account.liquidityPool.fetch(market.pool);
+      expect(pool.reserveOne.toString()).to.equal(initialTokenSupply.sub(tokensOut).toString());
+      expect(pool.reserveTwo.toString()).to.equal(new BN(LAMPORTS_PER_SOL).add(amount).sub(fee).toString());
+      const configAfter = await program.account.curveConfiguration.fetch(curveConfig);
+      expect(configAfter.accruedFees.sub(configBefore.accruedFees).toString()).to.equal(fee.toString());
+
+      // The creator's basis is the whole SOL spent, fee included
+      const positionPda = getUserPositionPDA(market.pool, creator.publicKey);
+      const position = await program.account.userPosition.fetch(positionPda);
+      expect(position.totalSol.toString()).to.equal(amount.toString());
+      expect(position.totalTokens.toString()).to.equal(tokensOut.toString());
+      expect(position.buyCount).to.equal(1);
+
+      const balance = await connection.getTokenAccountBalance(
+        getAssociatedTokenAddressSync(market.mint, creator.publicKey)
+      );
+      expect(balance.value.amount).to.equal(tokensOut.toString());
+
+      return { market, positionPda, amount, tokensOut };
+    };
+
+    it("Buys for the creator in the launch transaction", async () => {
+      const { positionPda } = await launchWithInitialBuy("EARLY", { averageCost: {} });
+      expect(await connection.getAccountInfo(getPositionLotsPDA(positionPda))).to.equal(null);
+    });
+
+    it("Opens the creator's first lot when the initial buy is on a FIFO pool", async () => {
+      const { positionPda, amount, tokensOut } = await launchWithInitialBuy("EARLYFIFO", { fifo: {} });
+
+      const lots = await program.account.positionLots.fetch(getPositionLotsPDA(positionPda));
+      expect(lots.position.toBase58()).to.equal(positionPda.toBase58());
+      expect(lots.count).to.equal(1);
+      expect(lots.lots[lots.head].tokens.toString()).to.equal(tokensOut.toString());
+      expect(lots.lots[lots.head].sol.toString()).to.equal(amount.toString());
+    });
+
+    describe("Creator vesting", () => {
+      let vested: Market;
+      const allocation = initialTokenSupply.divn(10);
+
+      const claimVested = (beneficiary: Keypair, market: Market = vested) => {
+        const escrow = getVestingEscrowPDA(market.mint);
+        return program.methods
+          .claimVested()
+          .accounts({
+            vestingEscrow: escrow,
+            pool: market.pool,
+            mint: market.mint,
+            userPosition: getUserPositionPDA(market.pool, beneficiary.publicKey),
+            positionLots: null,
+            poolStats: getPoolStatsPDA(market.pool),
+            escrowTokenAccount: getAssociatedTokenAddressSync(market.mint, escrow, true),
+            beneficiaryTokenAccount: getAssociatedTokenAddressSync(market.mint, beneficiary.publicKey),
+            beneficiary: beneficiary.publicKey,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_PROGRAM_ID
+          })
+          .signers([beneficiary])
+          .rpc();
+      };
+
+      it("Locks the creator allocation in its escrow at launch", async () => {
+        const { market, signature } = await launchToken(creator, "VEST", {
+          creatorVesting: { amount: allocation, cliffSeconds: 3_600, durationSeconds: 86_400 }
+        });
+        vested = market;
+
+        // The pool only gets what remains of the supply
+        const pool = await program.account.liquidityPool.fetch(market.pool);
+        expect(pool.totalSupply.toString()).to.equal(initialTokenSupply.toString());
+        expect(pool.reserveOne.toString()).to.equal(initialTokenSupply.sub(allocation).toString());
+
+        const escrowPda = getVestingEscrowPDA(market.mint);
+        const balance = await connection.getTokenAccountBalance(
+          getAssociatedTokenAddressSync(market.mint, escrowPda, true)
+        );
+        expect(balance.value.amount).to.equal(allocation.toString());
+
+        const escrow = await program.account.vestingEscrow.fetch(escrowPda);
+        const created = await getEvent(signature, "vestingCreated");
+        expect(escrow.mint.toBase58()).to.equal(market.mint.toBase58());
+        expect(escrow.pool.toBase58()).to.equal(market.pool.toBase58());
+        expect(escrow.beneficiary.toBase58()).to.equal(creator.publicKey.toBase58());
+        expect(escrow.totalAmount.toString()).to.equal(allocation.toString());
+        expect(escrow.claimedAmount.toNumber()).to.equal(0);
+        expect(escrow.startTimestamp.toString()).to.equal(created.startTimestamp.toString());
+        expect(escrow.cliffTimestamp.sub(escrow.startTimestamp).toNumber()).to.equal(3_600);
+        expect(escrow.endTimestamp.sub(escrow.startTimestamp).toNumber()).to.equal(86_400);
+      });
+
+      it("Should fail: Claim before the cliff", async () => {
+        try {
+          await claimVested(creator);
+          expect.fail("Should have thrown NothingToClaim error");
+        } catch (error: any) {
+          expect(error.message).to.include("NothingToClaim");
+        }
+      });
+
+      it("Should fail: Claim as someone other than the beneficiary", async () => {
+        try {
+          await claimVested(trader);
+          expect.fail("Should have thrown Unauthorized error");
+        } catch (error: any) {
+          expect(error.message).to.include("Unauthorized");
+        }
+      });
+
+      it("Credits claimed tokens to the creator's position at zero cost, so they can be sold", async () => {
+        // No cliff and a short schedule, so everything unlocks within the test
+        const { market } = await launchToken(creator, "VESTSELL", {
+          creatorVesting: { amount: allocation, cliffSeconds: 0, durationSeconds: 2 }
+        });
+        await sleep(3_000);
+
+        await claimVested(creator, market);
+        const positionPda = getUserPositionPDA(market.pool, creator.publicKey);
+        const position = await program.account.userPosition.fetch(positionPda);
+        expect(position.totalTokens.toString()).to.equal(allocation.toString());
+        expect(position.totalSol.toNumber()).to.equal(0);
+        const stats = await program.account.poolStats.fetch(getPoolStatsPDA(market.pool));
+        expect(stats.holderCount.toNumber()).to.equal(1);
+
+        // Sold under the default reject policy; free tokens never sell at a loss
+        const sold = allocation.divn(4);
+        const trade = await getEvent(await swapOn(market, creator, sold, 1), "tradeExecuted");
+        expect(trade.tokenAmount.toString()).to.equal(sold.toString());
+        const after = await program.account.userPosition.fetch(positionPda);
+        expect(after.totalTokens.toString()).to.equal(allocation.sub(sold).toString());
+        expect(after.totalTaxPaid.toNumber()).to.equal(0);
+      });
+    });
+
+    describe("FIFO cost basis", () => {
+      let fifo: Market;
+      const lotsOf = (wallet: Keypair) =>
+        program.account.positionLots.fetch(getPositionLotsPDA(getUserPositionPDA(fifo.pool, wallet.publicKey)));
+
+      it("Sales take their basis from the oldest lot", async () => {
+        ({ market: fifo } = await launchToken(creator, "FIFOLOT", { costBasisMode: { fifo: {} } }));
+        await getOrCreateAssociatedTokenAccount(connection, trader, fifo.mint, trader.publicKey);
+
+        // The same SOL buys fewer tokens the second time
+        await swapOn(fifo, trader, new BN(0.5 * LAMPORTS_PER_SOL), 2);
+        await swapOn(fifo, trader, new BN(0.5 * LAMPORTS_PER_SOL), 2);
+        const lots = await lotsOf(trader);
+        expect(lots.count).to.equal(2);
+        const [oldest, newest] = [lots.lots[lots.head], lots.lots[(lots.head + 1) % 16]];
+        expect(newest.tokens.lt(oldest.tokens)).to.equal(true);
+
+        const positionPda = getUserPositionPDA(fifo.pool, trader.publicKey);
+        const before = await program.account.userPosition.fetch(positionPda);
+        const sold = oldest.tokens.divn(2);
+        const oldestCost = oldest.sol.mul(sold).div(oldest.tokens);
+        const averageCost = before.totalSol.mul(sold).div(before.totalTokens);
+        expect(oldestCost.lt(averageCost)).to.equal(true);
+
+        await swapOn(fifo, trader, sold, 1);
+
+        const after = await program.account.userPosition.fetch(positionPda);
+        expect(before.totalSol.sub(after.totalSol).toString()).to.equal(oldestCost.toString());
+        const remaining = await lotsOf(trader);
+        expect(remaining.count).to.equal(2);
+        expect(remaining.lots[remaining.head].tokens.toString()).to.equal(oldest.tokens.sub(sold).toString());
+        expect(remaining.lots[remaining.head].sol.toString()).to.equal(oldest.sol.sub(oldestCost).toString());
+        expect(remaining.lots[(remaining.head + 1) % 16].tokens.toString()).to.equal(newest.tokens.toString());
+      });
+
+      it("Should fail: Trade on a FIFO pool without the position lots", async () => {
+        try {
+          await swapOn(fifo, trader, new BN(0.1 * LAMPORTS_PER_SOL), 2, null, null);
+          expect.fail("Should have thrown PositionLotsRequired error");
+        } catch (error: any) {
+          expect(error.message).to.include("PositionLotsRequired");
+        }
+      });
+
+      it("Buys beyond a full lot ring merge into the newest lot", async () => {
+        const collector = Keypair.generate();
+        await fundWallet(collector);
+        await getOrCreateAssociatedTokenAccount(connection, collector, fifo.mint, collector.publicKey);
+
+        const amount = new BN(0.01 * LAMPORTS_PER_SOL);
+        const bought: BN[] = [];
+        for (let i = 0; i < 17; i++) {
+          const trade = await getEvent(await swapOn(fifo, collector, amount, 2), "tradeExecuted");
+          bought.push(trade.tokenAmount);
+        }
+
+        const lots = await lotsOf(collector);
+        expect(lots.count).to.equal(16);
+        const slot = (offset: number) => lots.lots[(lots.head + offset) % 16];
+        expect(slot(0).tokens.toString()).to.equal(bought[0].toString());
+        expect(slot(14).sol.toString()).to.equal(amount.toString());
+        expect(slot(15).tokens.toString()).to.equal(bought[15].add(bought[16]).toString());
+        expect(slot(15).sol.toString()).to.equal(amount.muln(2).toString());
+      });
+
+      it("Closing a FIFO position requires its lots and closes them too", async () => {
+        const seller = Keypair.generate();
+        await fundWallet(seller);
+        await getOrCreateAssociatedTokenAccount(connection, seller, fifo.mint, seller.publicKey);
+        await swapOn(fifo, seller, new BN(0.1 * LAMPORTS_PER_SOL), 2);
+        const positionPda = getUserPositionPDA(fifo.pool, seller.publicKey);
+        const lotsPda = getPositionLotsPDA(positionPda);
+        const { totalTokens } = await program.account.userPosition.fetch(positionPda);
+        await swapOn(fifo, seller, totalTokens, 1);
+
+        const closePosition = (positionLots: PublicKey | null) =>
+          program.methods
+            .closePosition()
+            .accounts({
+              pool: fifo.pool,
+              userPosition: positionPda,
+              positionLots: positionLots,
+              user: seller.publicKey
+            })
+            .signers([seller])
+            .rpc();
+
+        try {
+          await closePosition(null);
+          expect.fail("Should have thrown PositionLotsRequired error");
+        } catch (error: any) {
+          expect(error.message).to.include("PositionLotsRequired");
+        }
+
+        await closePosition(lotsPda);
+        expect(await connection.getAccountInfo(positionPda)).to.equal(null);
+        expect(await connection.getAccountInfo(lotsPda)).to.equal(null);
+      });
+    });
+  });
+
+  describe("Migrations", () => {
+    const migrateConfiguration = (authority: Keypair) =>
+      program.methods
+        .migrateConfiguration()
+        .accounts({
+          dexConfigurationAccount: curveConfig,
+          programData: programData,
+          payer: authority.publicKey,
+          systemProgram: SystemProgram.programId
+        })
+        .signers([authority])
+        .rpc();
+
+    it("Should fail: Migrate the configuration from a wallet that is not the upgrade authority", async () => {
+      try {
+        await migrateConfiguration(user2);
+        expect.fail("Should have thrown Unauthorized error");
+      } catch (error: any) {
+        expect(error.message).to.include("Unauthorized");
+      }
+    });
+
+    it("Should fail: Migrate a configuration already in the current layout", async () => {
+      try {
+        await migrateConfiguration(upgradeAuthority);
+        expect.fail("Should have thrown AccountAlreadyMigrated error");
+      } catch (error: any) {
+        expect(error.message).to.include("AccountAlreadyMigrated");
+      }
+    });
+
+    // Pre-versioning pool and position loaded from tests/fixtures
+    const legacyMint = new PublicKey(Buffer.alloc(32, 7));
+    const legacyOwner = new PublicKey(Buffer.alloc(32, 9));
+    const [legacyPool] = PublicKey.findProgramAddressSync(
+      [Buffer.from(POOL_SEED_PREFIX), legacyMint.toBuffer()],
+      program.programId
+    );
+    const legacyPosition = getUserPositionPDA(legacyPool, legacyOwner);
+
+    const migratePool = () =>
+      program.methods
+        .migratePool()
+        .accounts({
+          pool: legacyPool,
+          mint: legacyMint,
+          payer: user2.publicKey,
+          systemProgram: SystemProgram.programId
+        })
+        .signers([user2])
+        .rpc();
+
+    const migratePosition = () =>
+      program.methods
+        .migratePosition()
+        .accounts({
+          position: legacyPosition,
+          pool: legacyPool,
+          owner: legacyOwner,
+          payer: user2.publicKey,
+          systemProgram: SystemProgram.programId
+        })
+        .signers([user2])
+        .rpc();
+
+    it("Reallocates a legacy pool, keeping its fields and charging the rent to the caller", async () => {
+      const before = await connection.getAccountInfo(legacyPool);
+      expect(before.data.length).to.equal(97);
+      const payerBefore = await connection.getBalance(user2.publicKey);
+
+      await migratePool();
+
+      const after = await connection.getAccountInfo(legacyPool);
+      expect(after.data.length).to.be.greaterThan(before.data.length);
+      const rent = await connection.getMinimumBalanceForRentExemption(after.data.length);
+      expect(after.lamports).to.equal(rent);
+      expect(payerBefore - (await connection.getBalance(user2.publicKey)))
+        .to.be.at.least(after.lamports - before.lamports);
+
+      const pool = await program.account.liquidityPool.fetch(legacyPool);
+      expect(pool.version).to.be.greaterThan(0);
+      expect(pool.tokenOne.toBase58()).to.equal(legacyMint.toBase58());
+      expect(pool.totalSupply.toString()).to.equal("1000000000000000");
+      expect(pool.reserveOne.toString()).to.equal("1000000000000000");
+      expect(pool.reserveTwo.toNumber()).to.equal(30 * LAMPORTS_PER_SOL);
+      expect(pool.costBasisMode).to.deep.equal({ averageCost: {} });
+    });
+
+    it("Should fail: Migrate a pool already in the current layout", async () => {
+      try {
+        await migratePool();
+        expect.fail("Should have thrown AccountAlreadyMigrated error");
+      } catch (error: any) {
+        expect(error.message).to.include("AccountAlreadyMigrated");
+      }
+    });
+
+    it("Reallocates a legacy position, keeping its basis and filling in its seeds", async () => {
+      expect((await connection.getAccountInfo(legacyPosition)).data.length).to.equal(89);
+
+      await migratePosition();
+
+      const after = await connection.getAccountInfo(legacyPosition);
+      expect(after.lamports).to.equal(await connection.getMinimumBalanceForRentExemption(after.data.length));
+      const position = await program.account.userPosition.fetch(legacyPosition);
+      expect(position.version).to.be.greaterThan(0);
+      expect(position.pool.toBase58()).to.equal(legacyPool.toBase58());
+      expect(position.owner.toBase58()).to.equal(legacyOwner.toBase58());
+      expect(position.totalTokens.toString()).to.equal("5000000000");
+      expect(position.totalSol.toNumber()).to.equal(0.1 * LAMPORTS_PER_SOL);
+      expect(position.referrer.toBase58()).to.equal(PublicKey.default.toBase58());
+    });
+
+    it("Should fail: Migrate a position already in the current layout", async () => {
+      try {
+        await migratePosition();
+        expect.fail("Should have thrown AccountAlreadyMigrated error");
+      } catch (error: any) {
+        expect(error.message).to.include("AccountAlreadyMigrated");
+      }
+    });
+  });
+
+  describe("Admin council", () => {
+    const [adminCouncil] = PublicKey.findProgramAddressSync(
+      [Buffer.from("admin_council")],
+      program.programId
+    );
+    const getProposalPDA = (id: number) => {
+      const [proposal] = PublicKey.findProgramAddressSync(
+        [Buffer.from("admin_proposal"), adminCouncil.toBuffer(), new BN(id).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      return proposal;
+    };
+
+    const propose = (proposal: PublicKey, action: any) =>
+      program.methods
+        .proposeAdminAction(action)
+        .accounts({
+          adminCouncil: adminCouncil,
+          proposal: proposal,
+          proposer: admin.publicKey,
+          systemProgram: SystemProgram.programId
+        })
+        .signers([admin])
+        .rpc();
+    const approve = (proposal: PublicKey, member: Keypair) =>
+      program.methods
+        .approveAdminAction()
+        .accounts({
+          adminCouncil: adminCouncil,
+          proposal: proposal,
+          member: member.publicKey
+        })
+        .signers([member])
+        .rpc();
+    const executeProposal = (proposal: PublicKey) =>
+      program.methods
+        .executeAdminAction()
+        .accounts({
+          dexConfigurationAccount: curveConfig,
+          adminCouncil: adminCouncil,
+          proposal: proposal,
+          globalAccount: globalAccount,
+          pool: null,
+          treasuryVault: null,
+          destination: null,
+          symbolRegistry: null,
+          executor: admin.publicKey,
+          systemProgram: SystemProgram.programId
+        })
+        .signers([admin])
+        .rpc();
+
+    it("Upgrade authority installs a 2-of-2 council", async () => {
+      await program.methods
+        .createAdminCouncil([admin.publicKey, user2.publicKey], 2)
+        .accounts({
+          dexConfigurationAccount: curveConfig,
+          adminCouncil: adminCouncil,
+          programData: programData,
+          admin: upgradeAuthority.publicKey,
+          systemProgram: SystemProgram.programId
+        })
+        .signers([upgradeAuthority])
+        .rpc();
+
+      const configAccount = await program.account.curveConfiguration.fetch(curveConfig);
+      expect(configAccount.adminCouncil.toBase58()).to.equal(adminCouncil.toBase58());
+    });
+
+    it("Direct update_configuration is rejected once a council exists", async () => {
+      try {
+        await program.methods
+          .updateConfiguration(treasuryVault, 200)
+          .accounts({
+            dexConfigurationAccount: curveConfig,
+            programData: programData,
+            admin: upgradeAuthority.publicKey
+          })
+          .signers([upgradeAuthority])
+          .rpc();
+        expect.fail("Should have thrown CouncilApprovalRequired error");
+      } catch (error: any) {
+        expect(error.message).to.include("CouncilApprovalRequired");
+      }
+    });
+
+    it("Fee change applies only after threshold approvals", async () => {
+      const proposal = getProposalPDA(0);
+
+      await program.methods
+        .proposeAdminAction({ setFees: { feeBps: 200 } })
+        .accounts({
+          adminCouncil: adminCouncil,
+          proposal: proposal,
+          proposer: admin.publicKey,
+          systemProgram: SystemProgram.programId
+        })
+        .signers([admin])
+        .rpc();
+
+      const execute = () =>
+        program.methods
+          .executeAdminAction()
+          .accounts({
+            dexConfigurationAccount: curveConfig,
+            adminCouncil: adminCouncil,
+            proposal: proposal,
+            globalAccount: globalAccount,
+            pool: null,
+            treasuryVault: null,
+            destination: null,
+            symbolRegistry: null,
+            executor: admin.publicKey,
+            systemProgram: SystemProgram.programId
+          })
+          .signers([admin])
+          .rpc();
+
+      try {
+        await execute();
+        expect.fail("Should have thrown ThresholdNotReached error");
+      } catch (error: any) {
+        expect(error.message).to.include("ThresholdNotReached");
+      }
+
+      await program.methods
+        .approveAdminAction()
+        .accounts({
+          adminCouncil: adminCouncil,
+          proposal: proposal,
+          member: user2.publicKey
+        })
+        .signers([user2])
+        .rpc();
+
+      await execute();
+
+      const configAccount = await program.account.curveConfiguration.fetch(curveConfig);
+      expect(configAccount.feeBps).to.equal(200);
+    });
+
+    it("Any member turns pause switches on, but turning them off takes the threshold", async () => {
+      const setPause = (pauseFlags: number, member: Keypair) =>
+        program.methods
+          .setPause(pauseFlags)
+          .accounts({
+            dexConfigurationAccount: curveConfig,
+            pool: null,
+            programData: null,
+            adminCouncil: adminCouncil,
+            admin: member.publicKey
+          })
+          .signers([member])
+          .rpc();
+
+      await setPause(PAUSE_BUY, user2);
+      expect((await program.account.curveConfiguration.fetch(curveConfig)).pauseFlags).to.equal(PAUSE_BUY);
+
+      try {
+        await setPause(0, user2);
+        expect.fail("Should have thrown CouncilApprovalRequired error");
+      } catch (error: any) {
+        expect(error.message).to.include("CouncilApprovalRequired");
+      }
+
+      try {
+        await setPause(PAUSE_BUY | PAUSE_SELL, Keypair.generate());
+        expect.fail("Should have thrown NotCouncilMember error");
+      } catch (error: any) {
+        expect(error.message).to.include("NotCouncilMember");
+      }
+
+      const proposal = getProposalPDA(1);
+      await propose(proposal, { setPauseFlags: { pauseFlags: 0 } });
+      await approve(proposal, user2);
+      await executeProposal(proposal);
+      expect((await program.account.curveConfiguration.fetch(curveConfig)).pauseFlags).to.equal(0);
+    });
+
+    it("Rotating the council invalidates proposals made under the old members", async () => {
+      const newMember = Keypair.generate();
+      const pending = getProposalPDA(2);
+      const rotation = getProposalPDA(3);
+
+      await propose(pending, { setFees: { feeBps: 300 } });
+      await propose(rotation, { setCouncil: { members: [admin.publicKey, newMember.publicKey], threshold: 1 } });
+      await approve(rotation, user2);
+      await executeProposal(rotation);
+
+      const council = await program.account.adminCouncil.fetch(adminCouncil);
+      expect(council.memberCount).to.equal(2);
+      expect(council.members[1].toBase58()).to.equal(newMember.publicKey.toBase58());
+      expect(council.threshold).to.equal(1);
+      expect(council.firstValidProposal.toNumber()).to.equal(4);
+
+      // The new member would take the removed member's approval bit
+      try {
+        await approve(pending, newMember);
+        expect.fail("Should have thrown StaleProposal error");
+      } catch (error: any) {
+        expect(error.message).to.include("StaleProposal");
+      }
+    });
+  });
 });